@@ -6,9 +6,6 @@ impl Generator for X64 {
     fn push(&self, reg: &str) -> String {
         format!("  push %{}\n", reg)
     }
-    fn pop(&self, reg: &str) -> String {
-        format!("  pop %{}\n", reg)
-    }
     fn label(&self, no: usize) -> String {
         format!(".L{}:\n", no)
     }
@@ -24,14 +21,11 @@ impl Generator for X64 {
     fn cmpl(&self, f: usize, r: &str) -> String {
         format!("  cmp ${}, %{}\n", f, r)
     }
-    fn mul(&self, reg: &str) -> String {
-        format!("  mul %{}\n", reg)
-    }
     fn multiple(&self) -> String {
-        "  imul %ecx\n".to_string()
+        "  imul %rcx, %rax\n".to_string()
     }
     fn plus(&self) -> String {
-        "  add %ecx, %eax\n".to_string()
+        "  add %rcx, %rax\n".to_string()
     }
     fn minus(&self) -> String {
         "  sub %rcx, %rax\n".to_string()
@@ -60,6 +54,9 @@ impl Generator for X64 {
     fn right_shift(&self) -> String {
         "  sar %cl, %rax\n".to_string()
     }
+    fn logical_right_shift(&self) -> String {
+        "  shr %cl, %rax\n".to_string()
+    }
     fn bit_and(&self) -> String {
         "  and %rcx, %rax\n".to_string()
     }
@@ -69,8 +66,17 @@ impl Generator for X64 {
     fn bit_xor(&self) -> String {
         "  xor %rcx, %rax\n".to_string()
     }
+    fn or(&self, src: &str, dst: &str) -> String {
+        format!("  or %{}, %{}\n", src, dst)
+    }
+    fn shl_imm(&self, i: usize, reg: &str) -> String {
+        format!("  shl ${}, %{}\n", i, reg)
+    }
+    fn sar_imm(&self, i: usize, reg: &str) -> String {
+        format!("  sar ${}, %{}\n", i, reg)
+    }
     fn bit_division(&self) -> String {
-        "  mov $0, %rdx\n  idiv %rcx\n".to_string()
+        "  cqo\n  idiv %rcx\n".to_string()
     }
     fn lea(&self, p: i64) -> String {
         format!("  lea -{}(%rbp), %rax\n", p)
@@ -78,27 +84,18 @@ impl Generator for X64 {
     fn lea_glb(&self, n: &str) -> String {
         format!("  lea {}(%rip), %rax\n", n)
     }
+    fn str_addr(&self, no: usize) -> String {
+        format!("  movq $.LC{}, %rax\n", no)
+    }
     fn not(&self, reg: &str) -> String {
         format!("  not %{}\n", reg)
     }
-    fn set(&self, reg: &str) -> String {
-        format!("  sete %{}\n", reg)
-    }
     fn neg(&self, reg: &str) -> String {
         format!("  neg %{}\n", reg)
     }
-    fn sub(&self, src: &str, dst: &str) -> String {
-        format!("  sub %{}, %{}\n", src, dst)
-    }
     fn sub_imm(&self, i: usize, reg: &str) -> String {
         format!("  sub ${}, %{}\n", i, reg)
     }
-    fn add(&self, src: &str, dst: &str) -> String {
-        format!("  add %{}, %{}\n", src, dst)
-    }
-    fn add_src(&self, src: &str, dst: &str, n: i64) -> String {
-        format!("  add {}(%{}), %{}\n", n, src, dst)
-    }
     fn add_imm(&self, i: usize, reg: &str) -> String {
         format!("  add ${}, %{}\n", i, reg)
     }
@@ -108,29 +105,30 @@ impl Generator for X64 {
     fn mov(&self, src: &str, dst: &str) -> String {
         format!("  mov %{}, %{}\n", src, dst)
     }
-    fn movq(&self, src: &str, dst: &str) -> String {
-        format!("  movq %{}, %{}\n", src, dst)
-    }
     fn mov_src(&self, src: &str, dst: &str, n: i64) -> String {
         format!("  mov {}(%{}), %{}\n", n, src, dst)
     }
     fn movl_src(&self, src: &str, dst: &str, n: i64) -> String {
         format!("  movl {}(%{}), %{}\n", n, src, dst)
     }
-    fn movq_src(&self, src: &str, dst: &str, n: i64) -> String {
-        format!("  movq {}(%{}), %{}\n", n, src, dst)
-    }
     fn mov_dst(&self, src: &str, dst: &str, n: i64) -> String {
         format!("  mov %{}, {}(%{})\n", src, n, dst)
     }
-    fn mov_imm(&self, dst: &str, n: i64) -> String {
-        format!("  mov ${}, %{}\n", n, dst)
+    fn movl_dst(&self, src: &str, dst: &str, n: i64) -> String {
+        format!("  movl %{}, {}(%{})\n", src, n, dst)
     }
-    fn mov_imm_dst(&self, dst: &str, n: i64, offset: i64) -> String {
-        format!("  movq ${}, {}(%{})\n", n, offset, dst)
+    fn movzb_src(&self, src: &str, dst: &str, n: i64) -> String {
+        format!("  movzbq {}(%{}), %{}\n", n, src, dst)
     }
-    fn movz(&self, src: &str, dst: &str) -> String {
-        format!("  movzb %{}, %{}\n", src, dst)
+    // 符号拡張して転送
+    fn movslq_src(&self, src: &str, dst: &str, n: i64) -> String {
+        format!("  movslq {}(%{}), %{}\n", n, src, dst)
+    }
+    fn movsbq_src(&self, src: &str, dst: &str, n: i64) -> String {
+        format!("  movsbq {}(%{}), %{}\n", n, src, dst)
+    }
+    fn mov_imm(&self, dst: &str, n: i64) -> String {
+        format!("  mov ${}, %{}\n", n, dst)
     }
     // %srcからn(%dst)へ転送
     fn movb_dst(&self, src: &str, dst: &str, n: i64) -> String {
@@ -140,27 +138,44 @@ impl Generator for X64 {
         format!("  movb {}(%{}), %{}\n", n, src, dst)
     }
     // n(%src)から%dstへ転送
-    fn movsbl_src(&self, src: &str, dst: &str, n: i64) -> String {
-        format!("  movsbl {}(%{}), %{}\n", n, src, dst)
-    }
     // global変数からの代入
-    fn mov_from_glb(&self, dst: &str, name: &str) -> String {
-        format!("  mov {}(%rip), %{}\n", name, dst)
-    }
-    fn movb_from_glb(&self, dst: &str, name: &str) -> String {
-        format!("  mov {}(%rip), %{}\n", name, dst)
-    }
     // global変数の代入
-    fn mov_to_glb(&self, src: &str, name: &str) -> String {
-        format!("  mov %{}, {}(%rip)\n", src, name)
-    }
-    fn movb_to_glb(&self, src: &str, name: &str) -> String {
-        format!("  movb %{}, {}(%rip)\n", src, name)
-    }
     fn call(&self, a: &str) -> String {
         format!("  call {}\n", a)
     }
+    fn call_indirect(&self, reg: &str) -> String {
+        format!("  call *%{}\n", reg)
+    }
     fn leave(&self) -> String {
         "  leave\n".to_string()
     }
+    fn data_section(&self) -> String {
+        "  .data\n".to_string()
+    }
+    fn bss_section(&self) -> String {
+        "  .bss\n".to_string()
+    }
+    fn rodata_section(&self) -> String {
+        "  .section .rodata\n".to_string()
+    }
+    fn align(&self, n: usize) -> String {
+        format!("  .align {}\n", n)
+    }
+    fn object_type(&self, name: &str) -> String {
+        format!("  .type {}, @object\n", name)
+    }
+    fn object_size(&self, name: &str, n: usize) -> String {
+        format!("  .size {}, {}\n", name, n)
+    }
+    fn zero(&self, n: usize) -> String {
+        format!("  .zero {}\n", n)
+    }
+    fn data(&self, size: usize, v: &str) -> String {
+        match size {
+            1 => format!("  .byte {}\n", v),
+            2 => format!("  .short {}\n", v),
+            4 => format!("  .long {}\n", v),
+            _ => format!("  .quad {}\n", v),
+        }
+    }
 }
@@ -1,102 +1,57 @@
 use arch::Generator;
 use arch::{x64::X64};
 use arch::{x64_mac::X64Mac};
-use ast::{AstTree, AstType};
 use config::Config;
-use std::process;
-use symbol::{Scope, Structure, Symbol, SymbolTable, Type};
+use ir::{Addr, Arg, BinOp, Block, Callee, Function, Init, InitValue, Inst, Module, Object, Reg, RetKind, Term, Ty, UnOp};
+use symbol::align_to;
 
-#[doc = "ラベル管理"]
-struct Label {
-    label_no: usize,
-    continue_labels: Vec<usize>,
-    break_labels: Vec<usize>,
-    return_label: usize,
+// 関数引数レジスタ.
+const REGS: &[&str] = &["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+
+#[doc = "構造体の受け渡しクラス(System V ABI)"]
+// 浮動小数点型は扱わないので、SSEクラスにはならない
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArgClass {
+    Integer(usize), // 汎用レジスタ渡し（eightbyte数）
+    Memory,         // メモリ渡し
 }
 
-impl Label {
-    // コンストラクタ.
-    pub fn new() -> Self {
-        Label {
-            label_no: 0,
-            continue_labels: vec![],
-            break_labels: vec![],
-            return_label: 0,
+impl ArgClass {
+    // サイズからクラスを判定（16バイトを超える場合はメモリ渡し）.
+    fn classify(size: usize) -> Self {
+        if size > 16 {
+            ArgClass::Memory
+        } else {
+            ArgClass::Integer(align_to(size, 8) / 8)
         }
     }
-
-    // ラベル番号インクリメント.
-    pub fn next_label(&mut self) -> usize {
-        self.label_no += 1;
-        self.label_no
-    }
-
-    // returnラベル取得.
-    pub fn next_return_label(&mut self) -> usize {
-        self.return_label = self.next_label();
-        self.return_label
-    }
-    pub fn get_return_label(&self) -> usize {
-        self.return_label
-    }
-
-    // continueラベル追加.
-    pub fn push_continue(&mut self, no: usize) {
-        self.continue_labels.push(no);
-    }
-    // continueラベルpop.
-    pub fn pop_continue(&mut self) -> Option<usize> {
-        self.continue_labels.pop()
-    }
-    // continueラベル削除.
-    pub fn remove_continue(&mut self, no: usize) {
-        self.continue_labels = self
-            .continue_labels
-            .iter()
-            .cloned()
-            .filter(|d| *d != no)
-            .collect();
-    }
-    // breakラベル追加.
-    pub fn push_break(&mut self, no: usize) {
-        self.break_labels.push(no);
-    }
-    // breakラベルpop.
-    pub fn pop_break(&mut self) -> Option<usize> {
-        self.break_labels.pop()
-    }
-    // breakラベル削除.
-    pub fn remove_break(&mut self, no: usize) {
-        self.break_labels = self
-            .break_labels
-            .iter()
-            .cloned()
-            .filter(|d| *d != no)
-            .collect();
-    }
 }
 
-// 関数引数レジスタ.
-const REGS: &[&str] = &["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+// 引数の配置先.
+enum ArgPlace {
+    Reg(usize, usize),   // 先頭レジスタ番号、サイズ
+    Stack(usize, usize), // スタック上のオフセット、サイズ
+}
 
 #[doc = "アセンブラ生成部"]
-pub struct Asm<'a> {
+// 中間表現から生成する（仮想レジスタは全てスタック上の退避領域へ割り当てる）
+pub struct Asm {
     inst: String,
     const_literal: String,
-    sym_table: &'a SymbolTable,
-    cur_scope: Scope,
-    label: Label,
+    label_no: usize, // ブロックのラベル番号の基点
+    ret_ptr: usize,  // 構造体の格納先アドレス退避位置（メモリ渡しの場合）
+    reg_pos: usize,  // 仮想レジスタの退避領域の開始位置
 }
 
-impl<'a> Asm<'a> {
+impl Asm {
     // コンストラクタ.
-    pub fn new(table: &'a SymbolTable) -> Asm<'a> {
+    pub fn new() -> Asm {
         Asm {
             inst: "".to_string(),
             const_literal: "".to_string(),
-            label: Label::new(),
-            sym_table: table,
-            cur_scope: Scope::Unknown,
+            label_no: 0,
+            ret_ptr: 0,
+            reg_pos: 0,
         }
     }
 
@@ -116,155 +71,104 @@ impl<'a> Asm<'a> {
     }
 
     // アセンブラ生成開始.
-    pub fn exec(&mut self, tree: &AstTree) {
-        tree.get_tree().iter().for_each(|a| self.generate(a));
+    pub fn exec(&mut self, m: &Module) {
+        m.strings.iter().for_each(|(i, s)| self.generate_string_literal(*i, s));
+        m.objects.iter().for_each(|o| self.generate_object(o));
+        m.funcs.iter().for_each(|f| self.generate_function(f));
     }
 
-    // 現在スコープ切り替え
-    fn switch_scope(&mut self, scope: Scope) {
-        self.cur_scope = scope;
+    // 文字列リテラル生成
+    fn generate_string_literal(&mut self, i: usize, s: &str) {
+        self.const_literal = format!("{}  .text\n", self.const_literal);
+        self.const_literal = format!("{}.LC{}:\n", self.const_literal, i);
+        self.const_literal = format!("{}  .string \"{}\"\n", self.const_literal, s);
+    }
+
+    // 静的領域のオブジェクト生成
+    //
+    // 初期化子があれば.data、なければ.bssへサイズ、アライメントを考慮して配置（constは.rodata）
+    fn generate_object(&mut self, o: &Object) {
+        let linkage = match o.export {
+            true => format!(".global {}\n", o.label),
+            false => "".to_string(),
+        };
+        let (section, body) = match o.init {
+            Some(ref data) => (self.gen_asm().data_section(), self.global_data(o.size, data)),
+            None => (self.gen_asm().bss_section(), self.gen_asm().zero(o.size)),
+        };
+        let section = if o.readonly { self.gen_asm().rodata_section() } else { section };
+        self.inst = format!(
+            "{}{}{}{}{}{}{}:\n{}",
+            self.inst,
+            section,
+            linkage,
+            self.gen_asm().align(o.align),
+            self.gen_asm().object_type(&o.label),
+            self.gen_asm().object_size(&o.label, o.size),
+            o.label,
+            body
+        );
     }
 
-    // アセンブラ生成.
-    fn generate(&mut self, ast: &AstType) {
-        match *ast {
-            AstType::Global(ref a) => {
-                self.switch_scope(Scope::Global);
-                self.generate_global(a);
-            }
-            AstType::FuncDef(ref t, ref _s, ref a, ref b, ref c) => {
-                self.switch_scope(Scope::Local(a.clone()));
-                self.generate_funcdef(t, a, b, c);
-            }
-            AstType::FuncCall(ref a, ref b) => self.generate_call_func(a, b),
-            AstType::Statement(_) => self.generate_statement(ast),
-            AstType::While(ref a, ref b) => self.generate_statement_while(a, b),
-            AstType::Do(ref a, ref b) => self.generate_statement_do(a, b),
-            AstType::If(ref a, ref b, ref c) => self.generate_statement_if(a, b, c),
-            AstType::For(ref a, ref b, ref c, ref d) => self.generate_statement_for(a, b, c, d),
-            AstType::Continue() => self.generate_statement_continue(),
-            AstType::Break() => self.generate_statement_break(),
-            AstType::Return(ref a) => self.generate_statement_return(a),
-            AstType::SizeOf(a) => self.generate_sizeof(a),
-            AstType::Factor(a) => self.generate_factor(a),
-            AstType::LogicalAnd(ref a, ref b) => self.generate_logical_and(a, b),
-            AstType::LogicalOr(ref a, ref b) => self.generate_logical_or(a, b),
-            AstType::Condition(ref a, ref b, ref c) => self.generate_condition(a, b, c),
-            AstType::UnPlus(ref a) => self.generate_unplus(a),
-            AstType::UnMinus(ref a) => self.generate_unminus(a),
-            AstType::Not(ref a) => self.generate_not(a),
-            AstType::BitReverse(ref a) => self.generate_bit_reverse(a),
-            AstType::Assign(ref a, ref b) => self.generate_assign(a, b),
-            AstType::PlusAssign(ref a, ref b) => self.generate_plus_assign(a, b),
-            AstType::MinusAssign(ref a, ref b) => self.generate_minus_assign(a, b),
-            AstType::MultipleAssign(ref a, ref b) => self.generate_multiple_assign(a, b),
-            AstType::DivisionAssign(ref a, ref b) => self.generate_division_assign(a, b),
-            AstType::RemainderAssign(ref a, ref b) => self.generate_remainder_assign(a, b),
-            AstType::Variable(_, _, _) => self.generate_variable(ast),
-            AstType::PreInc(ref a) => self.generate_pre_inc(a),
-            AstType::PreDec(ref a) => self.generate_pre_dec(a),
-            AstType::PostInc(ref a) => self.generate_post_inc(a),
-            AstType::PostDec(ref a) => self.generate_post_dec(a),
-            AstType::Plus(ref a, ref b) => self.generate_plus(a, b),
-            AstType::Minus(ref a, ref b) => self.generate_minus(a, b),
-            AstType::Multiple(ref a, ref b)
-            | AstType::Division(ref a, ref b)
-            | AstType::Remainder(ref a, ref b)
-            | AstType::Equal(ref a, ref b)
-            | AstType::NotEqual(ref a, ref b)
-            | AstType::LessThan(ref a, ref b)
-            | AstType::GreaterThan(ref a, ref b)
-            | AstType::LessThanEqual(ref a, ref b)
-            | AstType::GreaterThanEqual(ref a, ref b)
-            | AstType::LeftShift(ref a, ref b)
-            | AstType::RightShift(ref a, ref b)
-            | AstType::BitAnd(ref a, ref b)
-            | AstType::BitOr(ref a, ref b)
-            | AstType::BitXor(ref a, ref b) => self.generate_operator(ast, a, b),
-            AstType::Address(ref a) => self.generate_address(a),
-            AstType::Indirect(ref a) => self.generate_indirect(a),
-            AstType::StringLiteral(ref s, ref i) => {
-                self.generate_string_literal(&AstType::StringLiteral(s.to_string(), *i));
-                self.generate_string(s, *i);
-            }
-            AstType::Struct(ref _a, ref _b) => {}, // 構造体定義のみなので、現状は何もしない
-            _ => panic!("{} {}: not support expression {:?}", file!(), line!(), ast),
+    // 初期化データからデータ定義を生成（隙間はゼロ埋め）
+    fn global_data(&self, size: usize, data: &[Init]) -> String {
+        let (body, pos) = data.iter().fold((String::new(), 0), |(acc, pos), d| {
+            let pad = if d.offset > pos { self.gen_asm().zero(d.offset - pos) } else { "".to_string() };
+            (format!("{}{}{}", acc, pad, self.gen_asm().data(d.size, &self.init_value(&d.value))), d.offset + d.size)
+        });
+        if size > pos {
+            format!("{}{}", body, self.gen_asm().zero(size - pos))
+        } else {
+            body
         }
     }
 
-    // グローバル変数代入
-    fn generate_global_assign(&mut self, a: &AstType, b: &AstType) {
-        // 左辺が変数、右辺は数字をサポート
-        match b {
-            AstType::Factor(i) => match a {
-                AstType::Variable(ref t, _, ref name) => {
-                    self.inst = format!("{}{}:\n", self.inst, name);
-                    self.inst = match t {
-                        Type::Int =>  format!("{}  .long {}\n", self.inst, i),
-                        Type::Char => format!("{}  .byte {}\n", self.inst, i),
-                        _ => panic!("{}{}: cannot support type {:?}", file!(), line!(), t)
-                    }
-                }
-                _ => panic!("{}{}: cannot support AstType {:?}", file!(), line!(), b)
-            }
-            _ => panic!("{}{}: cannot support AstType {:?}", file!(), line!(), a)
+    // 初期化値生成
+    fn init_value(&self, v: &InitValue) -> String {
+        match v {
+            InitValue::Int(n) => n.to_string(),
+            InitValue::Addr(ref a, 0) => self.symbol(a),
+            InitValue::Addr(ref a, n) => format!("{}+{}", self.symbol(a), n),
         }
     }
 
-    // グローバル変数定義
-    fn generate_global(&mut self, a: &[AstType]) {
-        self.inst = format!("{}{}", self.inst, "  .data\n");
-        a.iter().for_each(|d| {
-            match d {
-                AstType::Assign(ref a, ref b) => self.generate_global_assign(a, b),
-                AstType::Variable(_, _, ref name) => {
-                    self.inst = format!("{}{}:\n", self.inst, name);
-                    self.inst = format!("{}  .zero 8\n", self.inst);
-                }
-                AstType::Struct(_, _) => {}, // 構造体定義のみなのでSKIP
-                _ => panic!("{}{}: cannot support AstType {:?}", file!(), line!(), d)
-            }
-        });
+    // アドレスの基点のシンボル取得
+    fn symbol(&self, a: &Addr) -> String {
+        match a {
+            Addr::Global(ref l) => l.clone(),
+            Addr::Func(ref n) => self.generate_func_symbol(n),
+            Addr::Str(i) => format!(".LC{}", i),
+            Addr::Local(_) => panic!("{} {}: local address is not constant", file!(), line!()),
+        }
     }
 
     // 関数定義.
-    fn generate_funcdef(&mut self, _t: &Type, a: &str, b: &AstType, c: &AstType) {
-        // return文のラベルを生成.
-        let return_label = self.label.next_return_label();
-
-        self.generate_func_start(a);
-        self.generate_func_args(b);
-        self.generate_statement(c);
-        self.generate_label_inst(return_label);
-        self.generate_func_end();
-    }
+    //
+    // ローカル変数、一時領域の後ろに、構造体の格納先アドレス退避領域、仮想レジスタの退避領域を確保
+    fn generate_function(&mut self, f: &Function) {
+        self.ret_ptr = match f.ret {
+            RetKind::Struct(size) if ArgClass::classify(size) == ArgClass::Memory => f.frame_size + 8,
+            _ => f.frame_size,
+        };
+        self.reg_pos = self.ret_ptr;
 
-    // statement生成.
-    fn generate_statement(&mut self, a: &AstType) {
-        // 各AstTypeを処理.
-        match *a {
-            AstType::Statement(ref s) => s.iter().for_each(|ast| {
-                self.generate(ast);
-                if ast.is_expr() {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                }
-            }),
-            _ => panic!("{} {}: not support expr", file!(), line!()),
-        }
+        self.generate_func_start(&f.name, f.export, self.reg_pos + 8 * f.regs.len());
+        self.generate_func_args(f);
+        f.blocks.iter().enumerate().for_each(|(i, b)| self.generate_block(f, i, b));
+        self.label_no += f.blocks.len();
     }
 
     // 関数開始アセンブラ出力.
-    fn generate_func_start(&mut self, a: &str) {
-        // スタート部分設定.
-        let mut start = if a == "main" {
+    fn generate_func_start(&mut self, a: &str, export: bool, size: usize) {
+        // スタート部分設定（static関数以外は外部から参照可能とする）.
+        let mut start = if export {
             format!("  .text\n.global {}\n", self.generate_func_symbol(a))
         } else {
             "  .text\n".to_string()
         };
 
         // 16バイトアライメント
-        let mut pos = self.sym_table.size(&Scope::Local(a.to_string())) ;
-        pos = (pos / 16) * 16 + 16;
+        let pos = (size / 16) * 16 + 16;
         start = format!("{}{}{}:\n", self.inst, start, self.generate_func_symbol(a));
         start = format!(
             "{}{}{}{}",
@@ -276,468 +180,352 @@ impl<'a> Asm<'a> {
         self.inst = start;
     }
 
-    // 関数終了部分アセンブラ生成
-    fn generate_func_end(&mut self) {
-        self.inst = format!(
-            "{}{}{}",
-            self.inst,
-            self.gen_asm().leave(),
-            self.gen_asm().ret()
-        );
-    }
-
     // 関数引数生成.
-    fn generate_func_args(&mut self, a: &AstType) {
-        // レジスタからスタックへ引数を移動(SPを8バイトずつ移動しながら).
-        let st = 8;
-        match *a {
-            AstType::Argment(ref args) => {
-                args.iter().zip(REGS.iter()).fold(st, |p, d| {
-                    match d.0 {
-                        AstType::Variable(_, s, _) if *s == Structure::Pointer => {
-                            self.inst = format!(
-                                "{}{}",
-                                self.inst,
-                                self.gen_asm().mov_dst(&d.1, "rbp", -(p as i64))
-                            );
-                        }
-                        _ => {
-                            self.inst = format!(
-                                "{}{}{}",
-                                self.inst,
-                                self.gen_asm().mov(&d.1, "rax"),
-                                self.gen_asm().mov_dst("rax", "rbp", -(p as i64))
-                            );
-                        }
-                    };
-                    p + 8
-                });
+    //
+    // レジスタ、又はスタック(16(%rbp)以降)で渡された引数をローカル変数の領域へ移動
+    fn generate_func_args(&mut self, f: &Function) {
+        // メモリ渡しの構造体を返す場合、格納先アドレスが第1引数となる
+        let mut reg = 0;
+        if self.ret_ptr != f.frame_size {
+            self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst(REGS[0], "rbp", -(self.ret_ptr as i64)));
+            reg = 1;
+        }
+
+        let mut stack = 16;
+        f.params.iter().for_each(|p| {
+            let pos = -(p.offset as i64);
+            let (class, size) = match p.size {
+                Some(size) => (ArgClass::classify(size), align_to(size, 8)),
+                None => (ArgClass::Integer(1), 8),
+            };
+            match class {
+                ArgClass::Integer(n) if reg + n <= REGS.len() => {
+                    (0..n).for_each(|i| {
+                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst(REGS[reg + i], "rbp", pos + 8 * i as i64));
+                    });
+                    reg += n;
+                }
+                _ => {
+                    // スタック渡しの引数は8バイト単位で配置されている
+                    (0..size / 8).for_each(|i| {
+                        let n = 8 * i as i64;
+                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rbp", "rax", stack + n));
+                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rbp", pos + n));
+                    });
+                    stack += size as i64;
+                }
             }
-            _ => panic!("{} {}: not support expr {:?}", file!(), line!(), a),
-        }
+        });
     }
 
-    // if statement生成.
-    fn generate_statement_if(&mut self, a: &AstType, b: &AstType, c: &Option<AstType>) {
-        let label_end = self.label.next_label();
-
-        // 条件式部分生成.
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(1, "rax"); // 等しい場合は、1に設定されている.
-
-        // elseブロック生成.
-        match c {
-            Some(e) => {
-                // if条件が満たされているとき、ifラベルへ
-                let label_if = self.label.next_label();
-                self.generate_je_inst(label_if);
-
-                // elseブロック生成.
-                // block部はAstType::Statementなので、演算結果に対するスタック操作は行わない.
-                self.generate(e);
-                self.generate_jmp_inst(label_end);
-
-                // ifブロック部生成.
-                // block部はAstType::Statementなので、演算結果に対するスタック操作は行わない.
-                self.generate_label_inst(label_if);
-                self.generate(b);
-                self.generate_jmp_inst(label_end);
+    // ブロックのラベル番号取得.
+    fn block_label(&self, b: usize) -> usize {
+        self.label_no + b + 1
+    }
+
+    // 仮想レジスタの退避位置取得.
+    fn reg_offset(&self, r: Reg) -> i64 {
+        -((self.reg_pos + 8 * (r + 1)) as i64)
+    }
+
+    // 仮想レジスタの値を読み込み.
+    fn load_reg(&mut self, r: Reg, dst: &str) {
+        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rbp", dst, self.reg_offset(r)));
+    }
+
+    // 仮想レジスタへ退避.
+    fn store_reg(&mut self, src: &str, r: Reg) {
+        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst(src, "rbp", self.reg_offset(r)));
+    }
+
+    // 基本ブロック生成.
+    fn generate_block(&mut self, f: &Function, i: usize, b: &Block) {
+        self.inst = format!("{}{}", self.inst, self.gen_asm().label(self.block_label(i)));
+        b.insts.iter().for_each(|inst| self.generate_inst(inst));
+        match b.term {
+            Some(ref t) => self.generate_term(f, t, i + 1),
+            None => panic!("{} {}: block without terminator in {}", file!(), line!(), f.name),
+        }
+    }
+
+    // 命令生成.
+    //
+    // 被演算子はrax、rcxへ読み込み、結果はraxから仮想レジスタへ退避する
+    fn generate_inst(&mut self, inst: &Inst) {
+        match *inst {
+            Inst::Const(d, v) => {
+                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", v));
+                self.store_reg("rax", d);
+            }
+            Inst::Addr(d, ref a) => {
+                self.inst = match a {
+                    Addr::Local(n) => format!("{}{}", self.inst, self.gen_asm().lea(*n as i64)),
+                    Addr::Str(i) => format!("{}{}", self.inst, self.gen_asm().str_addr(*i)),
+                    _ => format!("{}{}", self.inst, self.gen_asm().lea_glb(&self.symbol(a))),
+                };
+                self.store_reg("rax", d);
+            }
+            Inst::Load(d, ty, a, _) => {
+                self.load_reg(a, "rcx");
+                self.inst = match ty {
+                    Ty::I8 => format!("{}{}", self.inst, self.gen_asm().movsbq_src("rcx", "rax", 0)),
+                    Ty::I32 => format!("{}{}", self.inst, self.gen_asm().movslq_src("rcx", "rax", 0)),
+                    _ => format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0)),
+                };
+                self.store_reg("rax", d);
+            }
+            // 隣接する領域を壊さないよう、I8、I32は下位のレジスタのみ転送
+            Inst::Store(ty, v, a, _) => {
+                self.load_reg(v, "rax");
+                self.load_reg(a, "rcx");
+                self.inst = match ty {
+                    Ty::I8 => format!("{}{}", self.inst, self.gen_asm().movb_dst("al", "rcx", 0)),
+                    Ty::I32 => format!("{}{}", self.inst, self.gen_asm().movl_dst("eax", "rcx", 0)),
+                    _ => format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0)),
+                };
+            }
+            Inst::Bin(d, op, a, b) => {
+                self.load_reg(a, "rax");
+                self.load_reg(b, "rcx");
+                self.inst = format!("{}{}", self.inst, self.operator(op));
+                self.store_reg(if op == BinOp::Rem { "rdx" } else { "rax" }, d);
+            }
+            Inst::Un(d, op, a) => {
+                self.load_reg(a, "rax");
+                self.inst = match op {
+                    UnOp::Neg => format!("{}{}", self.inst, self.gen_asm().neg("rax")),
+                    UnOp::Not => format!("{}{}", self.inst, self.gen_asm().not("rax")),
+                };
+                self.store_reg("rax", d);
+            }
+            // int、charへの変換は変換先の幅で切り詰めて符号拡張する
+            Inst::Conv(d, ty, a) => {
+                self.load_reg(a, "rax");
+                let bits = match ty {
+                    Ty::I8 => 8,
+                    Ty::I32 => 32,
+                    _ => 64,
+                };
+                if bits < 64 {
+                    self.inst = format!("{}{}", self.inst, self.gen_asm().shl_imm(64 - bits, "rax"));
+                    self.inst = format!("{}{}", self.inst, self.gen_asm().sar_imm(64 - bits, "rax"));
+                }
+                self.store_reg("rax", d);
             }
-            _ => {
-                // if条件が満たされていない場合、endラベルへ
-                self.generate_jne_inst(label_end);
-
-                // ifブロック部生成.
-                // block部はAstType::Statementなので、演算結果に対するスタック操作は行わない.
-                self.generate(b);
+            Inst::Call { dst, ref callee, ref args, sret } => self.generate_call_func(dst, callee, args, sret),
+            Inst::Memcpy(d, s, size) => {
+                self.load_reg(s, "rax");
+                self.load_reg(d, "rcx");
+                self.generate_copy("rax", "rcx", 0, size);
             }
         }
-        // 終端ラベル
-        self.generate_label_inst(label_end);
-    }
-
-    // while statement生成.
-    fn generate_statement_while(&mut self, a: &AstType, b: &AstType) {
-        let label_begin = self.label.next_label();
-        let label_end = self.label.next_label();
-
-        // continue/breakラベル生成.
-        self.label.push_continue(label_begin);
-        self.label.push_break(label_end);
-
-        // condition部生成.
-        self.generate_label_inst(label_begin);
-        self.generate(a);
-        // conditionが偽であれば、ブロック終端へジャンプ.
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.generate_je_inst(label_end);
-
-        // ブロック部生成.
-        // block部はAstType::Statementなので、演算結果に対するスタック操作は行わない.
-        self.generate(b);
-        self.generate_jmp_inst(label_begin);
-
-        // endラベル.
-        self.generate_label_inst(label_end);
-
-        // 生成したcontinue/breakラベルを除去.
-        self.label.remove_continue(label_begin);
-        self.label.remove_break(label_end);
     }
 
-    // do-while statement生成.
-    fn generate_statement_do(&mut self, a: &AstType, b: &AstType) {
-        let label_begin = self.label.next_label();
-        let label_condition = self.label.next_label();
-        let label_end = self.label.next_label();
-
-        // continue/breakラベル生成.
-        self.label.push_continue(label_condition);
-        self.label.push_break(label_end);
-
-        // ブロック部生成.
-        self.generate_label_inst(label_begin);
-        self.generate(a);
-
-        // condition部生成.
-        self.generate_label_inst(label_condition);
-        self.generate(b);
-        // conditionが真であれば、ブロック先頭へジャンプ.
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-
-        self.generate_jne_inst(label_begin);
-        self.generate_label_inst(label_end);
-
-        // 生成したcontinue/breakラベルを除去.
-        self.label.remove_continue(label_condition);
-        self.label.remove_break(label_end);
-    }
-
-    // for statement生成.
-    fn generate_statement_for(
-        &mut self,
-        a: &Option<AstType>,
-        b: &Option<AstType>,
-        c: &Option<AstType>,
-        d: &AstType,
-    ) {
-        let label_begin = self.label.next_label();
-        let label_continue = self.label.next_label();
-        let label_end = self.label.next_label();
-
-        // continue/breakラベル生成.
-        self.label.push_continue(label_continue);
-        self.label.push_break(label_end);
-
-        // 初期条件.
-        if let Some(init) = a {
-            self.generate(init);
-            self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        }
-        self.generate_label_inst(label_begin);
-
-        // 終了条件.
-        if let Some(cond) = b {
-            self.generate(cond);
-            self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-            self.generate_cmp_inst(0, "rax");
-            self.generate_je_inst(label_end);
-        }
-
-        // ブロック部.
-        self.generate(d);
-        self.generate_label_inst(label_continue);
-
-        // 変数変化部分生成
-        if let Some(end) = c {
-            self.generate(end);
-            self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        }
-        self.generate_jmp_inst(label_begin);
-        self.generate_label_inst(label_end);
-
-        // 生成したcontinue/breakラベルを除去.
-        self.label.remove_continue(label_continue);
-        self.label.remove_break(label_end);
-    }
-
-    // continue文生成.
-    fn generate_statement_continue(&mut self) {
-        let label = self.label.pop_continue();
-        let no = label.expect("asm.rs(generate_statement_continue): invalid continue label");
-        self.generate_jmp_inst(no);
-    }
-
-    // break文生成.
-    fn generate_statement_break(&mut self) {
-        let label = self.label.pop_break();
-        let no = label.expect("asm.rs(generate_statement_break): invalid continue label");
-        self.generate_jmp_inst(no);
-    }
-
-    // return statement.
-    fn generate_statement_return(&mut self, a: &AstType) {
-        self.generate(a);
-        if a.is_expr() {
-            self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        }
-        let label_no = self.label.get_return_label();
-        self.generate_jmp_inst(label_no);
-    }
-
-    // assign indirect
-    fn generate_assign_indirect(&mut self, a: &AstType, b: &AstType) {
-        self.generate(a);
-        self.generate(b);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!(
-            "{}{}{}",
-            self.inst,
-            self.gen_asm().pop("rcx"),
-            self.gen_asm().mov_dst("rax", "rcx", 0)
-        );
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // assign生成.
-    fn generate_assign(&mut self, a: &AstType, b: &AstType) {
-        match *a {
-            AstType::Variable(ref t, ref s, _) => {
-                self.generate_lvalue_address(a);
-                self.generate(b);
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-
-                // ポインタは64bitで転送
-                match s {
-                    Structure::Pointer => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rcx", "rax", 0));
-                    }
-                    _ => {
-                        // 型に応じた転送サイズを考慮
-                        match t {
-                            Type::Char => {
-                                self.inst = format!("{}{}", self.inst, self.gen_asm().movb_dst("cl", "rax", 0));
-                            }
-                            _ =>  {
-                                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rcx", "rax", 0));
-                            }
-                        }
+    // 演算子アセンブラ生成.
+    fn operator(&self, op: BinOp) -> String {
+        match op {
+            BinOp::Add => self.gen_asm().plus(),
+            BinOp::Sub => self.gen_asm().minus(),
+            BinOp::Mul => self.gen_asm().multiple(),
+            BinOp::Div | BinOp::Rem => self.gen_asm().bit_division(),
+            BinOp::Shl => self.gen_asm().left_shift(),
+            BinOp::Sar => self.gen_asm().right_shift(),
+            BinOp::Shr => self.gen_asm().logical_right_shift(),
+            BinOp::And => self.gen_asm().bit_and(),
+            BinOp::Or => self.gen_asm().bit_or(),
+            BinOp::Xor => self.gen_asm().bit_xor(),
+            BinOp::Eq => self.gen_asm().equal(),
+            BinOp::Ne => self.gen_asm().not_equal(),
+            BinOp::Lt => self.gen_asm().less_than(),
+            BinOp::Le => self.gen_asm().less_than_equal(),
+            BinOp::Gt => self.gen_asm().greater_than(),
+            BinOp::Ge => self.gen_asm().greater_than_equal(),
+        }
+    }
+
+    // 終端命令生成.
+    //
+    // 直後のブロックへのジャンプは省略する
+    fn generate_term(&mut self, f: &Function, t: &Term, next: usize) {
+        match *t {
+            Term::Jump(b) if b == next => {}
+            Term::Jump(b) => self.inst = format!("{}{}", self.inst, self.gen_asm().jmp(self.block_label(b))),
+            Term::Branch(r, tb, fb) => {
+                self.load_reg(r, "rax");
+                self.inst = format!("{}{}", self.inst, self.gen_asm().cmpl(0, "rax"));
+                self.inst = match (tb == next, fb == next) {
+                    (true, _) => format!("{}{}", self.inst, self.gen_asm().je(self.block_label(fb))),
+                    (false, true) => format!("{}{}", self.inst, self.gen_asm().jne(self.block_label(tb))),
+                    (false, false) => format!(
+                        "{}{}{}",
+                        self.inst,
+                        self.gen_asm().jne(self.block_label(tb)),
+                        self.gen_asm().jmp(self.block_label(fb))
+                    ),
+                };
+            }
+            Term::Ret(v) => {
+                if let Some(r) = v {
+                    self.load_reg(r, "rax");
+                    if let RetKind::Struct(size) = f.ret {
+                        self.generate_return_struct(size);
                     }
                 }
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rcx"));
+                self.inst = format!("{}{}{}", self.inst, self.gen_asm().leave(), self.gen_asm().ret());
             }
-            AstType::Indirect(ref a) => self.generate_assign_indirect(a, b),
-            _ => self.generate(b),
         }
     }
 
-    // plus assign生成.
-    fn generate_plus_assign(&mut self, a: &AstType, b: &AstType) {
-        match a {
-            AstType::Variable(_, _, ref name) => {
-                self.generate_lvalue_address(a);
-                self.generate(b);
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().add_src("rcx", "rax", 0));
-
-                // 型に応じた転送サイズを考慮
-                let sym = self.get_var_symbol(name);
-                match sym.t {
-                    Type::Char => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movb_dst("al", "rcx", 0));
-                    }
-                    _ =>  {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                    }
+    // 構造体の戻り値生成.
+    //
+    // raxに構造体のアドレスが格納されているので、受け渡し方法に応じて転送
+    fn generate_return_struct(&mut self, size: usize) {
+        self.inst = format!("{}{}", self.inst, self.gen_asm().mov("rax", "r11"));
+        match ArgClass::classify(size) {
+            ArgClass::Integer(n) => {
+                self.generate_load_eightbyte("r11", 0, std::cmp::min(size, 8), "rax");
+                if n == 2 {
+                    self.generate_load_eightbyte("r11", 8, size - 8, "rdx");
                 }
             }
-            _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), a)
-        }
+            ArgClass::Memory => {
+                // 呼び出し元が用意した領域へコピーし、そのアドレスを返す
+                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rbp", "rcx", -(self.ret_ptr as i64)));
+                self.generate_copy("r11", "rcx", 0, size);
+                self.inst = format!("{}{}", self.inst, self.gen_asm().mov("rcx", "rax"));
+            }
+        }
+    }
+
+    // メモリコピー.
+    //
+    // srcの指すアドレスから、dstの指すアドレス+offへsizeバイトコピー（r10を使用）
+    fn generate_copy(&mut self, src: &str, dst: &str, off: i64, size: usize) {
+        let mut pos = 0;
+        while pos < size {
+            let n = pos as i64;
+            self.inst = match size - pos {
+                r if r >= 8 => format!(
+                    "{}{}{}", self.inst, self.gen_asm().mov_src(src, "r10", n), self.gen_asm().mov_dst("r10", dst, off + n)
+                ),
+                r if r >= 4 => format!(
+                    "{}{}{}", self.inst, self.gen_asm().movl_src(src, "r10d", n), self.gen_asm().movl_dst("r10d", dst, off + n)
+                ),
+                _ => format!(
+                    "{}{}{}", self.inst, self.gen_asm().movb_src(src, "r10b", n), self.gen_asm().movb_dst("r10b", dst, off + n)
+                ),
+            };
+            pos += match size - pos {
+                r if r >= 8 => 8,
+                r if r >= 4 => 4,
+                _ => 1,
+            };
+        }
+    }
+
+    // eightbyte読み込み.
+    //
+    // 構造体の末尾を超えて読み込まないよう、8バイト未満は1バイトずつ組み立てる（r10を使用）
+    fn generate_load_eightbyte(&mut self, src: &str, off: i64, size: usize, dst: &str) {
+        if size >= 8 {
+            self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src(src, dst, off));
+            return;
+        }
+        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm(dst, 0));
+        (0..size).rev().for_each(|i| {
+            self.inst = format!("{}{}", self.inst, self.gen_asm().shl_imm(8, dst));
+            self.inst = format!("{}{}", self.inst, self.gen_asm().movzb_src(src, "r10", off + i as i64));
+            self.inst = format!("{}{}", self.inst, self.gen_asm().or("r10", dst));
+        });
     }
 
-    // minus assign生成.
-    fn generate_minus_assign(&mut self, a: &AstType, b: &AstType) {
-        match a {
-            AstType::Variable(_, _, ref name) => {
-                self.generate_lvalue_address(a);
-                self.generate(b);
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rdx", 0));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().sub("rax", "rdx"));
-
-                // 型に応じた転送サイズを考慮
-                let sym = self.get_var_symbol(name);
-                match sym.t {
-                    Type::Char => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movb_dst("dl", "rcx", 0));
-                    }
-                    _ =>  {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rdx", "rcx", 0));
-                    }
+    // 関数コール生成.
+    //
+    // 引数はSystem V ABIに従い、レジスタ、又は16バイト境界に揃えたスタックで渡す
+    fn generate_call_func(&mut self, dst: Option<Reg>, callee: &Callee, args: &[Arg], sret: Option<(Reg, usize)>) {
+        // 引数の配置先を決定（メモリ渡しの構造体を返す場合、第1引数は格納先アドレス）.
+        let ret = sret.map(|(_, size)| ArgClass::classify(size));
+        let mut reg = match ret {
+            Some(ArgClass::Memory) => 1,
+            _ => 0,
+        };
+        let mut stack = 0;
+        let places: Vec<ArgPlace> = args.iter().map(|a| {
+            let (class, size) = match a.size {
+                Some(size) => (ArgClass::classify(size), size),
+                None => (ArgClass::Integer(1), 8),
+            };
+            match class {
+                ArgClass::Integer(n) if reg + n <= REGS.len() => {
+                    reg += n;
+                    ArgPlace::Reg(reg - n, size)
                 }
-            }
-            _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), a)
-        }
-    }
-
-    // multiple assign生成.
-    fn generate_multiple_assign(&mut self, a: &AstType, b: &AstType) {
-        match a {
-            AstType::Variable(_, _, ref name) => {
-                self.generate_lvalue_address(a);
-                self.generate(b);
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rdx", 0));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().mul("rdx"));
-
-                // 型に応じた転送サイズを考慮
-                let sym = self.get_var_symbol(name);
-                match sym.t {
-                    Type::Char => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movb_dst("al", "rcx", 0));
-                    }
-                    _ =>  {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                    }
+                _ => {
+                    stack += align_to(size, 8);
+                    ArgPlace::Stack(stack - align_to(size, 8), size)
                 }
             }
-            _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), a)
-        }
-    }
+        }).collect();
 
-    // division assign生成.
-    fn generate_division_assign(&mut self, a: &AstType, b: &AstType) {
-        match a {
-            AstType::Variable(_, _, ref name) => {
-                self.generate_lvalue_address(a);
-                self.generate(b);
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rbx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rbx", "rax", 0));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().bit_division());
-
-                // 型に応じた転送サイズを考慮
-                let sym = self.get_var_symbol(name);
-                match sym.t {
-                    Type::Char => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movb_dst("al", "rbx", 0));
-                    }
-                    _ =>  {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rbx", 0));
-                    }
-                }
-            }
-            _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), a)
+        // 引数領域を確保（スタックは関数の先頭で16バイト境界に揃えてある）.
+        let area = align_to(stack, 16);
+        if area > 0 {
+            self.inst = format!("{}{}", self.inst, self.gen_asm().sub_imm(area, "rsp"));
         }
-    }
-
-    // remainder assign生成.
-    fn generate_remainder_assign(&mut self, a: &AstType, b: &AstType) {
-        match a {
-            AstType::Variable(_, _, ref name) => {
-                self.generate_lvalue_address(a);
-                self.generate(b);
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rbx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rbx", "rax", 0));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().bit_division());
 
-                // 型に応じた転送サイズを考慮
-                let sym = self.get_var_symbol(name);
-                match sym.t {
-                    Type::Char => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movb_dst("dl", "rbx", 0));
-                    }
-                    _ =>  {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rdx", "rbx", 0));
-                    }
+        // スタック渡しの引数をコピー.
+        places.iter().zip(args.iter()).for_each(|(p, a)| {
+            if let ArgPlace::Stack(off, size) = *p {
+                self.load_reg(a.reg, "rax");
+                if a.size.is_some() {
+                    self.generate_copy("rax", "rsp", off as i64, size);
+                } else {
+                    self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rsp", off as i64));
                 }
             }
-            _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), a)
-        }
-    }
+        });
 
-    // 型や構造を判断し、variable生成
-    fn generate_variable_by_strt(&mut self, sym: &Symbol) {
-        match sym.strt {
-            Structure::Pointer => {
-                self.inst = format!("{}{}", self.inst, self.gen_asm().movq_src("rcx", "rax", 0));
-            }
-            Structure::Array(_) => {
-                self.inst = format!("{}{}", self.inst, self.gen_asm().movq("rcx", "rax"));
-            }
-            Structure::Identifier => {
-                match sym.t {
-                    Type::Int => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movl_src("rcx", "eax", 0));
-                    }
-                    Type::Char => {
-                        self.inst = format!("{}{}", self.inst, self.gen_asm().movsbl_src("rcx", "eax", 0));
-                    }
-                    _ => panic!("{}{}: cannot support type: {:?}", file!(), line!(), sym.t)
+        // レジスタ渡しの引数を設定.
+        places.iter().zip(args.iter()).for_each(|(p, a)| {
+            if let ArgPlace::Reg(r, size) = *p {
+                if a.size.is_some() {
+                    self.load_reg(a.reg, "rax");
+                    (0..align_to(size, 8) / 8).for_each(|k| {
+                        self.generate_load_eightbyte("rax", 8 * k as i64, std::cmp::min(size - 8 * k, 8), REGS[r + k]);
+                    });
+                } else {
+                    self.load_reg(a.reg, REGS[r]);
                 }
             }
-            Structure::Struct => {
-                // 変数自体の割当は未実装
-            },
-            _ => panic!("{}{}: cannot support structure: {:?}", file!(), line!(), sym.strt)
+        });
+
+        // 戻り値の格納領域.
+        if let (Some(ArgClass::Memory), Some((t, _))) = (ret, sret) {
+            self.load_reg(t, REGS[0]);
         }
-    }
 
-    // variable生成.
-    fn generate_variable(&mut self, a: &AstType) {
-        self.generate_lvalue_address(a);
-        match a {
-            AstType::Variable(_, _, ref name) => {
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                let sym = self.get_var_symbol(name);
-                self.generate_variable_by_strt(&sym);
+        let call_inst = match *callee {
+            Callee::Direct(ref n) => self.gen_asm().call(&self.generate_func_symbol(n)),
+            Callee::Indirect(r) => {
+                self.load_reg(r, "r11");
+                self.gen_asm().call_indirect("r11")
             }
-            _ => panic!("{}{}: cannot support AstType: {:?}", file!(), line!(), a)
+        };
+        self.inst = format!("{}{}", self.inst, call_inst);
+        if area > 0 {
+            self.inst = format!("{}{}", self.inst, self.gen_asm().add_imm(area, "rsp"));
         }
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // 関数コール生成.
-    fn generate_call_func(&mut self, lhs: &AstType, rhs: &AstType) {
-        match *lhs {
-            // 関数名.
-            AstType::Variable(_, _, ref n) if self.sym_table.search(&Scope::Func, n).is_some() => {
-                match *rhs {
-                    AstType::Argment(ref v) => {
-                        // 各引数を評価（スタックに積むので、逆順で積んでいく）.
-                        v.iter().rev().for_each(|d| self.generate(d));
-
-                        // 関数引数をスタックからレジスタへ.
-                        v.iter().zip(REGS.iter()).for_each(|d| match d.0 {
-                            AstType::Variable(_, s, _) if *s == Structure::Pointer => {
-                                self.inst = format!("{}{}", self.inst, self.gen_asm().pop(&d.1));
-                            }
-                            _ => {
-                                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                                self.inst =
-                                    format!("{}{}", self.inst, self.gen_asm().mov("rax", &d.1));
-                            }
-                        });
-                    }
-                    _ => panic!("{} {}: Not Function Argment", file!(), line!()),
-                }
 
-                self.inst = format!(
-                    "{}{}",
-                    self.inst,
-                    self.gen_asm().call(&self.generate_func_symbol(n))
-                );
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
+        // 構造体の戻り値はレジスタから格納領域へ転送する.
+        if let (Some(ArgClass::Integer(n)), Some((t, _))) = (ret, sret) {
+            self.load_reg(t, "rcx");
+            self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
+            if n == 2 {
+                self.inst = format!("{}{}", self.inst, self.gen_asm().mov_dst("rdx", "rcx", 8));
             }
-            _ => panic!("{} {}: Not Exists Function name", file!(), line!()),
+        }
+        if let Some(d) = dst {
+            self.store_reg("rax", d);
         }
     }
 
@@ -749,486 +537,4 @@ impl<'a> Asm<'a> {
             s.to_string()
         }
     }
-
-    // bit反転演算子生成.
-    fn generate_bit_reverse(&mut self, a: &AstType) {
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().not("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // Not演算子生成.
-    fn generate_not(&mut self, a: &AstType) {
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.inst = format!("{}{}", self.inst, self.gen_asm().set("al"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().movz("al", "rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // マイナス単項演算子生成.
-    fn generate_unminus(&mut self, a: &AstType) {
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().neg("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // プラス単項演算子生成.
-    fn generate_unplus(&mut self, a: &AstType) {
-        self.generate(a);
-    }
-
-    // 三項演算子生成.
-    fn generate_condition(&mut self, a: &AstType, b: &AstType, c: &AstType) {
-        let label_false = self.label.next_label();
-        let label_end = self.label.next_label();
-
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.generate_je_inst(label_false);
-
-        self.generate(b);
-        self.generate_jmp_inst(label_end);
-        self.generate_label_inst(label_false);
-
-        self.generate(c);
-        self.generate_label_inst(label_end);
-    }
-
-    // &&演算子生成.
-    fn generate_logical_and(&mut self, a: &AstType, b: &AstType) {
-        let label_false = self.label.next_label();
-        let label_end = self.label.next_label();
-
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.generate_je_inst(label_false);
-        self.generate(b);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.generate_je_inst(label_false);
-
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", 1));
-        self.generate_jmp_inst(label_end);
-        self.generate_label_inst(label_false);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", 0));
-        self.generate_label_inst(label_end);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // ||演算子生成.
-    fn generate_logical_or(&mut self, a: &AstType, b: &AstType) {
-        let label_true = self.label.next_label();
-        let label_end = self.label.next_label();
-
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.generate_jne_inst(label_true);
-        self.generate(b);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.generate_cmp_inst(0, "rax");
-        self.generate_jne_inst(label_true);
-
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", 0));
-        self.generate_jmp_inst(label_end);
-        self.generate_label_inst(label_true);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", 1));
-        self.generate_label_inst(label_end);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // 数値生成.
-    fn generate_factor(&mut self, a: i64) {
-        // 数値.
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", a));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // sizeof演算子.
-    fn generate_sizeof(&mut self, a: usize) {
-        // 数値.
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rax", a as i64));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // シンボル情報取得
-    fn get_var_symbol(&self, k: &str) -> Symbol {
-        // 現在のスコープから変数をサーチ
-        match self.cur_scope {
-            Scope::Global => {
-                self.sym_table
-                    .search(&self.cur_scope, k)
-                    .expect("asm.rs(generate_var_symbol): error option value")
-            }
-            _ => {
-                // もし、ローカルスコープで存在しない場合、Globalから検索
-                self.sym_table.search(&self.cur_scope, k)
-                    .unwrap_or_else(||
-                        self.sym_table.search(&Scope::Global, k).expect("asm.rs(generate_var_symbol): error option value")
-                )
-            }
-        }
-   }
-
-    // 左辺値変数アドレス取得
-    fn generate_lvalue_address(&mut self, a: &AstType) {
-        let (sym, name) = match *a {
-            AstType::Variable(_, _, ref s) => (self.get_var_symbol(s), s),
-            _ => panic!(format!(
-                "asm.rs(generate_lvalue_address): Not Support AstType {:?}",
-                a
-            )),
-        };
-
-        // アドレスをraxレジスタへ転送
-        self.inst = match sym.scope {
-            Scope::Global => format!("{}{}", self.inst, self.gen_asm().lea_glb(name)),
-            _ => match sym.strt {
-                Structure::Array(_) => {
-                    format!("{}{}", self.inst, self.gen_asm().lea(sym.size as i64))
-                }
-                _ => format!("{}{}", self.inst, self.gen_asm().lea(sym.offset as i64 + 8))
-            }
-        };
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // 後置インクリメント
-    fn generate_post_inc(&mut self, a: &AstType) {
-        self.generate_lvalue_address(a);
-
-        match *a {
-            AstType::Variable(_, ref s, _) => match s {
-                Structure::Identifier => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().add_imm(1, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                }
-                Structure::Pointer => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().add_imm(8, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                }
-                _ => panic!(format!("asm.rs(generate_post_inc): Not Support Structure {:?}", s)),
-            },
-            _ => panic!(format!("asm.rs(generate_post_inc): Not Support AstType {:?}",
-                a
-            )),
-        }
-    }
-
-    // 後置デクリメント
-    fn generate_post_dec(&mut self, a: &AstType) {
-        self.generate_lvalue_address(a);
-
-        match *a {
-            AstType::Variable(_, ref s, _) => match s {
-                Structure::Identifier => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().sub_imm(1, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                }
-                Structure::Pointer => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().sub_imm(8, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                }
-                _ => panic!(format!(
-                    "asm.rs(generate_post_dec): Not Support Structure {:?}",
-                    s
-                )),
-            },
-            _ => panic!(format!(
-                "asm.rs(generate_post_dec): Not Support AstType {:?}",
-                a
-            )),
-        }
-    }
-
-    // 前置インクリメント
-    fn generate_pre_inc(&mut self, a: &AstType) {
-        self.generate_lvalue_address(a);
-
-        match *a {
-            AstType::Variable(_, ref s, _) => match s {
-                Structure::Identifier => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().add_imm(1, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                }
-                Structure::Pointer => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().add_imm(8, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                }
-                _ => panic!(format!(
-                    "asm.rs(generate_pre_inc): Not Support Structure {:?}",
-                    s
-                )),
-            },
-            _ => panic!(format!(
-                "asm.rs(generate_pre_inc): Not Support AstType {:?}",
-                a
-            )),
-        }
-    }
-
-    // 前置デクリメント
-    fn generate_pre_dec(&mut self, a: &AstType) {
-        self.generate_lvalue_address(a);
-
-        match *a {
-            AstType::Variable(_, ref s, _) => match s {
-                Structure::Identifier => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().sub_imm(1, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                }
-                Structure::Pointer => {
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_src("rcx", "rax", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().sub_imm(8, "rax"));
-                    self.inst =
-                        format!("{}{}", self.inst, self.gen_asm().mov_dst("rax", "rcx", 0));
-                    self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-                }
-                _ => panic!(format!(
-                    "asm.rs(generate_pre_dec): Not Support Structure {:?}",
-                    s
-                )),
-            },
-            _ => panic!(format!(
-                "asm.rs(generate_pre_dec): Not Support AstType {:?}",
-                a
-            )),
-        }
-    }
-
-    // ポインタ同士の加算
-    fn generate_plus_with_pointer(&mut self, a: &AstType, b: &AstType) {
-        self.generate(a);
-        self.generate(b);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rcx", 8));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mul("rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().add("rax", "rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rcx"));
-    }
-
-    // variable同士の加算
-    fn generate_plus_variable(&mut self, a: &AstType, b: &AstType, s: &Structure) {
-        match s {
-            Structure::Array(_) => self.generate_plus_with_pointer(a, b),
-            _ => {
-                self.generate(a);
-                self.generate(b);
-
-                // 加算処理
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().plus());
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-            }
-        }
-    }
-
-    // 加算
-    fn generate_plus(&mut self, a: &AstType, b: &AstType) {
-        match (a, b) {
-            // ポインタ演算チェック
-            (AstType::Variable(ref _t1, ref s1, _), _) if *s1 == Structure::Pointer => {
-                self.generate_plus_with_pointer(a, b)
-            }
-            (AstType::Variable(ref _t1, ref s1, _), _) => self.generate_plus_variable(a, b, s1),
-            _ => {
-                self.generate(a);
-                self.generate(b);
-
-                // 加算処理
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().plus());
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-            }
-        }
-    }
-
-    // ポインタ同士の減算
-    fn generate_minus_with_pointer(&mut self, a: &AstType, b: &AstType) {
-        self.generate(a);
-        self.generate(b);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_imm("rcx", 8));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mul("rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().sub("rax", "rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rcx"));
-    }
-
-    // 減算
-    fn generate_minus(&mut self, a: &AstType, b: &AstType) {
-        match (a, b) {
-            (AstType::Variable(ref _t1, ref s1, _), AstType::Variable(ref t2, _, _))
-                if *s1 == Structure::Pointer && (*t2 == Type::Int || *t2 == Type::Char) =>
-            {
-                self.generate_minus_with_pointer(a, b)
-            }
-            (AstType::Variable(ref _t1, ref s1, _), AstType::Factor(_))
-                if *s1 == Structure::Pointer =>
-            {
-                self.generate_minus_with_pointer(a, b)
-            }
-            _ => {
-                self.generate(a);
-                self.generate(b);
-
-                // 減算処理
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().minus());
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-            }
-        }
-    }
-
-    // 演算子生成.
-    fn generate_operator(&mut self, ast: &AstType, a: &AstType, b: &AstType) {
-        self.generate(a);
-        self.generate(b);
-
-        // 各演算子評価.
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rcx"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!("{}{}", self.inst, self.operator(ast));
-
-        // 演算子に応じて退避するレジスタを変更.
-        match *ast {
-            AstType::Remainder(_, _) => {
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rdx"));
-            }
-            _ => {
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-            }
-        }
-    }
-
-    // アドレス演算子.
-    fn generate_address(&mut self, a: &AstType) {
-        match *a {
-            AstType::Variable(ref _t, ref _s, ref a) => {
-                let sym = self.get_var_symbol(a);
-                let pos = sym.offset as i64 + 8;
-                self.inst = format!("{}{}", self.inst, self.gen_asm().lea(pos));
-                self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-            }
-            _ => panic!("{} {}: Not Support Ast {:?}", file!(), line!(), a),
-        }
-    }
-
-    // 間接演算子.
-    fn generate_indirect(&mut self, a: &AstType) {
-        self.generate(a);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().pop("rax"));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().mov_src("rax", "rcx", 0));
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rcx"));
-    }
-
-    // 演算子アセンブラ生成.
-    fn operator(&self, ope: &AstType) -> String {
-        match *ope {
-            AstType::Multiple(_, _) => self.gen_asm().multiple(),
-            AstType::Equal(_, _) => self.gen_asm().equal(),
-            AstType::NotEqual(_, _) => self.gen_asm().not_equal(),
-            AstType::LessThan(_, _) => self.gen_asm().less_than(),
-            AstType::GreaterThan(_, _) => self.gen_asm().greater_than(),
-            AstType::LessThanEqual(_, _) => self.gen_asm().less_than_equal(),
-            AstType::GreaterThanEqual(_, _) => self.gen_asm().greater_than_equal(),
-            AstType::LeftShift(_, _) => self.gen_asm().left_shift(),
-            AstType::RightShift(_, _) => self.gen_asm().right_shift(),
-            AstType::BitAnd(_, _) => self.gen_asm().bit_and(),
-            AstType::BitOr(_, _) => self.gen_asm().bit_or(),
-            AstType::BitXor(_, _) => self.gen_asm().bit_xor(),
-            AstType::Division(_, _) | AstType::Remainder(_, _) => self.gen_asm().bit_division(),
-            _ => process::abort(),
-        }
-    }
-
-    // 文字列リテラル生成
-    fn generate_string_literal(&mut self, a: &AstType) {
-        match a {
-            AstType::StringLiteral(s, i) => {
-                self.const_literal = format!("{}  .text\n", self.const_literal);
-                self.const_literal = format!("{}.LC{}:\n", self.const_literal, i);
-                self.const_literal = format!("{}  .string \"{}\"\n", self.const_literal, s);
-            }
-            _ => panic!("asm.rs(generate_string_literal): not support {:?}", a),
-        }
-    }
-
-    // 文字列リテラル命令
-    fn generate_string(&mut self, _s: &str, i: usize) {
-        self.inst = format!("{}  movq $.LC{}, %rax\n", self.inst, i);
-        self.inst = format!("{}{}", self.inst, self.gen_asm().push("rax"));
-    }
-
-    // ラベル命令.
-    fn generate_label_inst(&mut self, no: usize) {
-        self.inst = format!("{}{}", self.inst, self.gen_asm().label(no));
-    }
-
-    // jmp命令生成.
-    fn generate_jmp_inst(&mut self, no: usize) {
-        self.inst = format!("{}{}", self.inst, self.gen_asm().jmp(no));
-    }
-
-    // je命令生成.
-    fn generate_je_inst(&mut self, no: usize) {
-        self.inst = format!("{}{}", self.inst, self.gen_asm().je(no));
-    }
-
-    // jne命令生成.
-    fn generate_jne_inst(&mut self, no: usize) {
-        self.inst = format!("{}{}", self.inst, self.gen_asm().jne(no));
-    }
-
-    // cmp命令生成.
-    fn generate_cmp_inst(&mut self, f: usize, r: &str) {
-        self.inst = format!("{}{}", self.inst, self.gen_asm().cmpl(f, r));
-    }
 }
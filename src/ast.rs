@@ -1,11 +1,12 @@
-use std::collections::HashMap;
-use symbol::{Scope, Structure, Symbol, SymbolTable, Type};
+use symbol::{source_name, type_name, BitField, Qualifier, Scope, Signature, Storage, Structure, Symbol, SymbolTable, Type};
 use token::{Token, TokenInfo};
+use warning::{Warning, WarningKind};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AstType {
     Global(Vec<AstType>),
     FuncDef(Type, Structure, String, Box<AstType>, Box<AstType>),
+    FuncDecl(Type, Structure, String, Box<AstType>), // 戻り値型、関数名、引数（プロトタイプ宣言）.
     Statement(Vec<AstType>),
     While(Box<AstType>, Box<AstType>), // 条件式、ブロック部.
     Do(Box<AstType>, Box<AstType>),    // ブロック部、条件式.
@@ -18,8 +19,10 @@ pub enum AstType {
     ), // 初期条件、終了条件、更新部、ブロック部.
     Continue(),
     Break(),
-    Return(Box<AstType>),
+    Return(Box<AstType>, String), // 戻り値、位置.
+    ReturnVoid(String), // 値なしのreturn文（位置）.
     Condition(Box<AstType>, Box<AstType>, Box<AstType>),
+    Comma(Box<AstType>, Box<AstType>),
     LogicalAnd(Box<AstType>, Box<AstType>),
     LogicalOr(Box<AstType>, Box<AstType>),
     BitAnd(Box<AstType>, Box<AstType>),
@@ -42,39 +45,384 @@ pub enum AstType {
     UnMinus(Box<AstType>),
     Not(Box<AstType>),
     BitReverse(Box<AstType>),
-    Assign(Box<AstType>, Box<AstType>),
+    Assign(Box<AstType>, Box<AstType>, String), // 代入先、値、演算子の位置.
+    Init(Box<AstType>, Box<AstType>, String), // 宣言の初期化子（変数、初期化子、位置）.
     Factor(i64),
-    Variable(Type, Structure, String),
-    FuncCall(Box<AstType>, Box<AstType>),
+    Variable(Type, Structure, String, String), // 型、構造、名前、位置.
+    FuncCall(Box<AstType>, Box<AstType>, String), // 関数、実引数、位置.
     Argment(Vec<AstType>),
-    Address(Box<AstType>),
+    Address(Box<AstType>, String),
     Indirect(Box<AstType>),
-    PreInc(Box<AstType>),
-    PreDec(Box<AstType>),
-    PostInc(Box<AstType>),
-    PostDec(Box<AstType>),
+    PreInc(Box<AstType>, String),
+    PreDec(Box<AstType>, String),
+    PostInc(Box<AstType>, String),
+    PostDec(Box<AstType>, String),
     StringLiteral(String, usize),
-    PlusAssign(Box<AstType>, Box<AstType>),
-    MinusAssign(Box<AstType>, Box<AstType>),
-    MultipleAssign(Box<AstType>, Box<AstType>),
-    DivisionAssign(Box<AstType>, Box<AstType>),
-    RemainderAssign(Box<AstType>, Box<AstType>),
+    StaticAssert(Box<AstType>, String, String),
+    PlusAssign(Box<AstType>, Box<AstType>, String),
+    MinusAssign(Box<AstType>, Box<AstType>, String),
+    MultipleAssign(Box<AstType>, Box<AstType>, String),
+    DivisionAssign(Box<AstType>, Box<AstType>, String),
+    RemainderAssign(Box<AstType>, Box<AstType>, String),
+    BitAndAssign(Box<AstType>, Box<AstType>, String),
+    BitOrAssign(Box<AstType>, Box<AstType>, String),
+    BitXorAssign(Box<AstType>, Box<AstType>, String),
+    LeftShiftAssign(Box<AstType>, Box<AstType>, String),
+    RightShiftAssign(Box<AstType>, Box<AstType>, String),
     SizeOf(usize),
     Struct(Box<AstType>, Vec<AstType>),
+    InitList(Vec<AstType>),
+    StaticVar(Box<AstType>), // 関数内static変数定義.
+    Member(Box<AstType>, Type, Structure, String), // 構造体、メンバーの型、メンバー名.
+    Cast(Type, Structure, Box<AstType>, String), // 変換先の型、被演算子、位置.
+    Error(), // 構文エラー箇所.
+}
+
+// 定数式の演算結果チェック.
+//
+// int同士の演算結果がintに収まらない場合はオーバーフローとする（longの演算はlongの範囲で判定）
+fn int_result(long: bool, operands: &[i64], v: Option<i64>) -> Result<i64, String> {
+    let is_int = |n: i64| n as i32 as i64 == n;
+    match v {
+        Some(v) if long || is_int(v) || !operands.iter().all(|n| is_int(*n)) => Ok(v),
+        _ => Err("integer overflow in constant expression".to_string()),
+    }
+}
+
+// 配列から先頭要素へのポインタへの変換.
+//
+// 配列型の仮引数、ポインタ演算、実引数の型判定で使用する
+pub fn decay(t: Type, s: Structure) -> (Type, Structure) {
+    match s {
+        Structure::Array(ref dims) if dims.len() > 1 => (Type::Array(Box::new(t), dims[1..].to_vec()), Structure::Pointer),
+        Structure::Array(_) => (t, Structure::Pointer),
+        _ => (t, s),
+    }
+}
+
+// ポインタ型取得.
+//
+// ポインタへのポインタ、配列へのポインタは、指す型を要素の型とする
+fn pointer_to(t: Type, s: Structure) -> (Type, Structure) {
+    match s {
+        Structure::Pointer => (Type::Pointer(Box::new(t)), Structure::Pointer),
+        Structure::Array(dims) => (Type::Array(Box::new(t), dims), Structure::Pointer),
+        _ => (t, Structure::Pointer),
+    }
 }
 
 impl AstType {
-    // 式判定.
-    pub fn is_expr(&self) -> bool {
+    // 子ノード取得.
+    //
+    // 構造体定義のメンバーは宣言のみなので含まない
+    pub fn children(&self) -> Vec<&AstType> {
         match self {
-            AstType::If(_, _, _)
-            | AstType::For(_, _, _, _)
-            | AstType::Do(_, _)
-            | AstType::Continue()
+            AstType::Global(v)
+            | AstType::Statement(v)
+            | AstType::Argment(v)
+            | AstType::InitList(v) => v.iter().collect(),
+            AstType::FuncDef(_, _, _, a, b) => vec![a, b],
+            AstType::FuncDecl(_, _, _, a) => vec![a],
+            AstType::If(a, b, c) => {
+                let mut v: Vec<&AstType> = vec![a, b];
+                v.extend(c.iter());
+                v
+            }
+            AstType::For(a, b, c, d) => {
+                let mut v: Vec<&AstType> = vec![];
+                v.extend(a.iter());
+                v.extend(b.iter());
+                v.extend(c.iter());
+                v.push(d);
+                v
+            }
+            AstType::Condition(a, b, c) => vec![a, b, c],
+            AstType::Comma(a, b) => vec![a, b],
+            AstType::While(a, b)
+            | AstType::Do(a, b)
+            | AstType::LogicalAnd(a, b)
+            | AstType::LogicalOr(a, b)
+            | AstType::BitAnd(a, b)
+            | AstType::BitOr(a, b)
+            | AstType::BitXor(a, b)
+            | AstType::Equal(a, b)
+            | AstType::NotEqual(a, b)
+            | AstType::LessThan(a, b)
+            | AstType::GreaterThan(a, b)
+            | AstType::LessThanEqual(a, b)
+            | AstType::GreaterThanEqual(a, b)
+            | AstType::Plus(a, b)
+            | AstType::Minus(a, b)
+            | AstType::LeftShift(a, b)
+            | AstType::RightShift(a, b)
+            | AstType::Multiple(a, b)
+            | AstType::Division(a, b)
+            | AstType::Remainder(a, b)
+            | AstType::Assign(a, b, _)
+            | AstType::Init(a, b, _)
+            | AstType::FuncCall(a, b, _)
+            | AstType::PlusAssign(a, b, _)
+            | AstType::MinusAssign(a, b, _)
+            | AstType::MultipleAssign(a, b, _)
+            | AstType::DivisionAssign(a, b, _)
+            | AstType::RemainderAssign(a, b, _)
+            | AstType::BitAndAssign(a, b, _)
+            | AstType::BitOrAssign(a, b, _)
+            | AstType::BitXorAssign(a, b, _)
+            | AstType::LeftShiftAssign(a, b, _)
+            | AstType::RightShiftAssign(a, b, _) => vec![a, b],
+            AstType::Return(a, _)
+            | AstType::UnPlus(a)
+            | AstType::UnMinus(a)
+            | AstType::Not(a)
+            | AstType::BitReverse(a)
+            | AstType::Address(a, _)
+            | AstType::Indirect(a)
+            | AstType::PreInc(a, _)
+            | AstType::PreDec(a, _)
+            | AstType::PostInc(a, _)
+            | AstType::PostDec(a, _)
+            | AstType::StaticVar(a)
+            | AstType::StaticAssert(a, _, _)
+            | AstType::Member(a, _, _, _)
+            | AstType::Cast(_, _, a, _) => vec![a],
+            AstType::Continue()
             | AstType::Break()
-            | AstType::Return(_)
-            | AstType::While(_, _) => false,
-            _ => true,
+            | AstType::ReturnVoid(_)
+            | AstType::Factor(_)
+            | AstType::Variable(_, _, _, _)
+            | AstType::StringLiteral(_, _)
+            | AstType::SizeOf(_)
+            | AstType::Struct(_, _)
+            | AstType::Error() => vec![],
+        }
+    }
+
+    // 関数ポインタ式のシグネチャ取得.
+    //
+    // 関数指定子、関数ポインタの変数、メンバー、配列の要素、関数指定子のアドレス、及びそれらの間接参照が対象
+    pub fn func_signature(&self) -> Option<Signature> {
+        match self {
+            AstType::Variable(Type::Func(ref sig), _, _, _) | AstType::Member(_, Type::Func(ref sig), _, _) => Some((**sig).clone()),
+            AstType::Address(ref a, _) => match **a {
+                AstType::Variable(Type::Func(_), Structure::Identifier, _, _) => a.func_signature(),
+                _ => None,
+            },
+            AstType::Indirect(ref a) => match **a {
+                AstType::Plus(ref v, _) => match v.value_type() {
+                    Some((Type::Func(sig), Structure::Array(_))) => Some(*sig),
+                    _ => None,
+                },
+                _ => a.func_signature(),
+            },
+            _ => None,
+        }
+    }
+
+    // 式の型取得.
+    //
+    // 配列要素はIndirect(Plus(配列, 添字))で表現されるので、配列の要素型を返す
+    pub fn value_type(&self) -> Option<(Type, Structure)> {
+        match self {
+            AstType::Factor(_) | AstType::SizeOf(_) => Some((Type::Int, Structure::Identifier)),
+            AstType::StringLiteral(_, _) => Some((Type::Char, Structure::Pointer)),
+            // ビットフィールドの値は格納単位の型の整数
+            AstType::Member(_, t, Structure::BitField(_), _) => Some((t.clone(), Structure::Identifier)),
+            AstType::Variable(t, s, _, _) | AstType::Member(_, t, s, _) | AstType::Cast(t, s, _, _) => Some((t.clone(), s.clone())),
+            AstType::Address(a, _) => a.value_type().map(|(t, s)| pointer_to(t, s)),
+            AstType::Indirect(a) => a.deref_type(),
+            AstType::FuncCall(f, _, _) => match f.func_signature() {
+                Some(sig) => Some(sig.ret),
+                None => match **f {
+                    AstType::Variable(ref t, ref s, _, _) => Some((t.clone(), s.clone())),
+                    _ => None,
+                },
+            },
+            AstType::Assign(a, _, _) | AstType::Init(a, _, _) => a.value_type(),
+            // 複合代入は左辺の型
+            _ if self.is_compound_assign() => self.children()[0].value_type(),
+            AstType::Condition(_, a, _) => a.value_type(),
+            AstType::Comma(_, b) => b.value_type(),
+            // ポインタ同士の差は要素数
+            AstType::Minus(a, b) if a.is_pointer_value() && b.is_pointer_value() => Some((Type::Long, Structure::Identifier)),
+            AstType::Plus(a, b) | AstType::Minus(a, b) => match (a.value_type(), b.value_type()) {
+                (Some((t, s @ Structure::Pointer)), _) | (Some((t, s @ Structure::Array(_))), _) => Some(decay(t, s)),
+                (_, Some((t, s @ Structure::Pointer))) | (_, Some((t, s @ Structure::Array(_)))) => Some(decay(t, s)),
+                _ => Some((Type::Int, Structure::Identifier)),
+            },
+            AstType::Multiple(_, _)
+            | AstType::Division(_, _)
+            | AstType::Remainder(_, _)
+            | AstType::LeftShift(_, _)
+            | AstType::RightShift(_, _)
+            | AstType::BitAnd(_, _)
+            | AstType::BitOr(_, _)
+            | AstType::BitXor(_, _)
+            | AstType::LogicalAnd(_, _)
+            | AstType::LogicalOr(_, _)
+            | AstType::Equal(_, _)
+            | AstType::NotEqual(_, _)
+            | AstType::LessThan(_, _)
+            | AstType::GreaterThan(_, _)
+            | AstType::LessThanEqual(_, _)
+            | AstType::GreaterThanEqual(_, _)
+            | AstType::UnPlus(_)
+            | AstType::UnMinus(_)
+            | AstType::Not(_)
+            | AstType::BitReverse(_) => Some((Type::Int, Structure::Identifier)),
+            AstType::PreInc(a, _) | AstType::PreDec(a, _) | AstType::PostInc(a, _) | AstType::PostDec(a, _) => a.value_type(),
+            _ => None,
+        }
+    }
+
+    // 間接参照した型取得.
+    //
+    // 多次元配列は一次元低い配列、ポインタの配列はポインタとなる
+    pub fn deref_type(&self) -> Option<(Type, Structure)> {
+        match self.value_type() {
+            Some((t, Structure::Array(ref dims))) if dims.len() > 1 => Some((t, Structure::Array(dims[1..].to_vec()))),
+            Some((t, Structure::Pointer)) | Some((t, Structure::Array(_))) => Some(match t {
+                Type::Struct(_) | Type::Union(_) => (t, Structure::Struct),
+                Type::Pointer(t) => (*t, Structure::Pointer),
+                Type::Array(t, dims) => (*t, Structure::Array(dims)),
+                _ => (t, Structure::Identifier),
+            }),
+            _ => None,
+        }
+    }
+
+    // 複合代入判定.
+    pub fn is_compound_assign(&self) -> bool {
+        matches!(
+            self,
+            AstType::PlusAssign(_, _, _)
+                | AstType::MinusAssign(_, _, _)
+                | AstType::MultipleAssign(_, _, _)
+                | AstType::DivisionAssign(_, _, _)
+                | AstType::RemainderAssign(_, _, _)
+                | AstType::BitAndAssign(_, _, _)
+                | AstType::BitOrAssign(_, _, _)
+                | AstType::BitXorAssign(_, _, _)
+                | AstType::LeftShiftAssign(_, _, _)
+                | AstType::RightShiftAssign(_, _, _)
+        )
+    }
+
+    // ポインタ（配列）の値判定.
+    pub fn is_pointer_value(&self) -> bool {
+        matches!(self.value_type(), Some((_, Structure::Pointer)) | Some((_, Structure::Array(_))))
+    }
+
+    // 構造体（共用体）の値判定.
+    pub fn is_struct_value(&self) -> bool {
+        matches!(self.value_type(), Some((Type::Struct(_) | Type::Union(_), Structure::Struct)))
+    }
+
+    // 関数ポインタ値判定.
+    //
+    // 関数ポインタ配列そのものは除く
+    pub fn is_func_pointer(&self) -> bool {
+        self.func_signature().is_some()
+            && !matches!(self, AstType::Variable(_, Structure::Array(_), _, _) | AstType::Member(_, _, Structure::Array(_), _))
+    }
+
+    // 定数式評価.
+    //
+    // 定数として評価できない場合はNoneを返す
+    pub fn const_value(&self) -> Option<i64> {
+        self.eval_const().ok()
+    }
+
+    // 定数式評価（診断付き）.
+    //
+    // 評価できない場合、ゼロ除算やオーバーフローはエラーメッセージを返す
+    // &&、||、?:は評価しない側の被演算子を無視する
+    pub fn eval_const(&self) -> Result<i64, String> {
+        let binary = |a: &AstType, b: &AstType, f: &dyn Fn(i64, i64) -> Result<i64, String>| {
+            f(a.eval_const()?, b.eval_const()?)
+        };
+        let long = self.is_long_const();
+        match self {
+            AstType::Factor(n) => Ok(*n),
+            AstType::SizeOf(n) => Ok(*n as i64),
+            AstType::UnPlus(a) => a.eval_const(),
+            AstType::UnMinus(a) => {
+                let v = a.eval_const()?;
+                int_result(long, &[v], v.checked_neg())
+            }
+            AstType::Not(a) => Ok((a.eval_const()? == 0) as i64),
+            AstType::BitReverse(a) => Ok(!a.eval_const()?),
+            AstType::Condition(a, b, c) => {
+                if a.eval_const()? != 0 { b.eval_const() } else { c.eval_const() }
+            }
+            AstType::LogicalAnd(a, b) => Ok((a.eval_const()? != 0 && b.eval_const()? != 0) as i64),
+            AstType::LogicalOr(a, b) => Ok((a.eval_const()? != 0 || b.eval_const()? != 0) as i64),
+            AstType::Plus(a, b) => binary(a, b, &|x, y| int_result(long, &[x, y], x.checked_add(y))),
+            AstType::Minus(a, b) => binary(a, b, &|x, y| int_result(long, &[x, y], x.checked_sub(y))),
+            AstType::Multiple(a, b) => binary(a, b, &|x, y| int_result(long, &[x, y], x.checked_mul(y))),
+            AstType::Division(_, _) | AstType::Remainder(_, _) => {
+                let (a, b) = (self.children()[0], self.children()[1]);
+                binary(a, b, &|x, y| match (y, self) {
+                    (0, _) => Err("division by zero in constant expression".to_string()),
+                    (_, AstType::Division(_, _)) => int_result(long, &[x, y], x.checked_div(y)),
+                    _ => int_result(long, &[x, y], x.checked_rem(y)),
+                })
+            }
+            AstType::LeftShift(_, _) | AstType::RightShift(_, _) => {
+                let (a, b) = (self.children()[0], self.children()[1]);
+                binary(a, b, &|x, y| {
+                    // 被演算子の型（int、long）の幅以上のシフトは未定義
+                    let width = if !long && x as i32 as i64 == x { 32 } else { 64 };
+                    if y < 0 || y >= width {
+                        return Err(format!("shift count {} is out of range in constant expression", y));
+                    }
+                    match self {
+                        // 型の範囲に収まらない左シフトはオーバーフロー
+                        AstType::LeftShift(_, _) => {
+                            let v = (x as i128) << y;
+                            int_result(long, &[x, y], Some(v as i64).filter(|n| *n as i128 == v))
+                        }
+                        _ => Ok(x >> y),
+                    }
+                })
+            }
+            AstType::BitAnd(a, b) => binary(a, b, &|x, y| Ok(x & y)),
+            AstType::BitOr(a, b) => binary(a, b, &|x, y| Ok(x | y)),
+            AstType::BitXor(a, b) => binary(a, b, &|x, y| Ok(x ^ y)),
+            AstType::Equal(a, b) => binary(a, b, &|x, y| Ok((x == y) as i64)),
+            AstType::NotEqual(a, b) => binary(a, b, &|x, y| Ok((x != y) as i64)),
+            AstType::LessThan(a, b) => binary(a, b, &|x, y| Ok((x < y) as i64)),
+            AstType::GreaterThan(a, b) => binary(a, b, &|x, y| Ok((x > y) as i64)),
+            AstType::LessThanEqual(a, b) => binary(a, b, &|x, y| Ok((x <= y) as i64)),
+            AstType::GreaterThanEqual(a, b) => binary(a, b, &|x, y| Ok((x >= y) as i64)),
+            // 変換先の幅で切り詰める
+            AstType::Cast(Type::Int, Structure::Identifier, a, _) => Ok(a.eval_const()? as i32 as i64),
+            AstType::Cast(Type::Short, Structure::Identifier, a, _) => Ok(a.eval_const()? as i16 as i64),
+            AstType::Cast(Type::Char, Structure::Identifier, a, _) => Ok(a.eval_const()? as i8 as i64),
+            AstType::Cast(_, _, a, _) => a.eval_const(),
+            _ => Err("expression is not a constant".to_string()),
+        }
+    }
+
+    // 定数式がlongの演算か判定.
+    //
+    // longへのキャストを含む被演算子があれば、演算もlongで行う（シフトは左辺の型）
+    fn is_long_const(&self) -> bool {
+        match self {
+            AstType::Cast(Type::Long, Structure::Identifier, _, _) => true,
+            AstType::Cast(_, _, _, _) => false,
+            AstType::LeftShift(a, _) | AstType::RightShift(a, _) => a.is_long_const(),
+            AstType::UnPlus(a) | AstType::UnMinus(a) | AstType::BitReverse(a) => a.is_long_const(),
+            AstType::Condition(_, a, b)
+            | AstType::Plus(a, b)
+            | AstType::Minus(a, b)
+            | AstType::Multiple(a, b)
+            | AstType::Division(a, b)
+            | AstType::Remainder(a, b)
+            | AstType::BitAnd(a, b)
+            | AstType::BitOr(a, b)
+            | AstType::BitXor(a, b) => a.is_long_const() || b.is_long_const(),
+            _ => false,
         }
     }
 }
@@ -84,9 +432,19 @@ pub struct AstGen<'a> {
     tokens: &'a [TokenInfo], // トークン配列.
     current_pos: usize,         // 現在読み取り位置.
     str_count: usize,           // 文字列リテラル位置
-    f_sym: HashMap<String, (Type, Structure)>,
     cur_scope: Scope,
+    cur_storage: Storage,       // 宣言中の記憶域クラス
+    cur_qual: Qualifier,        // 宣言中の型修飾子（型指定子より前のもの）
+    def_funcs: Vec<String>,     // 定義済み関数
     sym_table: SymbolTable,
+    errors: Vec<String>,        // 構文エラー
+    panic_mode: bool,           // エラー回復中（同期トークンまで後続エラーを抑止）
+    warnings: Vec<Warning>,     // 警告
+    used: Vec<(Scope, String)>, // 参照されたシンボル
+    address_of: bool,           // アドレス演算子の被演算子を解析中（配列末尾の次の要素を許容）
+    declarator: bool,           // 直前に解析した式が宣言子（続く=は初期化子）
+    enum_consts: Vec<(Scope, String, i64, String)>, // 列挙定数（スコープ、名前、値、宣言位置）
+    blocks: Vec<Vec<(String, String)>>, // 関数内のブロック毎の宣言（変数名、シンボル名）
 }
 
 #[derive(Debug)]
@@ -115,9 +473,19 @@ impl<'a> AstGen<'a> {
             current_pos: 0,
             str_count: 0,
             tokens: t,
-            f_sym: HashMap::new(),
             cur_scope: Scope::Global,
+            cur_storage: Storage::Auto,
+            cur_qual: Qualifier::default(),
+            def_funcs: vec![],
             sym_table: SymbolTable::new(),
+            errors: vec![],
+            panic_mode: false,
+            warnings: vec![],
+            used: vec![],
+            address_of: false,
+            declarator: false,
+            enum_consts: vec![],
+            blocks: vec![],
         }
     }
 
@@ -126,24 +494,73 @@ impl<'a> AstGen<'a> {
         &self.sym_table
     }
 
+    // 構文エラー取得
+    pub fn get_errors(&self) -> &Vec<String> {
+        &self.errors
+    }
+
+    // 警告取得
+    pub fn get_warnings(&self) -> &Vec<Warning> {
+        &self.warnings
+    }
+
     // トークン列を受け取り、抽象構文木を返す.
     pub fn parse(&mut self) -> AstTree {
-        // グローバル変数
-        let g = self.global_var(vec![]);
-        let mut s = if g.is_empty() {
-            vec![]
-        } else {
-            vec![AstType::Global(g)]
-        };
-
-        // 関数定義
-        while self.next().get_token_type() != Token::End {
-            let expr = self.func_def();
-            s.push(expr);
+        // グローバル変数と関数定義を交互に処理（グローバル変数は先頭にまとめる）
+        let mut g = vec![];
+        let mut s = vec![];
+        loop {
+            g = self.global_var(g);
+            if self.next().get_token_type() == Token::End {
+                break;
+            }
+            let f = self.func_def();
+
+            // 構文エラーがあれば、次の外部宣言まで読み飛ばす
+            if self.is_recovering() {
+                self.synchronize_global();
+                s.push(AstType::Error());
+            } else {
+                s.push(f);
+            }
         }
+        if !g.is_empty() {
+            s.insert(0, AstType::Global(g));
+        }
+        self.unused_warnings();
         AstTree::new(s)
     }
 
+    // 警告の記録
+    fn warn(&mut self, token: &TokenInfo, kind: WarningKind, msg: String) {
+        self.warnings.push(Warning::new(kind, token.pos.to_string(), msg));
+    }
+
+    // 未使用シンボルの警告
+    //
+    // ローカル変数、仮引数、定義された内部結合の関数が一度も参照されなければ警告する
+    fn unused_warnings(&mut self) {
+        let is_used = |s: &Symbol| self.used.iter().any(|(sc, n)| *sc == s.scope && *n == s.var);
+        let warns = self.sym_table.symbols().iter().filter(|s| !is_used(s)).filter_map(|s| {
+            let (kind, msg) = match s.scope {
+                // 構造体、共用体の定義名は変数ではない
+                Scope::Local(_) if s.is_tag() => return None,
+                Scope::Local(ref f) => match self.sym_table.search(&Scope::Func, f) {
+                    Some(ref func) if func.members.iter().any(|m| m.var == s.var) => {
+                        (WarningKind::UnusedParameter, format!("unused parameter {:?}", source_name(&s.var)))
+                    }
+                    _ => (WarningKind::UnusedVariable, format!("unused variable {:?}", source_name(&s.var))),
+                },
+                Scope::Func if s.storage == Storage::Static && self.def_funcs.contains(&s.var) => {
+                    (WarningKind::UnusedFunction, format!("{:?} defined but not used", s.var))
+                }
+                _ => return None,
+            };
+            Some(Warning::new(kind, s.decl.clone(), msg))
+        }).collect::<Vec<_>>();
+        self.warnings.extend(warns);
+    }
+
     // スコープ切り替え
     fn switch_scope(&mut self, scope: Scope) {
         self.cur_scope = scope;
@@ -153,88 +570,421 @@ impl<'a> AstGen<'a> {
     fn global_var(&mut self, acc: Vec<AstType>) -> Vec<AstType> {
         self.switch_scope(Scope::Global);
 
+        // 直前の宣言に構文エラーがあれば、エラーノードに置き換えて読み飛ばす
+        let mut acc = acc;
+        if self.is_recovering() {
+            acc.pop();
+            acc.push(AstType::Error());
+            self.synchronize_global();
+        }
+        if self.next().get_token_type() == Token::End {
+            return acc;
+        }
+
+        // 静的アサーション
+        if self.next().get_token_type() == Token::StaticAssert {
+            let token = self.next_consume();
+            let mut vars = acc;
+            vars.push(self.static_assert(token));
+            return self.global_var(vars);
+        }
+
         // タイプを判断する為、先読み
-        let (_t, s) = self.generate_type();
+        let start = self.current_pos;
+        let storage = self.storage_class();
+        let qual = self.qualifiers();
+        let spec = self.current_pos - start;
+        let (errors, panic_mode) = (self.errors.len(), self.panic_mode);
+        let (t, s) = self.generate_type();
+        self.qualifiers();
+        let (_t, s) = self.pointer_declarator(t, s);
         let token = self.next_consume();
         let paren = self.next();
 
         // 先読み分を戻る
-        self.back(2);
+        // 先読み中の診断は本解析で改めて報告する
+        let n = self.current_pos - start - spec;
+        self.back(n);
+        self.errors.truncate(errors);
+        self.panic_mode = panic_mode;
+
+        // 変数定義か判定（関数ポインタを含む）
+        let is_var = match token.get_token_type() {
+            Token::Variable => Token::LeftParen != paren.get_token_type(),
+            Token::LeftParen => Token::Multi == paren.get_token_type(),
+            _ => false,
+        };
+        let is_struct_def = s == Structure::Struct && (is_var || token.get_token_type() == Token::LeftBrace);
         match token.get_token_type() {
-            // 変数定義
-            Token::Variable if s != Structure::Struct && Token::LeftParen != paren.get_token_type() => {
+            // 変数定義（構造体へのポインタを含む）
+            _ if s != Structure::Struct && is_var => {
                 // グローバル変数
+                self.cur_storage = storage.clone();
+                self.cur_qual = qual;
                 let var = self.assign();
-                self.must_next(
-                    Token::SemiColon,
-                    "ast.rs(global_var): Not exists semi-colon",
-                );
+                self.cur_storage = Storage::Auto;
+                self.cur_qual = Qualifier::default();
+                self.must_next(Token::SemiColon);
+
+                // extern宣言済みの変数が定義された場合、記憶域クラスを更新
+                let name = match var {
+                    AstType::Variable(_, _, ref n, _) => Some(n.clone()),
+                    AstType::Init(ref v, _, _) => match **v {
+                        AstType::Variable(_, _, ref n, _) => Some(n.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(n) = name {
+                    let extern_decl = self.sym_table.search(&Scope::Global, &n)
+                        .map(|sym| sym.storage == Storage::Extern)
+                        .unwrap_or(false);
+                    if extern_decl && storage != Storage::Extern {
+                        self.sym_table.set_storage(&Scope::Global, &n, storage);
+                    }
+                }
 
                 let mut vars = acc;
                 vars.push(var);
                 self.global_var(vars)
             },
-            // 構造体定義
-            Token::Variable if s == Structure::Struct  => {
-                // Token::Structまでもどっているので一つSKIP
-                self.consume();
+            // 構造体（共用体）定義、構造体変数
+            _ if is_struct_def => {
+                // Token::Struct（Token::Union）までもどっているので一つSKIP
+                let keyword = self.next_consume();
 
                 // 構造体定義作成
                 let mut vars = acc;
-                vars.push(self.struct_def_or_var());
+                self.cur_storage = storage;
+                self.cur_qual = qual;
+                let def_or_var = self.struct_def_or_var(keyword);
+                self.cur_storage = Storage::Auto;
+                self.cur_qual = Qualifier::default();
+                match def_or_var {
+                    // 構造体変数の場合、初期化子とセミコロンを処理
+                    var @ AstType::Variable(_, _, _, _) => {
+                        vars.push(self.sub_logical(var));
+                        self.must_next(Token::SemiColon);
+                    }
+                    def => vars.push(def),
+                }
                 self.global_var(vars)
             },
-            _ => acc,
+            // 列挙型の定義のみ（列挙定数は型の解析時に登録）
+            Token::SemiColon if self.next().get_token_type() == Token::Enum => {
+                self.enum_specifier();
+                self.must_next(Token::SemiColon);
+                self.global_var(acc)
+            }
+            _ => {
+                // 関数定義の為、記憶域クラス指定子、型修飾子まで戻る
+                self.back(spec);
+                acc
+            }
+        }
+    }
+
+    // 記憶域クラス指定子
+    fn storage_class(&mut self) -> Storage {
+        match self.next().get_token_type() {
+            Token::Static => {
+                self.consume();
+                Storage::Static
+            }
+            Token::Extern => {
+                self.consume();
+                Storage::Extern
+            }
+            _ => Storage::Auto,
+        }
+    }
+
+    // 型修飾子
+    fn qualifiers(&mut self) -> Qualifier {
+        let mut qual = Qualifier::default();
+        loop {
+            match self.next().get_token_type() {
+                Token::Const => qual.is_const = true,
+                Token::Volatile => qual.is_volatile = true,
+                _ => return qual,
+            }
+            self.consume();
+        }
+    }
+
+    // 宣言の型修飾子決定
+    //
+    // 型指定子より前の修飾子は、ポインタであれば参照先を、それ以外は変数自身を修飾する
+    // (変数自身の修飾子, 参照先の修飾子)を返す
+    fn decl_qualifier(&self, s: &Structure, post: &Qualifier) -> (Qualifier, Qualifier) {
+        Self::split_qualifier(s, &self.cur_qual, post)
+    }
+
+    // 型指定子の前後の修飾子を、(変数自身の修飾子, 参照先の修飾子)へ振り分け
+    fn split_qualifier(s: &Structure, pre: &Qualifier, post: &Qualifier) -> (Qualifier, Qualifier) {
+        match s {
+            Structure::Pointer => (post.clone(), pre.clone()),
+            _ => (pre.merge(post), Qualifier::default()),
         }
     }
 
     // func def.
     fn func_def(&mut self) -> AstType {
-        // 型を取得.
+        // 記憶域クラス、型を取得（戻り値の型修飾子は無視）.
+        let storage = self.storage_class();
+        self.qualifiers();
         let (t, s) = self.generate_type();
 
         // 関数定義から始まらないとだめ（関数の中に様々な処理が入っている）.
         let token = self.next_consume();
         match token.get_token_type() {
             Token::Variable => {
-                self.switch_scope(Scope::Local(token.get_token_value()));
-
-                // 既に同じシンボルが登録されていればエラー.
-                if self.search_symbol(&Scope::Func, &token.get_token_value()).is_some() {
-                    panic!("{} {}: already define {}", file!(), line!(), token.get_token_value());
+                // プロトタイプ宣言であれば、定義は行わない.
+                if self.is_func_decl() {
+                    return self.func_decl(storage, t, s, token);
                 }
 
-                // 関数シンボルを登録.
-                self.sym_table.register_sym(Symbol::new(
-                    Scope::Func,
-                    token.get_token_value(),
-                    t.clone(),
-                    s.clone(),
-                ));
+                // 既に定義されていればエラー.
+                if self.def_funcs.contains(&token.get_token_value()) {
+                    let decl = self.search_symbol(&Scope::Func, &token.get_token_value()).map(|s| s.decl).unwrap_or_default();
+                    self.report(token, format!("redefinition of {:?} (previous declaration at {})", token.get_token_value(), decl));
+                } else {
+                    self.def_funcs.push(token.get_token_value());
+                }
+                self.switch_scope(Scope::Local(token.get_token_value()));
 
+                // 関数シンボルを登録し、本体を作成.
+                // 仮引数と本体の最も外側のブロックは同じスコープ
+                self.blocks = vec![vec![]];
+                let no_proto = self.next_n(1).get_token_type() == Token::RightParen;
+                let args = self.func_args();
+                self.register_func(storage, &t, &s, token, &args, no_proto);
+                let body = self.block();
+                self.blocks.clear();
                 AstType::FuncDef(
                     t,
                     s,
                     token.get_token_value(),
-                    Box::new(self.func_args()),
-                    Box::new(self.statement()),
+                    Box::new(args),
+                    Box::new(body),
                 )
             }
-            _ => panic!(
-                "{} {}: Not Exists Function def {:?}",
-                file!(),
-                line!(),
-                token
-            ),
+            _ => self.syntax_error(token, format!("expected function definition but found {}", token.get_token_type())),
+        }
+    }
+
+    // 変数シンボルの登録
+    //
+    // 同一スコープでの再宣言はエラー（最初の宣言位置を付記）
+    // ファイルスコープでは仮定義として、型が一致する再宣言を許可する
+    // 関数内では、外側や別のブロックの同名の変数と区別する為、番号を付けたシンボル名で登録する
+    // 戻り値は登録したシンボル名
+    fn declare(&mut self, token: &TokenInfo, sym: Symbol) -> String {
+        self.declarator = true;
+        let mut sym = sym;
+        if let (Scope::Local(_), Some(block)) = (&sym.scope, self.blocks.last()) {
+            let name = sym.var.clone();
+            if let Some((_, pre)) = block.iter().find(|(n, _)| *n == name).cloned() {
+                let decl = self.sym_table.search(&sym.scope, &pre).map(|s| s.decl).unwrap_or_default();
+                self.report(token, format!("redefinition of {:?} (previous declaration at {})", name, decl));
+                return pre;
+            }
+            let mut n = 0;
+            while self.sym_table.search(&sym.scope, &sym.var).is_some() {
+                n += 1;
+                sym.var = format!("{}.{}", name, n);
+            }
+            if let Some(block) = self.blocks.last_mut() {
+                block.push((name, sym.var.clone()));
+            }
+        }
+        let var = sym.var.clone();
+        match self.sym_table.search(&sym.scope, &sym.var) {
+            None => {
+                sym.decl = token.pos.to_string();
+                self.sym_table.register_sym(sym);
+            }
+            Some(ref pre) if sym.scope == Scope::Global => {
+                if pre.t != sym.t || pre.strt != sym.strt {
+                    self.report(token, format!("conflicting types for {:?} (previous declaration at {})", sym.var, pre.decl));
+                }
+            }
+            Some(ref pre) => {
+                self.report(token, format!("redefinition of {:?} (previous declaration at {})", sym.var, pre.decl));
+            }
+        }
+        var
+    }
+
+    // 使用位置で有効なローカル変数のシンボル名
+    fn visible_symbols(&self) -> Vec<String> {
+        self.blocks.iter().flatten().map(|(_, s)| s.clone()).collect()
+    }
+
+    // 参照できる変数のシンボル取得
+    //
+    // 関数内では内側のブロックから宣言を検索し、見つからなければグローバル変数とする（閉じたブロックの変数は参照できない）
+    fn search_var(&self, var: &str) -> Option<Symbol> {
+        if self.blocks.is_empty() {
+            return self.search_symbol(&self.cur_scope, var);
+        }
+        match self.blocks.iter().rev().find_map(|b| b.iter().rev().find(|(n, _)| n == var).map(|(_, s)| s.clone())) {
+            Some(name) => self.sym_table.search(&self.cur_scope, &name),
+            None => self.sym_table.search(&Scope::Global, var),
+        }
+    }
+
+    // 関数シンボル登録
+    //
+    // 引数はメンバーとして保持する（宣言済みの場合は、先の宣言を優先し、戻り値の型が異なればエラー）
+    // ()で宣言された場合は、仮引数の型が未指定であることを記録する
+    fn register_func(&mut self, storage: Storage, t: &Type, s: &Structure, token: &TokenInfo, args: &AstType, no_proto: bool) {
+        let name = &*token.get_token_value();
+        if let Some(pre) = self.search_symbol(&Scope::Func, name) {
+            if pre.t != *t || pre.strt != *s {
+                self.report(token, format!("conflicting types for {:?} (previous declaration at {})", name, pre.decl));
+            }
+            return;
+        }
+        let mut sym = Symbol::new(Scope::Func, name.to_string(), t.clone(), s.clone());
+        sym.storage = storage;
+        sym.decl = token.pos.to_string();
+        sym.no_proto = no_proto;
+        if let AstType::Argment(ref args) = *args {
+            sym.members = args.iter().filter_map(|a| match a {
+                AstType::Variable(ref t, ref s, ref n, _) => {
+                    Some(Symbol::new(Scope::Local(name.to_string()), n.clone(), t.clone(), s.clone()))
+                }
+                _ => None,
+            }).collect();
+        }
+        self.sym_table.register_sym(sym);
+    }
+
+    // 関数宣言判定
+    //
+    // 引数の閉じ括弧の後にセミコロンが続けばプロトタイプ宣言
+    fn is_func_decl(&mut self) -> bool {
+        let mut depth = 0;
+        let mut n = 0;
+        loop {
+            match self.next_n(n).get_token_type() {
+                Token::LeftParen => depth += 1,
+                Token::RightParen if depth == 1 => break,
+                Token::RightParen => depth -= 1,
+                Token::End => return false,
+                _ => {}
+            }
+            n += 1;
+        }
+        self.next_n(n + 1).get_token_type() == Token::SemiColon
+    }
+
+    // 関数宣言.
+    fn func_decl(&mut self, storage: Storage, t: Type, s: Structure, token: &TokenInfo) -> AstType {
+        let no_proto = self.next_n(1).get_token_type() == Token::RightParen;
+        let args = AstType::Argment(self.param_types());
+        self.must_next(Token::SemiColon);
+
+        self.register_func(storage, &t, &s, token, &args, no_proto);
+        AstType::FuncDecl(t, s, token.get_token_value(), Box::new(args))
+    }
+
+    // 仮引数の型リスト
+    //
+    // 引数は型のみ保持し、シンボルテーブルへは登録しない（引数名は省略可能）
+    fn param_types(&mut self) -> Vec<AstType> {
+        self.must_next(Token::LeftParen);
+        self.void_params();
+        let mut args = vec![];
+        while self.is_type_token() {
+            self.qualifiers();
+            let (arg_t, arg_s) = self.generate_type();
+            self.qualifiers();
+            let (arg_t, arg_s) = self.pointer_declarator(arg_t, arg_s);
+            let arg = if self.is_func_pointer() {
+                let token = self.next_n(2);
+                let (arg_name, strt, sig) = self.func_pointer_declarator(arg_t, arg_s);
+                AstType::Variable(Type::Func(Box::new(sig)), strt, arg_name, token.pos.to_string())
+            } else {
+                let pos = self.next().pos.to_string();
+                let arg_name = match self.next().get_token_type() {
+                    Token::Variable => self.next_consume().get_token_value(),
+                    _ => "".to_string(),
+                };
+                let dims = self.array_size(vec![]);
+                let (arg_t, arg_s) = Self::array_type(arg_t, arg_s, dims);
+                let (arg_t, arg_s) = decay(arg_t, arg_s);
+                AstType::Variable(arg_t, arg_s, arg_name, pos)
+            };
+            args.push(arg);
+
+            if self.next().get_token_type() != Token::Comma {
+                break;
+            }
+            self.consume();
         }
+        self.must_next(Token::RightParen);
+        args
+    }
+
+    // 関数ポインタ宣言判定
+    fn is_func_pointer(&mut self) -> bool {
+        self.next().get_token_type() == Token::LeftParen
+            && self.next_n(1).get_token_type() == Token::Multi
+    }
+
+    // 関数ポインタ宣言子
+    //
+    // (*name)(引数)、(*name[n])(引数)を解析し、(変数名, 構造, シグネチャ)を返す
+    fn func_pointer_declarator(&mut self, t: Type, s: Structure) -> (String, Structure, Signature) {
+        self.must_next(Token::LeftParen);
+        self.must_next(Token::Multi);
+        let name = match self.next().get_token_type() {
+            Token::Variable => self.next_consume().get_token_value(),
+            _ => "".to_string(),
+        };
+        let dims = self.array_size(vec![]);
+        self.must_next(Token::RightParen);
+
+        let params = self.param_types().into_iter().filter_map(|p| match p {
+            AstType::Variable(t, s, _, _) => Some((t, s)),
+            _ => None,
+        }).collect();
+        let strt = if dims.is_empty() { Structure::Pointer } else { Structure::Array(dims) };
+        (name, strt, Signature { ret: (t, s), params })
+    }
+
+    // 関数ポインタ変数
+    fn variable_func_pointer(&mut self, t: Type, s: Structure) -> AstType {
+        // 変数名は(*の後
+        let token = self.next_n(2);
+        let (name, strt, sig) = self.func_pointer_declarator(t, s);
+        let ty = Type::Func(Box::new(sig));
+
+        // シンボルテーブルへ保存.
+        let mut sym = Symbol::new(self.cur_scope.clone(), name.clone(), ty.clone(), strt.clone());
+        sym.storage = self.cur_storage.clone();
+        let name = self.declare(token, sym);
+        AstType::Variable(ty, strt, name, token.pos.to_string())
     }
 
     // typeトークンチェック
     fn is_type_token(&mut self) -> bool {
-        match self.next().get_token_type() {
-            Token::Int | Token::IntPointer | Token::Char | Token::CharPointer => true,
-            _ => false,
-        }
+        matches!(
+            self.next().get_token_type(),
+            Token::Int
+                | Token::IntPointer
+                | Token::Char
+                | Token::CharPointer
+                | Token::Void
+                | Token::VoidPointer
+                | Token::Struct
+                | Token::Union
+                | Token::Enum
+                | Token::Const
+                | Token::Volatile
+        )
     }
 
     // type/struct judge
@@ -245,10 +995,30 @@ impl<'a> AstGen<'a> {
             Token::IntPointer => (Type::Int, Structure::Pointer),
             Token::Char => (Type::Char, Structure::Identifier),
             Token::CharPointer => (Type::Char, Structure::Pointer),
-            Token::Struct => {
-                // 構造体の定義名を取得
-                let name = self.next();
-                (Type::Struct(name.get_token_value()), Structure::Struct)
+            Token::Void => (Type::Void, Structure::Identifier),
+            Token::VoidPointer => (Type::Void, Structure::Pointer),
+            Token::Struct | Token::Union => {
+                // 構造体の定義名を取得（ポインタであれば*も読み進める）
+                let name = self.next_consume();
+                let t = Self::tag_type(token, name.get_token_value());
+                match self.next().get_token_type() {
+                    Token::Multi => {
+                        self.consume();
+                        (t, Structure::Pointer)
+                    }
+                    _ => (t, Structure::Struct),
+                }
+            }
+            Token::Enum => {
+                self.back(1);
+                self.enum_specifier();
+                match self.next().get_token_type() {
+                    Token::Multi => {
+                        self.consume();
+                        (Type::Int, Structure::Pointer)
+                    }
+                    _ => (Type::Int, Structure::Identifier),
+                }
             }
             _ => (Type::Unknown("unknown type".to_string()), Structure::Unknown),
         }
@@ -256,17 +1026,30 @@ impl<'a> AstGen<'a> {
 
     // func argment.
     fn func_args(&mut self) -> AstType {
-        let token = self.next_consume();
+        let token = self.next();
         match token.get_token_type() {
             Token::LeftParen => {
                 // 引数を処理.
+                self.consume();
+                self.void_params();
                 let args = AstType::Argment(self.recur_func_args(vec![]));
 
                 // 閉じ括弧.
-                self.must_next(Token::RightParen, "ast.rs(func_arg): Not Exists RightParen");
+                self.must_next(Token::RightParen);
                 args
             }
-            _ => panic!("{} {}: Not Exists LeftParen {:?}", file!(), line!(), token),
+            // 括弧がなければ引数なしとして、本体の解析を続ける
+            _ => {
+                self.report(token, format!("expected '(' but found {}", token.get_token_type()));
+                AstType::Argment(vec![])
+            }
+        }
+    }
+
+    // 引数なしを表す(void)の読み飛ばし.
+    fn void_params(&mut self) {
+        if self.next().get_token_type() == Token::Void && self.next_n(1).get_token_type() == Token::RightParen {
+            self.consume();
         }
     }
 
@@ -277,9 +1060,18 @@ impl<'a> AstGen<'a> {
             return a;
         }
 
-        // 引数を評価
+        // 引数を評価（配列型の仮引数はポインタへ調整）
         let mut args = a;
-        args.push(self.assign());
+        let arg = match self.assign() {
+            AstType::Variable(t, s @ Structure::Array(_), n, pos) => {
+                let (t, s) = decay(t, s);
+                let scope = self.cur_scope.clone();
+                self.sym_table.set_type(&scope, &n, t.clone(), s.clone());
+                AstType::Variable(t, s, n, pos)
+            }
+            arg => arg,
+        };
+        args.push(arg);
 
         // カンマがあれば引き続き.
         match self.next().get_token_type() {
@@ -292,7 +1084,20 @@ impl<'a> AstGen<'a> {
     }
 
     // statement.
+    //
+    // ブロック内の宣言は、ブロックの終わりまで有効
     fn statement(&mut self) -> AstType {
+        self.blocks.push(vec![]);
+        let stmt = self.block();
+        self.blocks.pop();
+        stmt
+    }
+
+    // ブロック本体
+    fn block(&mut self) -> AstType {
+        if self.next().get_token_type() == Token::LeftBrace {
+            self.consume();
+        }
         AstType::Statement(self.sub_statement(&[]))
     }
 
@@ -300,6 +1105,21 @@ impl<'a> AstGen<'a> {
     fn sub_statement(&mut self, expr: &[AstType]) -> Vec<AstType> {
         // トークンがなくなるまで、構文木生成.
         let mut stmt = expr.to_owned();
+
+        // 直前の文に構文エラーがあれば、エラーノードに置き換えて同期
+        if self.is_recovering() {
+            stmt.pop();
+            stmt.push(AstType::Error());
+            self.synchronize();
+        }
+
+        // ジャンプ文の直後の文は実行されない
+        if let Some(AstType::Return(_, _) | AstType::ReturnVoid(_) | AstType::Break() | AstType::Continue()) = stmt.last() {
+            let token = self.next();
+            if !matches!(token.get_token_type(), Token::RightBrace | Token::SemiColon | Token::End) {
+                self.warn(token, WarningKind::UnreachableCode, "code will never be executed".to_string());
+            }
+        }
         let token = self.next_consume();
         match token.get_token_type() {
             Token::If => {
@@ -326,42 +1146,102 @@ impl<'a> AstGen<'a> {
                 stmt.push(self.statement_break());
                 self.sub_statement(&stmt)
             }
-            Token::LeftBrace => self.sub_statement(&stmt),
+            Token::Static => {
+                stmt.push(self.statement_static());
+                self.sub_statement(&stmt)
+            }
+            Token::StaticAssert => {
+                stmt.push(self.static_assert(token));
+                self.sub_statement(&stmt)
+            }
+            Token::LeftBrace => {
+                self.back(1);
+                stmt.push(self.statement());
+                self.sub_statement(&stmt)
+            }
             Token::SemiColon => self.sub_statement(&stmt),
             Token::RightBrace => stmt,
+            // 入れ子のブロックでも一度だけ報告する
+            Token::End => {
+                self.back(1);
+                self.syntax_error(token, format!("expected {} but found {}", Token::RightBrace, Token::End));
+                stmt
+            }
             Token::Comma => {
                 // 前の変数の型を考慮
-                let var = self.continue_variable_define(&stmt);
+                let var = self.continue_variable_define(token, &stmt);
+                self.statement_end(&var);
                 stmt.push(var);
                 self.sub_statement(&stmt)
             }
             _ => {
                 self.back(1);
-                stmt.push(self.expression());
+                let expr = self.expression();
+                self.statement_end(&expr);
+                stmt.push(expr);
                 self.sub_statement(&stmt)
             }
         }
     }
 
+    // 文の終端(;)確認.
+    //
+    // 宣言子の区切り(,)は続く宣言子の解析に、構文エラー後は同期処理に、入力の終端はブロックの解析に任せる
+    // （構造体定義は、定義の解析で;まで読み進めている）
+    fn statement_end(&mut self, stmt: &AstType) {
+        if self.panic_mode || matches!(stmt, AstType::Struct(..)) {
+            return;
+        }
+        if !matches!(self.next().get_token_type(), Token::Comma | Token::End) {
+            self.must_next(Token::SemiColon);
+        }
+    }
+
     // continue variable
-    fn continue_variable_define(&mut self, stmt: &[AstType]) -> AstType {
-        let last = stmt.last();
-        match last {
-            Some(ref s) => match s {
-                AstType::Variable(ref t, ref s, ref _n) => match t {
-                    Type::Int if s == &Structure::Identifier => self.factor_int(),
-                    Type::Char if s == &Structure::Identifier => self.factor_char(),
-                    Type::Int if s == &Structure::Pointer => {
-                        self.variable(Type::Int, Structure::Pointer)
-                    }
-                    Type::Char if s == &Structure::Pointer => {
-                        self.variable(Type::Char, Structure::Pointer)
-                    }
-                    _ => panic!("{} {}: Not Support Type {:?}", file!(), line!(), t),
-                },
-                _ => panic!("{} {}: Not Support Ast {:?}", file!(), line!(), s),
+    //
+    // 先の宣言子の記憶域クラス、型修飾子（ポインタの場合は型指定子より前の修飾子）を引き継ぐ
+    fn continue_variable_define(&mut self, comma: &TokenInfo, stmt: &[AstType]) -> AstType {
+        // static指定、初期化子付きの場合、宣言された変数の型を考慮
+        let last = match stmt.last() {
+            Some(AstType::StaticVar(ref v)) => Some(&**v),
+            last => last,
+        };
+        let last = match last {
+            Some(AstType::Init(ref v, _, _)) => Some(&**v),
+            last => last,
+        };
+        let (storage, qual) = match last {
+            Some(AstType::Variable(_, _, ref n, _)) => match self.sym_table.search(&self.cur_scope, n) {
+                Some(ref sym) if sym.strt == Structure::Pointer => (sym.storage.clone(), sym.ptr_qual.clone()),
+                Some(sym) => (sym.storage, sym.qual),
+                None => (Storage::Auto, Qualifier::default()),
+            },
+            _ => (Storage::Auto, Qualifier::default()),
+        };
+        self.cur_storage = storage.clone();
+        self.cur_qual = qual;
+        let var = match last {
+            Some(AstType::Variable(ref t, ref s, ref _n, _)) => match t {
+                Type::Int if s == &Structure::Identifier => self.factor_int(),
+                Type::Char if s == &Structure::Identifier => self.factor_char(),
+                Type::Int if s == &Structure::Pointer => {
+                    self.variable(Type::Int, Structure::Pointer)
+                }
+                Type::Char if s == &Structure::Pointer => {
+                    self.variable(Type::Char, Structure::Pointer)
+                }
+                _ => self.syntax_error(comma, format!("unexpected {} after {}", Token::Comma, type_name(t, s))),
             },
-            None => panic!("{} {}: Not exists Variable", file!(), line!()),
+            _ => self.syntax_error(comma, format!("unexpected {}", Token::Comma)),
+        };
+
+        // 初期化子
+        let var = self.sub_logical(var);
+        self.cur_storage = Storage::Auto;
+        self.cur_qual = Qualifier::default();
+        match storage {
+            Storage::Static => AstType::StaticVar(Box::new(var)),
+            _ => var,
         }
     }
 
@@ -369,27 +1249,18 @@ impl<'a> AstGen<'a> {
     //
     // ブロック部が一行の場合、asm部が期待しているAstType::Statementでexpression結果を包む
     fn statement_if(&mut self) -> AstType {
-        self.must_next(
-            Token::LeftParen,
-            "ast.rs(statement_if): Not Exists LeftParen",
-        );
+        self.must_next(Token::LeftParen);
 
         // 条件式を解析.
-        let condition = self.assign();
-        self.must_next(
-            Token::RightParen,
-            "ast.rs(statement_if): Not Exists RightParen",
-        );
+        let condition = self.condition_expr();
+        self.must_next(Token::RightParen);
 
         // ifブロック内を解析.
         let stmt = match self.next().get_token_type() {
             Token::LeftBrace => self.statement(),
             _ => {
                 let expr = AstType::Statement(vec![self.expression()]);
-                self.must_next(
-                    Token::SemiColon,
-                    "ast.rs(statement_if): Not Exists SemiColon",
-                );
+                self.must_next(Token::SemiColon);
                 expr
             }
         };
@@ -402,10 +1273,7 @@ impl<'a> AstGen<'a> {
                     Token::LeftBrace => self.statement(),
                     _ => {
                         let expr = AstType::Statement(vec![self.expression()]);
-                        self.must_next(
-                            Token::SemiColon,
-                            "ast.rs(statement_if): Not Exists SemiColon",
-                        );
+                        self.must_next(Token::SemiColon);
                         expr
                     }
                 };
@@ -421,17 +1289,11 @@ impl<'a> AstGen<'a> {
 
     // while statement.
     fn statement_while(&mut self) -> AstType {
-        self.must_next(
-            Token::LeftParen,
-            "ast.rs(statement_while): Not Exists LeftParen",
-        );
+        self.must_next(Token::LeftParen);
 
         // 条件式を解析.
-        let condition = self.assign();
-        self.must_next(
-            Token::RightParen,
-            "ast.rs(statement_while): Not Exists RightParen",
-        );
+        let condition = self.condition_expr();
+        self.must_next(Token::RightParen);
 
         AstType::While(Box::new(condition), Box::new(self.statement()))
     }
@@ -440,67 +1302,61 @@ impl<'a> AstGen<'a> {
     fn statement_do(&mut self) -> AstType {
         // ブロック部.
         let stmt = self.statement();
-        self.must_next(Token::While, "ast.rs(statement_do): Not Exists while token");
+        self.must_next(Token::While);
 
         // 条件式を解析.
-        self.must_next(
-            Token::LeftParen,
-            "ast.rs(statement_do): Not Exists LeftParen",
-        );
-        let condition = self.assign();
-        self.must_next(
-            Token::RightParen,
-            "ast.rs(statement_while): Not Exists RightParen",
-        );
+        self.must_next(Token::LeftParen);
+        let condition = self.condition_expr();
+        self.must_next(Token::RightParen);
 
         AstType::Do(Box::new(stmt), Box::new(condition))
     }
 
     // for statement.
+    //
+    // 初期化部の宣言は、for文の終わりまで有効
     fn statement_for(&mut self) -> AstType {
-        self.must_next(
-            Token::LeftParen,
-            "ast.rs(statement_for): Not Exists LeftParen",
-        );
+        self.blocks.push(vec![]);
+        self.must_next(Token::LeftParen);
 
         // 各種条件を解析.
         let begin = match self.next().get_token_type() {
             Token::SemiColon => None,
-            _ => Some(self.assign()),
+            _ => Some(self.expression()),
         };
-        self.must_next(
-            Token::SemiColon,
-            "ast.rs(statement_for): Not Exists Semicolon",
-        );
+        self.must_next(Token::SemiColon);
 
         let condition = match self.next().get_token_type() {
             Token::SemiColon => None,
-            _ => Some(self.assign()),
+            _ => Some(self.condition_expr()),
         };
-        self.must_next(
-            Token::SemiColon,
-            "ast.rs(statement_for): Not Exists Semicolon",
-        );
+        self.must_next(Token::SemiColon);
 
         let end = match self.next().get_token_type() {
             Token::RightParen => None,
-            _ => Some(self.assign()),
+            _ => Some(self.comma()),
         };
-        self.must_next(
-            Token::RightParen,
-            "ast.rs(statement_for): Not Exists RightParen",
-        );
+        self.must_next(Token::RightParen);
 
-        AstType::For(
-            Box::new(begin),
-            Box::new(condition),
-            Box::new(end),
-            Box::new(self.statement()),
-        )
+        let stmt = self.statement();
+        self.blocks.pop();
+        AstType::For(Box::new(begin), Box::new(condition), Box::new(end), Box::new(stmt))
     }
 
-    // continue statement.
-    fn statement_continue(&mut self) -> AstType {
+    // 制御文の条件式.
+    //
+    // 括弧で囲まれていない代入は、比較の誤りとして警告する
+    fn condition_expr(&mut self) -> AstType {
+        let token = self.next();
+        let condition = self.comma();
+        if matches!(condition, AstType::Assign(_, _, _)) && token.get_token_type() != Token::LeftParen {
+            self.warn(token, WarningKind::Parentheses, "suggest parentheses around assignment used as truth value".to_string());
+        }
+        condition
+    }
+
+    // continue statement.
+    fn statement_continue(&mut self) -> AstType {
         AstType::Continue()
     }
 
@@ -509,20 +1365,91 @@ impl<'a> AstGen<'a> {
         AstType::Break()
     }
 
+    // static variable.
+    //
+    // 関数スコープのシンボルとして登録し、領域は静的に確保する
+    fn statement_static(&mut self) -> AstType {
+        self.cur_storage = Storage::Static;
+        let var = self.assign();
+        self.cur_storage = Storage::Auto;
+        AstType::StaticVar(Box::new(var))
+    }
+
+    // 静的アサーション.
+    //
+    // 評価は意味解析で行うので、診断用に宣言位置を保持する
+    fn static_assert(&mut self, token: &TokenInfo) -> AstType {
+        self.must_next(Token::LeftParen);
+        let expr = self.condition();
+        self.must_next(Token::Comma);
+        let msg = self.next();
+        if msg.get_token_type() != Token::StringLiteral {
+            return self.syntax_error(msg, format!("expected string literal but found {}", msg.get_token_type()));
+        }
+        self.consume();
+        self.must_next(Token::RightParen);
+        self.must_next(Token::SemiColon);
+        AstType::StaticAssert(Box::new(expr), msg.get_token_value(), token.pos.to_string())
+    }
+
     // return statement.
-    fn statement_return(&mut self) -> AstType {
-        let expr = self.assign();
-        AstType::Return(Box::new(expr))
+    fn statement_return(&mut self, token: &TokenInfo) -> AstType {
+        if Token::SemiColon == self.next().get_token_type() {
+            return AstType::ReturnVoid(token.pos.to_string());
+        }
+        let expr = self.comma();
+        AstType::Return(Box::new(expr), token.pos.to_string())
     }
 
     // expression.
     fn expression(&mut self) -> AstType {
         match self.next().get_token_type() {
             Token::Return => {
+                let token = self.next_consume();
+                self.statement_return(token)
+            }
+            // 宣言のカンマは、宣言子の区切り
+            _ if self.is_declaration() => self.assign(),
+            _ => self.comma(),
+        }
+    }
+
+    // 宣言判定.
+    fn is_declaration(&mut self) -> bool {
+        matches!(
+            self.next().get_token_type(),
+            Token::Int
+                | Token::IntPointer
+                | Token::Char
+                | Token::CharPointer
+                | Token::Void
+                | Token::VoidPointer
+                | Token::Struct
+                | Token::Union
+                | Token::Enum
+                | Token::Const
+                | Token::Volatile
+                | Token::Extern
+        )
+    }
+
+    // comma.
+    //
+    // 左から順に評価し、右端の値を結果とする
+    fn comma(&mut self) -> AstType {
+        let left = self.assign();
+        self.sub_comma(left)
+    }
+
+    // sub comma.
+    fn sub_comma(&mut self, acc: AstType) -> AstType {
+        match self.next().get_token_type() {
+            Token::Comma => {
                 self.consume();
-                self.statement_return()
+                let right = self.assign();
+                self.sub_comma(AstType::Comma(Box::new(acc), Box::new(right)))
             }
-            _ => self.assign(),
+            _ => acc,
         }
     }
 
@@ -536,35 +1463,12 @@ impl<'a> AstGen<'a> {
         match token.get_token_type() {
             Token::Variable if Token::Assign == next_token.get_token_type() => {
                 let var = self.factor();
-                self.consume();  // Assignトークン消費
-                AstType::Assign(Box::new(var), Box::new(self.condition()))
-            }
-            Token::Variable if Token::PlusAssign == next_token.get_token_type() => {
-                let var = self.factor();
-                self.consume();  // Assignトークン消費
-                AstType::PlusAssign(Box::new(var), Box::new(self.condition()))
-            }
-            Token::Variable if Token::MinusAssign == next_token.get_token_type() => {
-                let var = self.factor();
-                self.consume();  // Assignトークン消費
-                AstType::MinusAssign(Box::new(var), Box::new(self.condition()))
-             }
-            Token::Variable if Token::MultipleAssign == next_token.get_token_type() => {
-                let var = self.factor();
-                self.consume();  // Assignトークン消費
-                AstType::MultipleAssign(Box::new(var), Box::new(self.condition()))
-            }
-            Token::Variable if Token::DivisionAssign == next_token.get_token_type() => {
-                let var = self.factor();
-                self.consume();  // Assignトークン消費
-                AstType::DivisionAssign(Box::new(var), Box::new(self.condition()))
+                let ope = self.next_consume();  // Assignトークン消費
+                let value = self.condition();
+                self.check_narrowing(ope, &var, &value);
+                AstType::Assign(Box::new(var), Box::new(value), ope.pos.to_string())
             }
-            Token::Variable if Token::RemainderAssign == next_token.get_token_type() => {
-                let var = self.factor();
-                self.consume();  // Assignトークン消費
-                AstType::RemainderAssign(Box::new(var), Box::new(self.condition()))
-             }
-             _ => self.condition(),
+            _ => self.condition(),
         }
     }
 
@@ -576,14 +1480,15 @@ impl<'a> AstGen<'a> {
                 let call_func = AstType::FuncCall(
                     Box::new(acc),
                     Box::new(self.argment(AstType::Argment(vec![]))),
+                    token.pos.to_string(),
                 );
-                self.must_next(
-                    Token::RightParen,
-                    "ast.rs(call_func): Not exists RightParen",
-                );
+                self.must_next(Token::RightParen);
                 call_func
             }
-            _ => panic!("{} {}: Not exists LeftParen: {:?}", file!(), line!(), token),
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected '(' but found {}", token.get_token_type()))
+            }
         }
     }
 
@@ -631,7 +1536,7 @@ impl<'a> AstGen<'a> {
                 let middle = self.logical();
 
                 // コロンがない場合、終了.
-                self.must_next(Token::Colon, "ast.rs(sub_condition): Not exists Colon");
+                self.must_next(Token::Colon);
 
                 let right = self.logical();
                 let tree = AstType::Condition(Box::new(acc), Box::new(middle), Box::new(right));
@@ -651,21 +1556,189 @@ impl<'a> AstGen<'a> {
     fn sub_logical(&mut self, acc: AstType) -> AstType {
         let create = |ope: Token, left, right| match ope {
             Token::LogicalAnd => AstType::LogicalAnd(Box::new(left), Box::new(right)),
-            Token::Assign => AstType::Assign(Box::new(left), Box::new(right)),
             _ => AstType::LogicalOr(Box::new(left), Box::new(right)),
         };
 
         let ope_type = self.next().get_token_type();
         match ope_type {
-            Token::LogicalAnd | Token::LogicalOr | Token::Assign => {
+            // 宣言子に続く場合は初期化子
+            Token::Assign if self.is_initializer(&acc) => {
+                let ope = self.next_consume();
+                let init = match self.next().get_token_type() {
+                    Token::LeftBrace => self.init_list(),
+                    _ => self.condition(),
+                };
+                let var = self.complete_array(ope, acc, &init);
+                self.check_narrowing(ope, &var, &init);
+                self.check_excess_init(ope, &var, &init);
+                AstType::Init(Box::new(var), Box::new(init), ope.pos.to_string())
+            }
+            // 代入（右結合、右辺は条件式）
+            Token::Assign => {
+                let ope = self.next_consume();
+                let value = self.condition();
+                self.check_narrowing(ope, &acc, &value);
+                AstType::Assign(Box::new(acc), Box::new(value), ope.pos.to_string())
+            }
+            Token::LogicalAnd | Token::LogicalOr => {
                 self.consume();
                 let right = self.bit_operator();
                 self.sub_logical(create(ope_type, acc, right))
             }
+            // 複合代入（右結合）
+            Token::PlusAssign
+            | Token::MinusAssign
+            | Token::MultipleAssign
+            | Token::DivisionAssign
+            | Token::RemainderAssign
+            | Token::BitAndAssign
+            | Token::BitOrAssign
+            | Token::BitXorAssign
+            | Token::LeftShiftAssign
+            | Token::RightShiftAssign => {
+                let ope = self.next_consume();
+                let (left, right) = (Box::new(acc), Box::new(self.condition()));
+                self.check_zero_division(ope, &right);
+                if matches!(ope_type, Token::PlusAssign | Token::MinusAssign) {
+                    self.check_pointer_operands(ope, &left, &right);
+                    self.check_void_pointer(ope, &left);
+                }
+                let pos = ope.pos.to_string();
+                match ope_type {
+                    Token::PlusAssign => AstType::PlusAssign(left, right, pos),
+                    Token::MinusAssign => AstType::MinusAssign(left, right, pos),
+                    Token::MultipleAssign => AstType::MultipleAssign(left, right, pos),
+                    Token::DivisionAssign => AstType::DivisionAssign(left, right, pos),
+                    Token::RemainderAssign => AstType::RemainderAssign(left, right, pos),
+                    Token::BitAndAssign => AstType::BitAndAssign(left, right, pos),
+                    Token::BitOrAssign => AstType::BitOrAssign(left, right, pos),
+                    Token::BitXorAssign => AstType::BitXorAssign(left, right, pos),
+                    Token::LeftShiftAssign => AstType::LeftShiftAssign(left, right, pos),
+                    _ => AstType::RightShiftAssign(left, right, pos),
+                }
+            }
             _ => acc,
         }
     }
 
+    // 暗黙の縮小変換チェック
+    //
+    // 代入先より幅の広い整数型の値は警告する（値が収まる定数は除く）
+    fn check_narrowing(&mut self, token: &TokenInfo, var: &AstType, value: &AstType) {
+        let width = |t: &Type| match t {
+            Type::Char => Some(8),
+            Type::Short => Some(16),
+            Type::Int => Some(32),
+            Type::Long => Some(64),
+            _ => None,
+        };
+        let (to, from) = match (var.value_type(), value.value_type()) {
+            (Some((to, Structure::Identifier)), Some((from, Structure::Identifier))) => (to, from),
+            _ => return,
+        };
+        if let (Some(w), Some(fw)) = (width(&to), width(&from)) {
+            let fits = value.const_value().is_some_and(|v| (v << (64 - w)) >> (64 - w) == v);
+            if fw > w && !fits {
+                let msg = format!(
+                    "conversion from {} to {} may change value",
+                    type_name(&from, &Structure::Identifier),
+                    type_name(&to, &Structure::Identifier)
+                );
+                self.warn(token, WarningKind::Conversion, msg);
+            }
+        }
+    }
+
+    // 配列の要素数を超える初期化子チェック
+    //
+    // 超過分は配置されないので警告する（文字列リテラルは終端文字が収まらない場合を許容）
+    fn check_excess_init(&mut self, token: &TokenInfo, var: &AstType, init: &AstType) {
+        let (t, dims) = match var {
+            AstType::Variable(ref t, Structure::Array(ref dims), _, _) => (t, dims),
+            _ => return,
+        };
+        let msg = match init {
+            AstType::StringLiteral(ref s, _) if *t == Type::Char && dims.len() == 1 && s.len() > dims[0] => {
+                "initializer-string for array is too long"
+            }
+            AstType::InitList(ref items) => {
+                // 波括弧が省略されている多次元配列は、全要素数と比較
+                let nested = items.iter().any(|i| matches!(i, AstType::InitList(_)));
+                let len = if nested { dims[0] } else { dims.iter().product() };
+                if items.len() <= len {
+                    return;
+                }
+                "excess elements in array initializer"
+            }
+            _ => return,
+        };
+        self.warn(token, WarningKind::ExcessInitializers, msg.to_string());
+    }
+
+    // 初期化子判定
+    //
+    // 宣言子の直後の=は代入ではなく初期化（判定済みの印は消費する）
+    fn is_initializer(&mut self, acc: &AstType) -> bool {
+        std::mem::replace(&mut self.declarator, false) && matches!(acc, AstType::Variable(_, _, _, _))
+    }
+
+    // 要素数が省略された配列の補完
+    //
+    // 初期化子の要素数（文字列リテラルは終端文字を含む長さ）から決定し、シンボルテーブルも更新する
+    fn complete_array(&mut self, token: &TokenInfo, var: AstType, init: &AstType) -> AstType {
+        let (t, dims, name, pos) = match var {
+            AstType::Variable(ref t, Structure::Array(ref dims), ref name, ref pos) if dims[0] == 0 => {
+                (t.clone(), dims.clone(), name.clone(), pos.clone())
+            }
+            _ => return var,
+        };
+        let len = match init {
+            AstType::StringLiteral(ref s, _) if t == Type::Char && dims.len() == 1 => s.len() + 1,
+            AstType::InitList(ref items) => {
+                // 多次元配列で波括弧が省略されている場合、後続の次元の要素数単位で切り上げ
+                let nested = items.iter().any(|i| matches!(i, AstType::InitList(_)));
+                let inner = dims[1..].iter().product::<usize>();
+                match inner {
+                    0 => 0,
+                    _ if nested => items.len(),
+                    _ => items.len().div_ceil(inner),
+                }
+            }
+            _ => 0,
+        };
+        if len == 0 {
+            self.report(token, format!("array size missing in {:?}", name));
+            return var;
+        }
+        let mut dims = dims;
+        dims[0] = len;
+        let s = Structure::Array(dims);
+        self.sym_table.set_type(&self.cur_scope.clone(), &name, t.clone(), s.clone());
+        AstType::Variable(t, s, name, pos)
+    }
+
+    // 初期化子リスト.
+    fn init_list(&mut self) -> AstType {
+        self.must_next(Token::LeftBrace);
+
+        // 右波括弧が表れるまで、要素とみなす（末尾のカンマも許容）
+        let mut items = vec![];
+        while Token::RightBrace != self.next().get_token_type() {
+            let item = match self.next().get_token_type() {
+                Token::LeftBrace => self.init_list(),
+                _ => self.condition(),
+            };
+            items.push(item);
+
+            if Token::Comma != self.next().get_token_type() {
+                break;
+            }
+            self.consume();
+        }
+        self.must_next(Token::RightBrace);
+        AstType::InitList(items)
+    }
+
     // bit operator.
     fn bit_operator(&mut self) -> AstType {
         let left = self.relation();
@@ -710,7 +1783,8 @@ impl<'a> AstGen<'a> {
             _ => panic!("{} {}: Not Support Token Type {:?}", file!(), line!(), ope),
         };
 
-        let ope_type = self.next().get_token_type();
+        let ope = self.next();
+        let ope_type = ope.get_token_type();
         match ope_type {
             Token::Equal
             | Token::NotEqual
@@ -720,12 +1794,28 @@ impl<'a> AstGen<'a> {
             | Token::GreaterThanEqual => {
                 self.consume();
                 let right = self.shift();
+                if Self::is_pointer_int_compare(&acc, &right) || Self::is_pointer_int_compare(&right, &acc) {
+                    self.warn(ope, WarningKind::PointerIntegerCompare, "comparison between pointer and integer".to_string());
+                }
                 self.sub_relation(create(ope_type, acc, right))
             }
             _ => acc,
         }
     }
 
+    // ポインタと整数の比較判定（定数0はヌルポインタとして除く）
+    fn is_pointer_int_compare(p: &AstType, i: &AstType) -> bool {
+        let is_pointer = matches!(p.value_type(), Some((_, Structure::Pointer)) | Some((_, Structure::Array(_))));
+        let is_int = matches!(
+            i.value_type(),
+            Some((Type::Int, Structure::Identifier))
+                | Some((Type::Char, Structure::Identifier))
+                | Some((Type::Short, Structure::Identifier))
+                | Some((Type::Long, Structure::Identifier))
+        );
+        is_pointer && is_int && i.const_value() != Some(0)
+    }
+
     // shift operation.
     fn shift(&mut self) -> AstType {
         let left = self.expr();
@@ -768,12 +1858,67 @@ impl<'a> AstGen<'a> {
             Token::Plus | Token::Minus => {
                 self.consume();
                 let right = self.term();
+                self.check_pointer_offset(ope, &acc, &right);
+                self.check_pointer_operands(ope, &acc, &right);
+                self.check_void_pointer(ope, &acc);
+                self.check_void_pointer(ope, &right);
                 self.expr_add_sub(create(ope.get_token_type(), acc, right))
             }
             _ => acc,
         }
     }
 
+    // 配列へのポインタ演算の範囲チェック
+    //
+    // 要素数が既知の配列に定数を加減算した結果は、先頭から末尾の次の要素までの範囲でなければならない
+    fn check_pointer_offset(&mut self, ope: &TokenInfo, left: &AstType, right: &AstType) {
+        let (array, offset) = match ope.get_token_type() {
+            Token::Plus if right.is_pointer_value() => (right, left.const_value()),
+            Token::Minus => (left, right.const_value().map(|v| -v)),
+            _ => (left, right.const_value()),
+        };
+        if let (Some((_, Structure::Array(dims))), Some(offset)) = (array.value_type(), offset) {
+            if offset < 0 || offset > dims[0] as i64 {
+                let msg = format!(
+                    "pointer arithmetic with offset {} is outside the bounds of the array (which contains {} elements)",
+                    offset, dims[0]
+                );
+                self.warn(ope, WarningKind::ArrayBounds, msg);
+            }
+        }
+    }
+
+    // ポインタ演算の被演算子チェック
+    //
+    // ポインタ同士の加算、整数からのポインタの減算、型の異なるポインタ同士の減算を検出する
+    fn check_pointer_operands(&mut self, ope: &TokenInfo, left: &AstType, right: &AstType) {
+        let plus = matches!(ope.get_token_type(), Token::Plus);
+        let invalid = match (left.value_type(), right.value_type()) {
+            (Some((ref t1, _)), Some((ref t2, _))) if left.is_pointer_value() && right.is_pointer_value() => {
+                matches!(ope.get_token_type(), Token::Plus | Token::PlusAssign) || t1 != t2
+            }
+            // 整数 + ポインタのみ許可
+            _ => right.is_pointer_value() && !plus,
+        };
+        if invalid {
+            let name = |e: &AstType| e.value_type().map(|(t, s)| type_name(&t, &s)).unwrap_or_default();
+            let msg = format!("invalid operands to binary {}: {} and {}", ope.get_token_value(), name(left), name(right));
+            self.report(ope, msg);
+        }
+    }
+
+    // 定数によるゼロ除算チェック
+    fn check_zero_division(&mut self, ope: &TokenInfo, right: &AstType) {
+        if right.const_value() == Some(0) {
+            let msg = match ope.get_token_type() {
+                Token::Division | Token::DivisionAssign => "division by zero",
+                Token::Remainder | Token::RemainderAssign => "modulo by zero",
+                _ => return,
+            };
+            self.warn(ope, WarningKind::DivByZero, msg.to_string());
+        }
+    }
+
     // term.
     fn term(&mut self) -> AstType {
         let left = self.factor();
@@ -793,6 +1938,7 @@ impl<'a> AstGen<'a> {
             Token::Multi | Token::Division | Token::Remainder => {
                 self.consume();
                 let right = self.factor();
+                self.check_zero_division(ope, &right);
                 self.term_multi_div(create(ope.get_token_type(), acc, right))
             }
             _ => acc,
@@ -801,53 +1947,184 @@ impl<'a> AstGen<'a> {
 
     // factor.
     fn factor(&mut self) -> AstType {
+        self.declarator = false;
         let token = self.next_consume();
         match token.get_token_type() {
-            Token::Inc => AstType::PreInc(Box::new(self.factor())),
-            Token::Dec => AstType::PreDec(Box::new(self.factor())),
+            Token::Inc | Token::Dec => {
+                let operand = self.factor();
+                self.check_void_pointer(token, &operand);
+                match token.get_token_type() {
+                    Token::Inc => AstType::PreInc(Box::new(operand), token.pos.to_string()),
+                    _ => AstType::PreDec(Box::new(operand), token.pos.to_string()),
+                }
+            }
             Token::Plus => AstType::UnPlus(Box::new(self.factor())),
             Token::Minus => AstType::UnMinus(Box::new(self.factor())),
             Token::Not => AstType::Not(Box::new(self.factor())),
             Token::BitReverse => AstType::BitReverse(Box::new(self.factor())),
             Token::SizeOf => self.factor_sizeof(),
-            Token::IntPointer => self.variable(Type::Int, Structure::Pointer),
-            Token::CharPointer => self.variable(Type::Char, Structure::Pointer),
-            Token::And => AstType::Address(Box::new(self.factor())),
-            Token::Multi => AstType::Indirect(Box::new(self.factor())),
+            Token::AlignOf => self.factor_alignof(),
+            Token::IntPointer => self.factor_pointer(Type::Int),
+            Token::CharPointer => self.factor_pointer(Type::Char),
+            Token::VoidPointer => self.factor_pointer(Type::Void),
+            Token::And => self.factor_address(token),
+            Token::Multi => {
+                let operand = self.factor();
+                self.check_void_pointer(token, &operand);
+                AstType::Indirect(Box::new(operand))
+            }
             Token::Number => self.number(token),
             Token::Int => self.factor_int(),
             Token::Char => self.factor_char(),
+            Token::Void => self.factor_void(token),
             Token::StringLiteral => self.string_literal(token),
-            Token::Struct => self.struct_def_or_var(),
+            Token::Struct | Token::Union => self.struct_def_or_var(token),
+            Token::Enum => self.factor_enum(),
+            Token::Const | Token::Volatile => self.factor_qualified(),
             Token::Variable => {
                 // variable位置へ
                 self.back(1);
-                self.factor_variable(&token)
+                self.factor_variable(token)
             }
+            Token::LeftParen if self.is_type_name(0) => self.factor_cast(token),
             Token::LeftParen => {
-                let tree = self.assign();
-                self.must_next(Token::RightParen, "ast.rs(factor): Not exists RightParen");
-                tree
+                let tree = self.comma();
+                self.must_next(Token::RightParen);
+
+                // (*fp)(...)の様な関数ポインタ経由の呼び出し
+                match self.next().get_token_type() {
+                    Token::LeftParen if tree.func_signature().is_some() => self.call_func(tree),
+                    _ => self.postfix_member(tree),
+                }
+            }
+            // 不正なトークンは読み進めない（同期処理に任せる）
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected expression but found {}", token.get_token_type()))
+            }
+        }
+    }
+
+    // キャスト演算子.
+    fn factor_cast(&mut self, token: &TokenInfo) -> AstType {
+        let (t, s) = self.type_name();
+        self.must_next(Token::RightParen);
+        AstType::Cast(t, s, Box::new(self.factor()), token.pos.to_string())
+    }
+
+    // 型修飾子付き宣言作成
+    fn factor_qualified(&mut self) -> AstType {
+        self.back(1);
+        self.cur_qual = self.qualifiers();
+        let var = self.factor();
+        self.cur_qual = Qualifier::default();
+        var
+    }
+
+    // 列挙型の宣言作成
+    //
+    // 列挙型の変数はintとして扱う（定義のみの場合は空の文）
+    fn factor_enum(&mut self) -> AstType {
+        self.back(1);
+        self.enum_specifier();
+        match self.next().get_token_type() {
+            Token::SemiColon => AstType::Statement(vec![]),
+            Token::Multi => {
+                self.consume();
+                self.variable(Type::Int, Structure::Pointer)
+            }
+            _ => self.factor_int(),
+        }
+    }
+
+    // 列挙型指定子
+    //
+    // 列挙子は定数式で値を指定でき、省略時は直前の値+1（先頭は0）とする
+    fn enum_specifier(&mut self) -> (Type, Structure) {
+        self.must_next(Token::Enum);
+        if self.next().get_token_type() == Token::Variable {
+            self.consume();
+        }
+        if self.next().get_token_type() != Token::LeftBrace {
+            return (Type::Int, Structure::Identifier);
+        }
+        self.consume();
+
+        let mut value = 0;
+        while self.next().get_token_type() != Token::RightBrace {
+            let name = self.next();
+            if name.get_token_type() != Token::Variable {
+                self.syntax_error(name, format!("expected enumerator name but found {}", name.get_token_type()));
+                break;
+            }
+            self.consume();
+            if self.next().get_token_type() == Token::Assign {
+                self.consume();
+                let token = self.next();
+                match self.condition().eval_const() {
+                    Ok(v) => value = v,
+                    Err(e) => self.report(token, format!("enumerator value for {:?}: {}", name.get_token_value(), e)),
+                }
+            }
+            self.declare_enum_const(name, value);
+            value += 1;
+
+            if self.next().get_token_type() != Token::Comma {
+                break;
             }
-            _ => panic!("{} {}: failed in factor {:?}", file!(), line!(), token),
+            self.consume();
+        }
+        self.must_next(Token::RightBrace);
+        (Type::Int, Structure::Identifier)
+    }
+
+    // 列挙定数の登録
+    //
+    // 先読みで同じ宣言を再度解析した場合は登録済みとする
+    fn declare_enum_const(&mut self, token: &TokenInfo, value: i64) {
+        let (name, decl) = (token.get_token_value(), token.pos.to_string());
+        let pre = self.enum_consts.iter().find(|(sc, n, _, _)| *sc == self.cur_scope && *n == name).cloned();
+        match pre {
+            None => self.enum_consts.push((self.cur_scope.clone(), name, value, decl)),
+            Some((_, _, _, ref pre)) if *pre == decl => {}
+            Some((_, _, _, ref pre)) => self.report(token, format!("redeclaration of enumerator {:?} (previous declaration at {})", name, pre)),
         }
     }
 
-    // 構造体定義、宣言作成
-    fn struct_def_or_var(&mut self) -> AstType {
+    // 列挙定数の検索（ローカルで発見できない場合、グローバルで検索）
+    fn search_enum_const(&self, name: &str) -> Option<i64> {
+        let find = |scope: &Scope| self.enum_consts.iter().find(|(sc, n, _, _)| sc == scope && n == name).map(|c| c.2);
+        find(&self.cur_scope).or_else(|| find(&Scope::Global))
+    }
+
+    // 構造体（共用体）定義、宣言作成
+    fn struct_def_or_var(&mut self, keyword: &TokenInfo) -> AstType {
         let def_name = self.next_consume();
+        let t = Self::tag_type(keyword, def_name.get_token_value());
         let token = self.next_consume();
         match token.get_token_type() {
-            Token::LeftBrace => self.struct_def(def_name),
-            Token::Variable => self.struct_variable(def_name, token),
-            _ => panic!("{} {}: failed in struct_def_or_var {:?} {:?}", file!(), line!(), def_name, token),
+            Token::LeftBrace => self.struct_def(t, def_name),
+            Token::Variable => self.struct_variable(t, token),
+            Token::Multi => self.factor_pointer(t),
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected '{{' or declarator but found {}", token.get_token_type()))
+            }
+        }
+    }
+
+    // 構造体、共用体の型作成
+    fn tag_type(keyword: &TokenInfo, tag: String) -> Type {
+        match keyword.get_token_type() {
+            Token::Union => Type::Union(tag),
+            _ => Type::Struct(tag),
         }
     }
 
     /// 構造体定義作成
     ///
-    /// 構造体定義でシンボル登録し、ASTを返却
-    fn struct_def(&mut self, def_name: &TokenInfo) -> AstType {
+    /// 構造体（共用体）定義でシンボル登録し、ASTを返却
+    fn struct_def(&mut self, t: Type, def_name: &TokenInfo) -> AstType {
         // 右波括弧が出てくるまで、メンバー定義
         let mut right_brace = self.next();
         let mut members = vec![];
@@ -856,75 +2133,219 @@ impl<'a> AstGen<'a> {
             match right_brace.get_token_type() {
                 Token::RightBrace => {
                     self.consume();
-                    self.must_next(
-                        Token::SemiColon, "ast.rs(struct_def_or_var): Not exists SemiColon"
-                    );
+                    self.must_next(Token::SemiColon);
                     break;
                 }
+                Token::End => {
+                    self.syntax_error(right_brace, format!("expected {} but found {}", Token::RightBrace, Token::End));
+                    break;
+                }
+                // 静的アサーションはメンバーではないので、シンボル登録しない
+                Token::StaticAssert => {
+                    self.consume();
+                    members.push(self.static_assert(right_brace));
+                }
                 _ => {
                     // 構造体に所属しているメンバーをシンボルに登録
-                    let member = self.assign();
+                    // （不正なメンバーは登録しない）
+                    let (member, qual) = self.struct_member();
                     let mem_sym = match member {
-                        AstType::Variable(ref t, ref st, ref mem_name) => {
-                            Symbol::new(self.cur_scope.clone(), mem_name.clone(), t.clone(), st.clone())
+                        AstType::Variable(ref t, ref st, ref mem_name, _) => {
+                            let mut m = Symbol::new(self.cur_scope.clone(), mem_name.clone(), t.clone(), st.clone());
+                            (m.qual, m.ptr_qual) = qual;
+
+                            // 構造体型（配列を含む）のメンバーは、定義からメンバーを引き継ぐ
+                            if let (Type::Struct(ref n) | Type::Union(ref n), false) = (t, *st == Structure::Pointer) {
+                                if let Some(def) = self.search_symbol(&self.cur_scope, n) {
+                                    m.regist_mem(def.members);
+                                }
+                            }
+                            Some(m)
                         }
-                        _ => panic!("not find variable")
+                        _ => None,
                     };
-                    members.push(member);
-                    syms.push(mem_sym);
+                    if let Some(m) = mem_sym {
+                        members.push(member);
+                        syms.push(m);
+                    }
 
-                    self.must_next(
-                        Token::SemiColon, "ast.rs(struct_def_or_var): Not exists SemiColon"
-                    );
+                    self.must_next(Token::SemiColon);
                 }
             };
+
+            // 構文エラーがあれば、次のメンバーまで読み飛ばす
+            if self.panic_mode {
+                self.synchronize();
+            }
             right_brace = self.next();
         }
 
-        // シンボルテーブルへ構造体定義を保存（未登録の場合）.
-        if self.search_symbol(&self.cur_scope, &def_name.get_token_value()).is_none() {
-            let mut sym = Symbol::new(
-                self.cur_scope.clone(),
-                def_name.get_token_value(), // 構造体定義名で作成
-                Type::Struct(def_name.get_token_value()),
-                Structure::Struct,
-            );
-            // 構造体メンバーを登録し、シンボル保存
-            sym.regist_mem(syms);
-            self.sym_table.register_sym(sym);
+        // シンボルテーブルへ構造体定義を保存（同じスコープで定義済みの場合はエラー）.
+        let tag = def_name.get_token_value();
+        match self.sym_table.search(&self.cur_scope, &tag) {
+            Some(ref pre) if pre.is_tag() => {
+                let msg = format!(
+                    "redefinition of '{}' (previous definition at {})",
+                    type_name(&t, &Structure::Identifier),
+                    pre.decl
+                );
+                self.report(def_name, msg);
+            }
+            _ => {
+                let mut sym = Symbol::new(
+                    self.cur_scope.clone(),
+                    tag.clone(), // 構造体定義名で作成
+                    t.clone(),
+                    Structure::Struct,
+                );
+                sym.decl = def_name.pos.to_string();
+                // 構造体メンバーを登録し、シンボル保存
+                sym.regist_mem(syms);
+                self.sym_table.register_sym(sym);
+            }
         }
 
         AstType::Struct(
             Box::new( AstType::Variable(
-                    Type::Struct(def_name.get_token_value()),
+                    t,
                     Structure::Struct,
-                    def_name.get_token_value()
+                    def_name.get_token_value(),
+                    def_name.pos.to_string()
             )),
             members
         )
     }
 
+    /// 構造体メンバー宣言
+    ///
+    /// メンバーは構造体定義に属するので、変数としてはシンボル登録しない
+    /// (メンバー, (メンバー自身の修飾子, 参照先の修飾子))を返す
+    fn struct_member(&mut self) -> (AstType, (Qualifier, Qualifier)) {
+        let pre = self.qualifiers();
+        let signed = self.sign_specifier();
+        let type_token = self.next();
+        let (t, s) = match type_token.get_token_type() {
+            Token::Int | Token::IntPointer | Token::Char | Token::CharPointer => self.generate_type(),
+            // signed、unsignedのみの場合はint
+            _ if signed.is_some() => (Type::Int, Structure::Identifier),
+            _ => self.generate_type(),
+        };
+        let post = self.qualifiers();
+        let qual = Self::split_qualifier(&s, &pre, &post);
+        if let Type::Unknown(_) = t {
+            let e = match type_token.get_token_type() {
+                Token::Variable => format!("unknown type name {:?}", type_token.get_token_value()),
+                other => format!("expected type name but found {}", other),
+            };
+            return (self.syntax_error(type_token, e), qual);
+        }
+        if self.is_func_pointer() {
+            let token = self.next_n(2);
+            let (name, strt, sig) = self.func_pointer_declarator(t, s);
+            return (AstType::Variable(Type::Func(Box::new(sig)), strt, name, token.pos.to_string()), qual);
+        }
+
+        // 無名ビットフィールド
+        if self.next().get_token_type() == Token::Colon {
+            return (self.bit_field(t, s, signed, None), qual);
+        }
+        let name = self.next();
+        if name.get_token_type() != Token::Variable {
+            return (self.syntax_error(name, format!("expected member name but found {}", name.get_token_type())), qual);
+        }
+        self.consume();
+        if self.next().get_token_type() == Token::Colon {
+            return (self.bit_field(t, s, signed, Some(name)), qual);
+        }
+        if signed == Some(false) {
+            self.report(name, format!("unsigned is only supported for bit-field {:?}", name.get_token_value()));
+        }
+        let dims = self.array_size(vec![]);
+        let (t, s) = Self::array_type(t, s, dims);
+        (AstType::Variable(t, s, name.get_token_value(), name.pos.to_string()), qual)
+    }
+
+    /// 符号指定子
+    ///
+    /// signedであればSome(true)、unsignedであればSome(false)を返す
+    fn sign_specifier(&mut self) -> Option<bool> {
+        match self.next().get_token_type() {
+            Token::Signed => {
+                self.consume();
+                Some(true)
+            }
+            Token::Unsigned => {
+                self.consume();
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+
+    /// ビットフィールド宣言
+    ///
+    /// 幅は定数式で、型のビット数を超えてはならない（幅0は無名の場合のみ）
+    /// 符号指定子のないint、charは符号付きとする
+    fn bit_field(&mut self, t: Type, s: Structure, signed: Option<bool>, name: Option<&TokenInfo>) -> AstType {
+        let token = self.next();
+        let (name, pos) = match name {
+            Some(n) => (n.get_token_value(), n.pos.to_string()),
+            None => (String::new(), token.pos.to_string()),
+        };
+        self.must_next(Token::Colon);
+        let bits = match (&t, &s) {
+            (Type::Int, Structure::Identifier) => 32,
+            (Type::Char, Structure::Identifier) => 8,
+            _ => 0,
+        };
+        let width = self.condition().eval_const();
+        let err = match width {
+            _ if bits == 0 => Some(format!("bit-field {:?} has non-integral type", name)),
+            Err(ref e) => Some(format!("bit-field width: {}", e)),
+            Ok(w) if w < 0 => Some(format!("negative width in bit-field {:?}", name)),
+            Ok(w) if w > bits => Some(format!("width of bit-field {:?} ({} bits) exceeds its type ({} bits)", name, w, bits)),
+            Ok(0) if !name.is_empty() => Some(format!("named bit-field {:?} has zero width", name)),
+            Ok(_) => None,
+        };
+        if let Some(e) = err {
+            self.report(token, e);
+            return AstType::Error();
+        }
+        let width = width.unwrap_or(0);
+        let b = BitField { width: width as usize, pos: 0, signed: signed.unwrap_or(true) };
+        AstType::Variable(t, Structure::BitField(b), name, pos)
+    }
+
     /// 構造体変数作成
     ///
     /// 構造体変数名でシンボルに登録し、ASTを返却
-    fn struct_variable(&mut self, def_name: &TokenInfo, name: &TokenInfo) -> AstType {
+    fn struct_variable(&mut self, t: Type, name: &TokenInfo) -> AstType {
+        // 構造体の配列
+        let dims = self.array_size(vec![]);
+        let strt = if dims.is_empty() { Structure::Struct } else { Structure::Array(dims) };
+
         // 定義がシンボルテーブルに保存されているので、それを元にシンボル保存
-        if let Some(s) = self.search_symbol(&self.cur_scope, &def_name.get_token_value()) {
+        let tag = match t {
+            Type::Struct(ref n) | Type::Union(ref n) => n.clone(),
+            _ => String::new(),
+        };
+        if let Some(s) = self.search_symbol(&self.cur_scope, &tag) {
             let mut sym = Symbol::new(
                 self.cur_scope.clone(),
                 name.get_token_value(), // 構造体変数名で作成
-                Type::Struct(def_name.get_token_value()),
-                Structure::Struct,
+                t.clone(),
+                strt.clone(),
             );
 
             // 構造体定義よりメンバーを設定し、シンボル登録
             sym.regist_mem(s.members);
-            self.sym_table.register_sym(sym);
+            sym.storage = self.cur_storage.clone();
+            sym.qual = self.cur_qual.clone();
+            let var = self.declare(name, sym);
+            return AstType::Variable(t, strt, var, name.pos.to_string());
         }
 
-        AstType::Variable(
-            Type::Struct(def_name.get_token_value()), Structure::Struct, name.get_token_value()
-        )
+        AstType::Variable(t, strt, name.get_token_value(), name.pos.to_string())
     }
 
     // 文字列作成
@@ -936,114 +2357,315 @@ impl<'a> AstGen<'a> {
 
     // variable型の作成
     fn factor_variable(&mut self, token: &TokenInfo) -> AstType {
+        // 列挙定数は値に置き換える
+        if let Some(v) = self.search_enum_const(&token.get_token_value()) {
+            self.consume();
+            return AstType::Factor(v);
+        }
+
         // 変数シンボルサーチ
-        match self.search_symbol(&self.cur_scope, &token.get_token_value()) {
+        match self.search_var(&token.get_token_value()) {
             Some(ref sym) => {
+                self.used.push((sym.scope.clone(), sym.var.clone()));
+
                 // 後置演算子判定
-                let var = self.variable(sym.t.clone(), sym.strt.clone());
+                let var = self.variable_ref(sym.t.clone(), sym.strt.clone(), sym.var.clone());
                 match self.next().get_token_type() {
+                    // 関数ポインタ経由の呼び出し
+                    Token::LeftParen if matches!(sym.t, Type::Func(_)) => self.call_func(var),
                     Token::Inc => {
-                        self.consume();
-                        AstType::PostInc(Box::new(var))
+                        let ope = self.next_consume();
+                        self.check_void_pointer(ope, &var);
+                        AstType::PostInc(Box::new(var), ope.pos.to_string())
                     }
                     Token::Dec => {
-                        self.consume();
-                        AstType::PostDec(Box::new(var))
+                        let ope = self.next_consume();
+                        self.check_void_pointer(ope, &var);
+                        AstType::PostDec(Box::new(var), ope.pos.to_string())
                     }
+                    Token::Dot | Token::Arrow => self.postfix_member(var),
                     _ => var,
                 }
             }
             None => {
                 // 関数シンボルサーチ
-                match self.search_symbol(&Scope::Func, &token.get_token_value()) {
-                    Some(s) => {
+                let func = self.search_symbol(&Scope::Func, &token.get_token_value());
+                if let Some(ref f) = func {
+                    self.used.push((Scope::Func, f.var.clone()));
+                }
+                match func {
+                    Some(s) if self.next_n(1).get_token_type() == Token::LeftParen => {
                         let f_sym = self.variable_func(s.t.clone(), s.strt);
-                        self.call_func(f_sym)
+                        let call = self.call_func(f_sym);
+                        self.postfix_member(call)
+                    }
+                    // 呼び出しでなければ、関数指定子
+                    Some(s) => {
+                        self.consume();
+                        AstType::Variable(
+                            Type::Func(Box::new(s.signature())),
+                            Structure::Identifier,
+                            token.get_token_value(),
+                            token.pos.to_string(),
+                        )
+                    }
+                    _ => {
+                        self.consume();
+                        let name = token.get_token_value();
+                        let msg = match self.sym_table.similar_name(&self.cur_scope, &name, &self.visible_symbols()) {
+                            Some(s) => format!("use of undeclared identifier {:?}; did you mean {:?}?", name, s),
+                            None => format!("use of undeclared identifier {:?}", name),
+                        };
+                        self.report(token, msg);
+
+                        // 未宣言の関数呼び出しも、引数までは読み進める
+                        if self.next().get_token_type() == Token::LeftParen {
+                            self.call_func(AstType::Error());
+                        }
+                        self.postfix_member(AstType::Error())
                     }
-                    _ => panic!("{} {}: cannot define {:?}", file!(), line!(), token),
                 }
             }
         }
     }
 
+    // メンバー参照.
+    //
+    // a->bは(*a).bとして扱う
+    fn postfix_member(&mut self, acc: AstType) -> AstType {
+        let base = match self.next().get_token_type() {
+            Token::Dot => acc,
+            Token::Arrow => AstType::Indirect(Box::new(acc)),
+            Token::LeftBracket => {
+                let tree = self.postfix_index(acc);
+                return self.postfix_member(tree);
+            }
+            // 関数ポインタのメンバー経由の呼び出し
+            Token::LeftParen if acc.func_signature().is_some() => {
+                let tree = self.call_func(acc);
+                return self.postfix_member(tree);
+            }
+            // メンバー、配列要素への後置演算子
+            Token::Inc | Token::Dec if matches!(acc, AstType::Member(_, _, _, _) | AstType::Indirect(_)) => {
+                let ope = self.next_consume();
+                return match ope.get_token_type() {
+                    Token::Inc => AstType::PostInc(Box::new(acc), ope.pos.to_string()),
+                    _ => AstType::PostDec(Box::new(acc), ope.pos.to_string()),
+                };
+            }
+            _ => return acc,
+        };
+        self.consume();
+        let name = self.next();
+        if name.get_token_type() != Token::Variable {
+            return self.syntax_error(name, format!("expected member name but found {}", name.get_token_type()));
+        }
+        self.consume();
+
+        // 構造体定義からメンバーの型を取得
+        let (t, tag) = match base.value_type() {
+            Some((ref t @ (Type::Struct(ref n) | Type::Union(ref n)), Structure::Struct)) => (t.clone(), n.clone()),
+            _ if base == AstType::Error() => return base,
+            _ => {
+                self.report(name, format!("request for member {:?} in non-struct", name.get_token_value()));
+                return AstType::Error();
+            }
+        };
+        let member = self.search_symbol(&self.cur_scope, &tag)
+            .and_then(|def| def.members.into_iter().find(|m| m.var == name.get_token_value()));
+        let member = match member {
+            Some(m) => m,
+            None => {
+                let msg = format!("no member named {:?} in {}", name.get_token_value(), type_name(&t, &Structure::Identifier));
+                self.report(name, msg);
+                return AstType::Error();
+            }
+        };
+        let tree = AstType::Member(Box::new(base), member.t, member.strt, name.get_token_value());
+        self.postfix_member(tree)
+    }
+
     // int型要素の作成
     fn factor_int(&mut self) -> AstType {
+        // 型指定子の後の型修飾子も考慮
+        let outer = self.cur_qual.clone();
+        let post = self.qualifiers();
+        self.cur_qual = outer.merge(&post);
+
         // 配列かどうか決定する為に、一文字読み飛ばして、後で戻る
         let _ = self.next_consume();
         let token = self.next();
         self.back(1);
-        match token.get_token_type() {
+        let var = match token.get_token_type() {
             Token::LeftBracket => self.variable_array(Type::Int),
             _ => self.variable(Type::Int, Structure::Identifier),
-        }
+        };
+        self.cur_qual = outer;
+        var
     }
 
     // char型要素の作成
     fn factor_char(&mut self) -> AstType {
+        // 型指定子の後の型修飾子も考慮
+        let outer = self.cur_qual.clone();
+        let post = self.qualifiers();
+        self.cur_qual = outer.merge(&post);
+
         // 配列かどうか決定する為に、一文字読み飛ばして、後で戻る
         let _ = self.next_consume();
         let token = self.next();
         self.back(1);
-        match token.get_token_type() {
+        let var = match token.get_token_type() {
             Token::LeftBracket => self.variable_array(Type::Char),
             _ => self.variable(Type::Char, Structure::Identifier),
+        };
+        self.cur_qual = outer;
+        var
+    }
+
+    // void型要素の作成
+    //
+    // 不完全型なので、変数として宣言できない
+    fn factor_void(&mut self, token: &TokenInfo) -> AstType {
+        let var = self.variable(Type::Void, Structure::Identifier);
+        if let AstType::Variable(_, _, ref n, _) = var {
+            self.report(token, format!("variable {:?} declared void", n));
         }
+        var
     }
 
-    // array index
-    fn array_index(&mut self, s: &Structure) -> AstType {
-        self.consume();
-        let index = self.expression();
-        self.must_next(
-            Token::RightBracket,
-            "ast.rs(variable): Not exists RightBracket",
-        );
-        // 多次元配列か？
-        match self.next().get_token_type() {
-            // 最初のインデックス分のオフセットを算出
-            Token::LeftBracket => {
-                let (count, tails) = match s {
-                    Structure::Array(v) => (v[1] as i64, v.split_first().unwrap().1.to_vec()),
-                    _ => panic!("ast.rs(array_index): cannot support structure {:?}", s),
-                };
-                let offset = AstType::Multiple(
-                    Box::new(index), Box::new(AstType::Factor(count))
-                );
-                AstType::Plus(
-                    Box::new(offset),
-                    Box::new(self.array_index(&Structure::Array(tails))),
-                )
+    // void*の参照、演算チェック
+    //
+    // voidは不完全型なので、void*は参照できず、要素サイズを必要とする演算もできない
+    fn check_void_pointer(&mut self, token: &TokenInfo, operand: &AstType) {
+        if operand.value_type() != Some((Type::Void, Structure::Pointer)) {
+            return;
+        }
+        let msg = match token.get_token_type() {
+            Token::Multi => "dereferencing 'void *' pointer",
+            Token::LeftBracket => "subscript of 'void *' pointer",
+            _ => "arithmetic on 'void *' pointer",
+        };
+        self.report(token, msg.to_string());
+    }
+
+    // 添字演算子.
+    //
+    // a[i]は*(a + i)として扱い、多次元配列は添字毎に一次元ずつ参照する
+    fn postfix_index(&mut self, acc: AstType) -> AstType {
+        match self.next().get_token_type() {
+            Token::LeftBracket => {
+                let bracket = self.next_consume();
+                let address_of = std::mem::replace(&mut self.address_of, false);
+                let index = self.comma();
+                self.address_of = address_of;
+                self.must_next(Token::RightBracket);
+                self.check_index(bracket, &acc, &index);
+                self.check_void_pointer(bracket, &acc);
+                let tree = AstType::Indirect(Box::new(AstType::Plus(Box::new(acc), Box::new(index))));
+                self.postfix_index(tree)
             }
-            _ => index,
+            _ => acc,
+        }
+    }
+
+    // 配列添字の範囲チェック
+    //
+    // 要素数が既知の配列の定数添字は、要素数未満でなければならない
+    // アドレス演算子の被演算子（&a[N]）に限り、末尾の次の要素を許容する
+    fn check_index(&mut self, bracket: &TokenInfo, array: &AstType, index: &AstType) {
+        let (len, index) = match (array.value_type(), index.const_value()) {
+            (Some((_, Structure::Array(dims))), Some(i)) => (dims[0] as i64, i),
+            _ => return,
+        };
+        let one_past = self.address_of
+            && !matches!(self.next().get_token_type(), Token::LeftBracket | Token::Dot | Token::Arrow);
+        let msg = if index < 0 {
+            format!("array index {} is before the beginning of the array", index)
+        } else if index > len || (index == len && !one_past) {
+            format!("array index {} is past the end of the array (which contains {} elements)", index, len)
+        } else {
+            return;
+        };
+        self.warn(bracket, WarningKind::ArrayBounds, msg);
+    }
+
+    // アドレス演算子
+    fn factor_address(&mut self, token: &TokenInfo) -> AstType {
+        let address_of = std::mem::replace(&mut self.address_of, true);
+        let operand = self.factor();
+        self.address_of = address_of;
+        AstType::Address(Box::new(operand), token.pos.to_string())
+    }
+
+    // ポインタ型要素の作成
+    fn factor_pointer(&mut self, t: Type) -> AstType {
+        let (t, s) = self.pointer_declarator(t, Structure::Pointer);
+        match self.next_n(1).get_token_type() {
+            // ポインタの配列
+            Token::LeftBracket => self.variable_array(Type::Pointer(Box::new(t))),
+            _ => self.variable(t, s),
+        }
+    }
+
+    // 多重ポインタ宣言子
+    //
+    // 型指定子に続く*毎に、ポインタへのポインタとする
+    fn pointer_declarator(&mut self, t: Type, s: Structure) -> (Type, Structure) {
+        let mut ts = (t, s);
+        while self.next().get_token_type() == Token::Multi {
+            self.consume();
+            self.qualifiers();
+            ts = pointer_to(ts.0, ts.1);
+        }
+        ts
+    }
+
+    // 配列型の作成
+    //
+    // ポインタの配列は、要素の型をポインタとする
+    fn array_type(t: Type, s: Structure, dims: Vec<usize>) -> (Type, Structure) {
+        match s {
+            _ if dims.is_empty() => (t, s),
+            Structure::Pointer => (Type::Pointer(Box::new(t)), Structure::Array(dims)),
+            _ => (t, Structure::Array(dims)),
         }
     }
 
     // variable.
     fn variable(&mut self, t: Type, s: Structure) -> AstType {
+        let post = self.qualifiers();
+        if self.is_func_pointer() {
+            return self.variable_func_pointer(t, s);
+        }
         let token = self.next_consume();
         let next = self.next();
         match token.get_token_type() {
             Token::Variable if Token::LeftBracket == next.get_token_type() => {
-                // ポインタと同じようにアクセスするため、Indirectでくるむ
-                let index = self.array_index(&s);
-                AstType::Indirect(Box::new(AstType::Plus(
-                    Box::new(AstType::Variable(t, s, token.get_token_value())),
-                    Box::new(index),
-                )))
+                self.postfix_index(AstType::Variable(t, s, token.get_token_value(), token.pos.to_string()))
             }
             Token::Variable => {
-                // シンボルテーブルへ保存（未登録の場合）.
-                if self.search_symbol(&self.cur_scope, &token.get_token_value()).is_none() {
-                    self.sym_table.register_sym(Symbol::new(
-                            self.cur_scope.clone(),
-                            token.get_token_value(),
-                            t.clone(),
-                            s.clone(),
-                    ));
-                }
-                AstType::Variable(t, s, token.get_token_value())
+                // シンボルテーブルへ保存.
+                let mut sym = Symbol::new(self.cur_scope.clone(), token.get_token_value(), t.clone(), s.clone());
+                sym.storage = self.cur_storage.clone();
+                (sym.qual, sym.ptr_qual) = self.decl_qualifier(&s, &post);
+                let name = self.declare(token, sym);
+                AstType::Variable(t, s, name, token.pos.to_string())
+            }
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected identifier but found {}", token.get_token_type()))
             }
-            _ => panic!("{} {}: not support token {:?}", file!(), line!(), token),
+        }
+    }
+
+    // 宣言済み変数の参照.
+    fn variable_ref(&mut self, t: Type, s: Structure, name: String) -> AstType {
+        let token = self.next_consume();
+        let var = AstType::Variable(t, s, name, token.pos.to_string());
+        match self.next().get_token_type() {
+            Token::LeftBracket => self.postfix_index(var),
+            _ => var,
         }
     }
 
@@ -1052,8 +2674,11 @@ impl<'a> AstGen<'a> {
         // 関数名は定義時に登録されている為、シンボルテーブルには追加しない
         let token = self.next_consume();
         match token.get_token_type() {
-            Token::Variable => AstType::Variable(t, s, token.get_token_value()),
-            _ => panic!("{} {}: not support token {:?}", file!(), line!(), token),
+            Token::Variable => AstType::Variable(t, s, token.get_token_value(), token.pos.to_string()),
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected identifier but found {}", token.get_token_type()))
+            }
         }
     }
 
@@ -1063,16 +2688,27 @@ impl<'a> AstGen<'a> {
             Token::LeftBracket => {
                 let mut sizes = size;
                 self.consume();
-                let s = self
-                    .next()
-                    .get_token_value()
-                    .parse::<usize>()
-                    .expect("failed parse");
-                self.must_next(Token::Number, "ast.rs(arra_size): Not exists Number");
-                self.must_next(
-                    Token::RightBracket,
-                    "ast.rs(arra_size): Not exists RightBracket",
-                );
+
+                // 要素数の省略（仮引数）は0とする
+                let s = match self.next().get_token_type() {
+                    Token::RightBracket => 0,
+                    _ => {
+                        // 要素数は定数式（sizeof等を含む）
+                        let token = self.next();
+                        match self.condition().eval_const() {
+                            Ok(n) if n >= 0 => n as usize,
+                            Ok(n) => {
+                                self.report(token, format!("size of array is negative: {}", n));
+                                0
+                            }
+                            Err(e) => {
+                                self.report(token, format!("array size: {}", e));
+                                0
+                            }
+                        }
+                    }
+                };
+                self.must_next(Token::RightBracket);
                 sizes.push(s);
                 self.array_size(sizes)
             }
@@ -1085,72 +2721,126 @@ impl<'a> AstGen<'a> {
         let token = self.next_consume();
         match token.get_token_type() {
             Token::Variable => {
-                // シンボルテーブルへ保存（未登録の場合）.
+                // シンボルテーブルへ保存.
                 let s = Structure::Array(self.array_size(vec![]));
-                if self.search_symbol(&self.cur_scope, &token.get_token_value()).is_none() {
-                    self.sym_table.register_sym(Symbol::new(
-                            self.cur_scope.clone(),
-                            token.get_token_value(),
-                            t.clone(),
-                            s.clone(),
-                    ));
-                }
-                AstType::Variable(t, s, token.get_token_value())
+                let mut sym = Symbol::new(self.cur_scope.clone(), token.get_token_value(), t.clone(), s.clone());
+                sym.storage = self.cur_storage.clone();
+                (sym.qual, sym.ptr_qual) = self.decl_qualifier(&s, &Qualifier::default());
+                let name = self.declare(token, sym);
+                AstType::Variable(t, s, name, token.pos.to_string())
+            }
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected identifier but found {}", token.get_token_type()))
             }
-            _ => panic!(
-                "ast.rs(variable_array): Not Support Token {:?}",
-                self.next()
-            ),
         }
     }
 
     // sizeof演算子
+    //
+    // 被演算子は評価せず、型からサイズを算出する
     fn factor_sizeof(&mut self) -> AstType {
-        self.must_next(Token::LeftParen, "ast.rs(factor_sizeof): Not exists LeftParen");
+        let (t, s) = self.operand_type();
+        let size = match (&t, &s) {
+            // 関数はGNU拡張と同様に1とする
+            (Type::Func(_), Structure::Identifier) => 1,
+            _ => self.type_symbol(t, s).storage_size(),
+        };
+        AstType::SizeOf(size)
+    }
 
-        // 次のトークンが型であるか判定
-        let token = self.next();
-        let ast = match token.get_token_type() {
-            Token::Int => {
-                self.consume();
-                AstType::SizeOf(4)
-            }
-            Token::Char  => {
-                self.consume();
-                AstType::SizeOf(1)
+    // _Alignof演算子
+    //
+    // sizeofと同様、size_t型の定数とする
+    fn factor_alignof(&mut self) -> AstType {
+        let (t, s) = self.operand_type();
+        AstType::SizeOf(self.type_symbol(t, s).align())
+    }
+
+    // sizeof、_Alignofの被演算子の型取得.
+    //
+    // 括弧で囲まれた型名、もしくは単項式
+    fn operand_type(&mut self) -> (Type, Structure) {
+        if Token::LeftParen == self.next().get_token_type() && self.is_type_name(1) {
+            self.consume();
+            let ts = self.type_name();
+            self.must_next(Token::RightParen);
+            return ts;
+        }
+        match self.factor() {
+            // 文字列リテラルは終端文字を含む配列
+            AstType::StringLiteral(ref v, _) => (Type::Char, Structure::Array(vec![v.len() + 1])),
+            // 型が決まらない場合（エラー報告済み）はintとみなす
+            e => e.value_type().unwrap_or((Type::Int, Structure::Identifier)),
+        }
+    }
+
+    // 型名判定.
+    fn is_type_name(&mut self, n: usize) -> bool {
+        matches!(
+            self.next_n(n).get_token_type(),
+            Token::Int
+                | Token::IntPointer
+                | Token::Char
+                | Token::CharPointer
+                | Token::Void
+                | Token::VoidPointer
+                | Token::Struct
+                | Token::Union
+                | Token::Enum
+                | Token::Const
+                | Token::Volatile
+        )
+    }
+
+    // 型名.
+    //
+    // int*[4]のような抽象宣言子を含む
+    fn type_name(&mut self) -> (Type, Structure) {
+        let token = self.next_consume();
+        let (t, s) = match token.get_token_type() {
+            Token::Int => (Type::Int, Structure::Identifier),
+            Token::Char => (Type::Char, Structure::Identifier),
+            Token::IntPointer => (Type::Int, Structure::Pointer),
+            Token::CharPointer => (Type::Char, Structure::Pointer),
+            Token::Void => (Type::Void, Structure::Identifier),
+            Token::VoidPointer => (Type::Void, Structure::Pointer),
+            Token::Struct | Token::Union => (Self::tag_type(token, self.next_consume().get_token_value()), Structure::Struct),
+            Token::Enum => {
+                self.back(1);
+                self.enum_specifier()
             }
-            Token::IntPointer | Token::CharPointer => {
-                self.consume();
-                AstType::SizeOf(8)
+            // 型修飾子はサイズに影響しない
+            Token::Const | Token::Volatile => return self.type_name(),
+            _ => {
+                self.back(1);
+                self.syntax_error(token, format!("expected type name but found {}", token.get_token_type()));
+                (Type::Int, Structure::Identifier)
             }
-            Token::Struct => {
-                // シンボルテーブルより、構造体定義を取得し、サイズ算出
-                self.consume();
-                let name = self.next_consume();
-                let sym = self.search_symbol(&self.cur_scope, &name.get_token_value())
-                              .expect("cannot search token");
-                AstType::SizeOf(sym.size)
+        };
+        let mut ts = (t, s);
+        while let Token::Multi | Token::Const | Token::Volatile = self.next().get_token_type() {
+            if Token::Multi == self.next_consume().get_token_type() {
+                ts = pointer_to(ts.0, ts.1);
             }
-            _ => {
-                // 型でない場合は、変数や数値リテラル
-                let factor = self.factor();
-
-                // サイズを算出し、AST作成
-                match factor {
-                    AstType::Variable(_, _, _) => {
-                        // シンボルテーブルから変数をサーチし、サイズ算出
-                        let sym = self.search_symbol(&self.cur_scope, &token.get_token_value()).expect("cannot search token");
-                        AstType::SizeOf(sym.size)
+        }
+        let dims = self.array_size(vec![]);
+        Self::array_type(ts.0, ts.1, dims)
+    }
 
-                    }
-                    AstType::Factor(_) => AstType::SizeOf(8),
-                    _ => panic!("{} {}: not supprt ast: {:?}", file!(), line!(), factor)
+    // 型のシンボル作成（サイズ、アライメント算出用）
+    fn type_symbol(&mut self, t: Type, s: Structure) -> Symbol {
+        let mut sym = Symbol::new(self.cur_scope.clone(), "".to_string(), t, s);
+        if let Type::Struct(ref n) | Type::Union(ref n) = sym.t.clone() {
+            match self.search_symbol(&self.cur_scope, n) {
+                Some(def) => sym.regist_mem(def.members),
+                None => {
+                    let token = self.next();
+                    self.report(token, format!("undefined {}", type_name(&sym.t, &Structure::Identifier)));
                 }
             }
-        };
-
-        self.must_next(Token::RightParen, "ast.rs(factor_sizeof): Not exists LeftParen");
-        ast
+        }
+        sym
     }
 
     // number
@@ -1160,16 +2850,23 @@ impl<'a> AstGen<'a> {
     }
 
     // トークン読み取り.
+    //
+    // 終端を超えた場合は、最後のトークン（End）を返す
     fn next(&mut self) -> &'a TokenInfo {
-        let n = self.tokens.get(self.current_pos);
-        n.expect("ast.rs(next): cannot read next value")
+        self.next_n(0)
+    }
+
+    // n個先のトークン読み取り.
+    fn next_n(&mut self, n: usize) -> &'a TokenInfo {
+        let t = self.tokens.get(self.current_pos + n).or_else(|| self.tokens.last());
+        t.expect("ast.rs(next_n): cannot read next value")
     }
 
     // 読み取り位置更新.
     fn next_consume(&mut self) -> &'a TokenInfo {
-        let token = self.tokens.get(self.current_pos);
+        let token = self.next();
         self.current_pos += 1;
-        token.expect("ast.rs(next_consume): cannot read next value")
+        token
     }
 
     // 読み取り位置更新.
@@ -1182,12 +2879,124 @@ impl<'a> AstGen<'a> {
         self.current_pos -= i;
     }
 
-    // 指定されたトークンでない場合、panicメッセージ表示.
-    fn must_next(&mut self, t: Token, m: &str) {
-        let token = self.next_consume();
+    // 指定されたトークンでない場合、構文エラーを記録.
+    //
+    // 不一致のトークンは読み進めない（同期処理に任せる）
+    fn must_next(&mut self, t: Token) {
+        let token = self.next();
         if token.get_token_type() != t {
-            panic!("{} {}: {} {:?}", file!(), line!(), m, token)
+            self.syntax_error(token, format!("expected {} but found {}", t, token.get_token_type()));
+            return;
+        }
+        self.consume();
+    }
+
+    // 構文エラー記録.
+    //
+    // 回復中は後続のエラーを抑止し、エラーノードを返す
+    fn syntax_error(&mut self, token: &TokenInfo, m: String) -> AstType {
+        if !self.panic_mode {
+            self.errors.push(format!("{}: {}", token.pos, m));
+            self.panic_mode = true;
+        }
+        AstType::Error()
+    }
+
+    // エラー回復が必要か判定.
+    //
+    // 終端に達した場合は読み飛ばす対象がないので、解析済みの部分を残す
+    fn is_recovering(&mut self) -> bool {
+        self.panic_mode && self.next().get_token_type() != Token::End
+    }
+
+    // 構文は正しいが、不正な宣言などのエラー記録（回復は不要）.
+    fn report(&mut self, token: &TokenInfo, m: String) {
+        if !self.panic_mode {
+            self.errors.push(format!("{}: {}", token.pos, m));
+        }
+    }
+
+    // 同期トークンまで読み飛ばし.
+    //
+    // 文の区切り(;)は読み進め、閉じ波括弧と文・宣言の開始は残す
+    fn synchronize(&mut self) {
+        let start = self.current_pos;
+        loop {
+            match self.next().get_token_type() {
+                Token::SemiColon => {
+                    self.consume();
+                    break;
+                }
+                Token::RightBrace | Token::End => break,
+                t if self.current_pos > start && Self::is_sync_token(&t) => break,
+                _ => self.consume(),
+            }
+        }
+        self.panic_mode = false;
+    }
+
+    // 外部宣言の区切りまで読み飛ばし.
+    //
+    // 波括弧の対応を考慮し、最上位の;もしくは}（直後の;を含む）、宣言の開始まで読み進める
+    // （エラーとなった外部宣言は、型指定子等を読み進めているので必ず前進する）
+    fn synchronize_global(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.next().get_token_type() {
+                Token::End => break,
+                t if depth == 0 && Self::is_decl_token(&t) => break,
+                Token::LeftBrace => depth += 1,
+                Token::RightBrace if depth <= 1 => {
+                    self.consume();
+                    if self.next().get_token_type() == Token::SemiColon {
+                        self.consume();
+                    }
+                    break;
+                }
+                Token::RightBrace => depth -= 1,
+                Token::SemiColon if depth == 0 => {
+                    self.consume();
+                    break;
+                }
+                _ => {}
+            }
+            self.consume();
         }
+        self.panic_mode = false;
+    }
+
+    // 同期対象となる文・宣言の開始トークン判定.
+    fn is_sync_token(t: &Token) -> bool {
+        Self::is_decl_token(t)
+            || matches!(
+                t,
+                Token::If
+                    | Token::While
+                    | Token::For
+                    | Token::Do
+                    | Token::Return
+                    | Token::Continue
+                    | Token::Break
+            )
+    }
+
+    // 宣言の開始トークン判定.
+    fn is_decl_token(t: &Token) -> bool {
+        matches!(
+            t,
+            Token::Static
+                | Token::Extern
+                | Token::StaticAssert
+                | Token::Int
+                | Token::IntPointer
+                | Token::Char
+                | Token::CharPointer
+                | Token::Void
+                | Token::VoidPointer
+                | Token::Struct
+                | Token::Union
+                | Token::Enum
+        )
     }
 
     // シンボルサーチ
@@ -1215,6 +3024,11 @@ mod tests {
         TokenInfo::new(t, s, ("".to_string(), 0, 0))
     }
 
+    // テスト用トークンの位置表記
+    fn pos() -> String {
+        ":0:0".to_string()
+    }
+
     #[test]
     fn test_add_operator() {
         // 単純な加算テスト.
@@ -3308,13 +5122,15 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         )),
-                        Box::new(AstType::Factor(3))
+                        Box::new(AstType::Factor(3)),
+                        pos(),
                     ),])),
                 )
             )
@@ -3347,16 +5163,18 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         )),
                         Box::new(AstType::Plus(
                             Box::new(AstType::Factor(3)),
                             Box::new(AstType::Factor(1)),
-                        ))
+                        )),
+                        pos(),
                     ),])),
                 )
             )
@@ -3392,17 +5210,19 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::LogicalAnd(
                                 Box::new(AstType::Factor(3)),
                                 Box::new(AstType::Factor(1)),
-                            ))
+                            )),
+                            pos(),
                         ),
                     ])),
                 )
@@ -3436,16 +5256,18 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         )),
                         Box::new(AstType::Multiple(
                             Box::new(AstType::Factor(3)),
                             Box::new(AstType::Factor(1)),
-                        ))
+                        )),
+                        pos(),
                     ),])),
                 )
             )
@@ -3478,16 +5300,18 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         )),
                         Box::new(AstType::BitOr(
                             Box::new(AstType::Factor(3)),
                             Box::new(AstType::Factor(1)),
-                        ))
+                        )),
+                        pos(),
                     ),])),
                 )
             )
@@ -3541,9 +5365,11 @@ mod tests {
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         )),
-                        Box::new(AstType::Argment(vec![]))
+                        Box::new(AstType::Argment(vec![])),
+                        pos(),
                     ),])),
                 )
             );
@@ -3587,7 +5413,8 @@ mod tests {
                     Box::new(AstType::Argment(vec![AstType::Variable(
                         Type::Int,
                         Structure::Identifier,
-                        "x".to_string()
+                        "x".to_string(),
+                        pos(),
                     ),])),
                     Box::new(AstType::Statement(vec![]))
                 )
@@ -3600,18 +5427,21 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), pos()),
                         AstType::FuncCall(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Argment(vec![AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                'b'.to_string()
-                            )]),)
+                                'b'.to_string(),
+                                pos(),
+                            )]),),
+                            pos(),
                         ),
                     ])),
                 )
@@ -3662,8 +5492,8 @@ mod tests {
                     Structure::Identifier,
                     "test".to_string(),
                     Box::new(AstType::Argment(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "x".to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, "y".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "x".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "y".to_string(), pos()),
                     ])),
                     Box::new(AstType::Statement(vec![]))
                 )
@@ -3676,26 +5506,30 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, 'b'.to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, 'c'.to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, 'b'.to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, 'c'.to_string(), pos()),
                         AstType::FuncCall(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "test".to_string()
+                                "test".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Argment(vec![
                                 AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    'b'.to_string()
+                                    'b'.to_string(),
+                                    pos(),
                                 ),
                                 AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    'c'.to_string()
+                                    'c'.to_string(),
+                                    pos(),
                                 ),
-                            ]))
+                            ])),
+                            pos(),
                         ),
                     ])),
                 )
@@ -3741,7 +5575,8 @@ mod tests {
                     Box::new(AstType::Argment(vec![AstType::Variable(
                         Type::Int,
                         Structure::Pointer,
-                        "x".to_string()
+                        "x".to_string(),
+                        pos(),
                     ),])),
                     Box::new(AstType::Statement(vec![])),
                 )
@@ -3754,20 +5589,24 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "y".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "y".to_string(), pos()),
                         AstType::FuncCall(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Argment(vec![AstType::Address(Box::new(
                                 AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    'y'.to_string()
+                                    'y'.to_string(),
+                                    pos(),
                                 )
-                            ))]))
+                            ),
+                                pos(),)])),
+                            pos(),
                         ),
                     ])),
                 )
@@ -3810,28 +5649,33 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Assign(
+                        AstType::Init(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         ),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Plus(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(3)),
-                            ))
+                            )),
+                            pos(),
                         ),
                     ])),
                 )
@@ -3870,25 +5714,29 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Assign(
+                        AstType::Init(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         ),
                         AstType::Plus(
                             Box::new(AstType::Multiple(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                             )),
                             Box::new(AstType::Factor(1))
@@ -3938,13 +5786,15 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         )),
-                        Box::new(AstType::Factor(3))
+                        Box::new(AstType::Factor(3)),
+                        pos(),
                     ),])),
                 )
             );
@@ -3955,13 +5805,15 @@ mod tests {
                     Structure::Identifier,
                     "test".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "b".to_string()
+                            "b".to_string(),
+                            pos(),
                         )),
-                        Box::new(AstType::Factor(1))
+                        Box::new(AstType::Factor(1)),
+                        pos(),
                     ),])),
                 )
             );
@@ -4001,16 +5853,18 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), pos()),
                     ])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "c".to_string()
+                            "c".to_string(),
+                            pos(),
                         )),
-                        Box::new(AstType::Factor(3))
+                        Box::new(AstType::Factor(3)),
+                        pos(),
                     ),])),
                 )
             );
@@ -4046,16 +5900,18 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), pos()),
                     ])),
-                    Box::new(AstType::Statement(vec![AstType::Assign(
+                    Box::new(AstType::Statement(vec![AstType::Init(
                         Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "c".to_string()
+                            "c".to_string(),
+                            pos(),
                         )),
-                        Box::new(AstType::Factor(3))
+                        Box::new(AstType::Factor(3)),
+                        pos(),
                     ),])),
                 )
             );
@@ -4104,25 +5960,28 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::If(
                             Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(3))
                             )),
                             Box::new(AstType::Statement(vec![
                                 AstType::Factor(1),
-                                AstType::Assign(
+                                AstType::Init(
                                     Box::new(AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "b".to_string()
+                                        "b".to_string(),
+                                        pos(),
                                     )),
-                                    Box::new(AstType::Factor(10))
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
                                 )
                             ],)),
                             Box::new(None)
@@ -4187,15 +6046,16 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, "e".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "e".to_string(), pos()),
                         AstType::If(
                             Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(3))
                             )),
@@ -4205,18 +6065,22 @@ mod tests {
                                     Box::new(AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "b".to_string()
+                                        "b".to_string(),
+                                        pos(),
                                     )),
-                                    Box::new(AstType::Factor(10))
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
                                 )
                             ],)),
                             Box::new(Some(AstType::Statement(vec![AstType::Assign(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "e".to_string()
+                                    "e".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(9))
+                                Box::new(AstType::Factor(9)),
+                                pos(),
                             )],))),
                         ),
                     ]))
@@ -4262,24 +6126,27 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::If(
                             Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(3))
                             )),
                             Box::new(AstType::Statement(vec![AstType::Factor(1)])),
-                            Box::new(Some(AstType::Statement(vec![AstType::Assign(
+                            Box::new(Some(AstType::Statement(vec![AstType::Init(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "e".to_string()
+                                    "e".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(9))
+                                Box::new(AstType::Factor(9)),
+                                pos(),
                             )])))
                         ),
                     ]))
@@ -4330,25 +6197,28 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::While(
                             Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(3))
                             )),
                             Box::new(AstType::Statement(vec![
                                 AstType::Factor(1),
-                                AstType::Assign(
+                                AstType::Init(
                                     Box::new(AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "b".to_string()
+                                        "b".to_string(),
+                                        pos(),
                                     )),
-                                    Box::new(AstType::Factor(10))
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
                                 )
                             ]))
                         )
@@ -4381,7 +6251,7 @@ mod tests {
                 create_token(Token::Number, "10".to_string()),
                 create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::RightBrace, "}".to_string()),
-                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::Variable, "a".to_string()),
                 create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::RightBrace, "}".to_string()),
                 create_token(Token::End, "End".to_string()),
@@ -4398,29 +6268,32 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::While(
                             Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(3))
                             )),
                             Box::new(AstType::Statement(vec![
                                 AstType::Factor(1),
-                                AstType::Assign(
+                                AstType::Init(
                                     Box::new(AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "b".to_string()
+                                        "b".to_string(),
+                                        pos(),
                                     )),
-                                    Box::new(AstType::Factor(10))
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
                                 )
                             ],))
                         ),
-                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string())
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos())
                     ]))
                 )
             );
@@ -4470,13 +6343,15 @@ mod tests {
                         Box::new(None),
                         Box::new(AstType::Statement(vec![
                             AstType::Factor(1),
-                            AstType::Assign(
+                            AstType::Init(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "b".to_string()
+                                    "b".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(10))
+                                Box::new(AstType::Factor(10)),
+                                pos(),
                             )
                         ],))
                     )]))
@@ -4531,19 +6406,22 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![AstType::For(
-                        Box::new(Some(AstType::Assign(
+                        Box::new(Some(AstType::Init(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "i".to_string()
+                                "i".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(0))
+                            Box::new(AstType::Factor(0)),
+                            pos(),
                         ),)),
                         Box::new(Some(AstType::LessThan(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "i".to_string()
+                                "i".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Factor(10))
                         ),)),
@@ -4551,26 +6429,31 @@ mod tests {
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "i".to_string()
+                                "i".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Plus(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "i".to_string()
+                                    "i".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(1))
-                            ))
+                            )),
+                            pos(),
                         ))),
                         Box::new(AstType::Statement(vec![
                             AstType::Factor(1),
-                            AstType::Assign(
+                            AstType::Init(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "b".to_string()
+                                    "b".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(10))
+                                Box::new(AstType::Factor(10)),
+                                pos(),
                             )
                         ],))
                     )]))
@@ -4588,6 +6471,9 @@ mod tests {
                 create_token(Token::LeftParen, "(".to_string()),
                 create_token(Token::RightParen, ")".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::Do, "do".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
                 create_token(Token::Number, "1".to_string()),
@@ -4621,28 +6507,34 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Do(
-                        Box::new(AstType::Statement(vec![
-                            AstType::Factor(1),
-                            AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                            AstType::Assign(
+                    Box::new(AstType::Statement(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Do(
+                            Box::new(AstType::Statement(vec![
+                                AstType::Factor(1),
+                                AstType::Variable(Type::Int, Structure::Identifier, "a.1".to_string(), pos()),
+                                AstType::Init(
+                                    Box::new(AstType::Variable(
+                                        Type::Int,
+                                        Structure::Identifier,
+                                        "b".to_string(),
+                                        pos(),
+                                    )),
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
+                                )
+                            ],)),
+                            Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "b".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(10))
-                            )
-                        ],)),
-                        Box::new(AstType::Equal(
-                            Box::new(AstType::Variable(
-                                Type::Int,
-                                Structure::Identifier,
-                                "a".to_string()
+                                Box::new(AstType::Factor(3))
                             )),
-                            Box::new(AstType::Factor(3))
-                        )),
-                    )]))
+                        ),
+                    ]))
                 )
             );
         }
@@ -4657,6 +6549,9 @@ mod tests {
                 create_token(Token::LeftParen, "(".to_string()),
                 create_token(Token::RightParen, ")".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::Do, "do".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
                 create_token(Token::Number, "1".to_string()),
@@ -4692,29 +6587,35 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Do(
-                        Box::new(AstType::Statement(vec![
-                            AstType::Factor(1),
-                            AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                            AstType::Assign(
+                    Box::new(AstType::Statement(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Do(
+                            Box::new(AstType::Statement(vec![
+                                AstType::Factor(1),
+                                AstType::Variable(Type::Int, Structure::Identifier, "a.1".to_string(), pos()),
+                                AstType::Init(
+                                    Box::new(AstType::Variable(
+                                        Type::Int,
+                                        Structure::Identifier,
+                                        "b".to_string(),
+                                        pos(),
+                                    )),
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
+                                ),
+                                AstType::Continue(),
+                            ],)),
+                            Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "b".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(10))
-                            ),
-                            AstType::Continue(),
-                        ],)),
-                        Box::new(AstType::Equal(
-                            Box::new(AstType::Variable(
-                                Type::Int,
-                                Structure::Identifier,
-                                "a".to_string()
+                                Box::new(AstType::Factor(3))
                             )),
-                            Box::new(AstType::Factor(3))
-                        )),
-                    )]))
+                        ),
+                    ]))
                 )
             );
         }
@@ -4725,6 +6626,9 @@ mod tests {
                 create_token(Token::LeftParen, "(".to_string()),
                 create_token(Token::RightParen, ")".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::Do, "do".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
                 create_token(Token::Int, "int".to_string()),
@@ -4760,29 +6664,35 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Do(
-                        Box::new(AstType::Statement(vec![
-                            AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                            AstType::Factor(1),
-                            AstType::Assign(
+                    Box::new(AstType::Statement(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Do(
+                            Box::new(AstType::Statement(vec![
+                                AstType::Variable(Type::Int, Structure::Identifier, "a.1".to_string(), pos()),
+                                AstType::Factor(1),
+                                AstType::Init(
+                                    Box::new(AstType::Variable(
+                                        Type::Int,
+                                        Structure::Identifier,
+                                        "b".to_string(),
+                                        pos(),
+                                    )),
+                                    Box::new(AstType::Factor(10)),
+                                    pos(),
+                                ),
+                                AstType::Break(),
+                            ],)),
+                            Box::new(AstType::Equal(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Identifier,
-                                    "b".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
-                                Box::new(AstType::Factor(10))
-                            ),
-                            AstType::Break(),
-                        ],)),
-                        Box::new(AstType::Equal(
-                            Box::new(AstType::Variable(
-                                Type::Int,
-                                Structure::Identifier,
-                                "a".to_string()
+                                Box::new(AstType::Factor(3))
                             )),
-                            Box::new(AstType::Factor(3))
-                        )),
-                    )]))
+                        ),
+                    ]))
                 )
             );
         }
@@ -4791,6 +6701,7 @@ mod tests {
     #[test]
     fn test_statement_return() {
         {
+            // 値なしのreturn文
             let data = vec![
                 create_token(Token::Int, "int".to_string()),
                 create_token(Token::Variable, "main".to_string()),
@@ -4798,9 +6709,6 @@ mod tests {
                 create_token(Token::RightParen, ")".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
                 create_token(Token::Return, "return".to_string()),
-                create_token(Token::Number, "1".to_string()),
-                create_token(Token::Equal, "==".to_string()),
-                create_token(Token::Number, "2".to_string()),
                 create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::RightBrace, "}".to_string()),
                 create_token(Token::End, "End".to_string()),
@@ -4816,9 +6724,7 @@ mod tests {
                     Structure::Identifier,
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
-                    Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                        AstType::Equal(Box::new(AstType::Factor(1)), Box::new(AstType::Factor(2)))
-                    ))]))
+                    Box::new(AstType::Statement(vec![AstType::ReturnVoid(pos())]))
                 )
             );
         }
@@ -4829,12 +6735,45 @@ mod tests {
                 create_token(Token::LeftParen, "(".to_string()),
                 create_token(Token::RightParen, ")".to_string()),
                 create_token(Token::LeftBrace, "{".to_string()),
-                create_token(Token::Int, "int".to_string()),
-                create_token(Token::Variable, "a".to_string()),
-                create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::Return, "return".to_string()),
-                create_token(Token::Variable, "a".to_string()),
-                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::Equal, "==".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![AstType::Return(Box::new(
+                        AstType::Equal(Box::new(AstType::Factor(1)), Box::new(AstType::Factor(2)))
+                    ),
+                        pos(),)]))
+                )
+            );
+        }
+        {
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::RightBrace, "}".to_string()),
                 create_token(Token::End, "End".to_string()),
             ];
@@ -4850,12 +6789,14 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -4897,24 +6838,30 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Address(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
-                            )))),
+                                "a".to_string(),
+                                pos(),
+                            )),
+                                pos(),)),
+                            pos(),
                         ),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -4952,24 +6899,29 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )))),
+                            pos(),
                         ),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5006,12 +6958,14 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Pointer,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5044,12 +6998,14 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Pointer,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5092,27 +7048,32 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),)),
                             Box::new(AstType::Indirect(Box::new(AstType::Plus(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Pointer,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(1))
-                            )),))
+                            )),)),
+                            pos(),
                         ),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Pointer,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5153,27 +7114,32 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),)),
                             Box::new(AstType::Plus(
                                 Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Pointer,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )))),
                                 Box::new(AstType::Factor(1))
                             )),
+                            pos(),
                         ),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Pointer,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5214,27 +7180,32 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),)),
                             Box::new(AstType::Minus(
                                 Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Pointer,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )))),
                                 Box::new(AstType::Factor(1))
                             )),
+                            pos(),
                         ),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Pointer,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5267,12 +7238,14 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Return(Box::new(AstType::Variable(
                             Type::Char,
                             Structure::Pointer,
-                            "a".to_string()
-                        )))
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),)
                     ]))
                 )
             );
@@ -5315,20 +7288,24 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                        AstType::Assign(
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Init(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "b".to_string()
+                                "b".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Address(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
-                            )),))
+                                "a".to_string(),
+                                pos(),
+                            )),
+                                pos(),)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5372,28 +7349,34 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                        AstType::Assign(
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Init(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "b".to_string()
+                                "b".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Address(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
-                            )),))
+                                "a".to_string(),
+                                pos(),
+                            )),
+                                pos(),)),
+                            pos(),
                         ),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "b".to_string()
+                                "b".to_string(),
+                                pos(),
                             )),)),
                             Box::new(AstType::Factor(120)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5434,16 +7417,17 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Plus(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Factor(1)),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5480,16 +7464,17 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Minus(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Factor(1)),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5529,8 +7514,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Array(vec![3]), "a".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Int, Structure::Array(vec![3]), "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5573,19 +7558,21 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Array(vec![3]), "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Array(vec![3]), "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Plus(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Array(vec![3]),
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(0)),
                             )),)),
                             Box::new(AstType::Factor(10)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5624,8 +7611,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Array(vec![3, 3]), "a".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Int, Structure::Array(vec![3, 3]), "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5664,9 +7651,9 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5696,7 +7683,8 @@ mod tests {
                 AstType::Global(vec![AstType::Variable(
                     Type::Int,
                     Structure::Identifier,
-                    "a".to_string()
+                    "a".to_string(),
+                    pos(),
                 ),]),
             );
             assert_eq!(
@@ -5708,7 +7696,8 @@ mod tests {
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![AstType::Return(Box::new(
                         AstType::Factor(1)
-                    ),)]))
+                    ),
+                        pos(),)]))
                 )
             );
         }
@@ -5736,13 +7725,15 @@ mod tests {
             // 期待値確認.
             assert_eq!(
                 result.get_tree()[0],
-                AstType::Global(vec![AstType::Assign(
+                AstType::Global(vec![AstType::Init(
                     Box::new(AstType::Variable(
                         Type::Int,
                         Structure::Identifier,
-                        "a".to_string()
+                        "a".to_string(),
+                        pos(),
                     )),
                     Box::new(AstType::Factor(100)),
+                    pos(),
                 )])
             );
             assert_eq!(
@@ -5754,7 +7745,8 @@ mod tests {
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![AstType::Return(Box::new(
                         AstType::Factor(1)
-                    ),)]))
+                    ),
+                        pos(),)]))
                 )
             );
         }
@@ -5786,7 +7778,8 @@ mod tests {
                 AstType::Global(vec![AstType::Variable(
                     Type::Int,
                     Structure::Array(vec![10]),
-                    "a".to_string()
+                    "a".to_string(),
+                    pos(),
                 ),])
             );
             assert_eq!(
@@ -5798,7 +7791,8 @@ mod tests {
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![AstType::Return(Box::new(
                         AstType::Factor(1)
-                    ),)]))
+                    ),
+                        pos(),)]))
                 )
             );
         }
@@ -5832,9 +7826,9 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Identifier, "a".to_string()),
-                        AstType::Variable(Type::Char, Structure::Identifier, "b".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Char, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Variable(Type::Char, Structure::Identifier, "b".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5869,9 +7863,9 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
-                        AstType::Variable(Type::Int, Structure::Pointer, "b".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "b".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5906,9 +7900,9 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string()),
-                        AstType::Variable(Type::Char, Structure::Pointer, "b".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string(), pos()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "b".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -5941,12 +7935,14 @@ mod tests {
                 AstType::Global(vec![AstType::Variable(
                     Type::Int,
                     Structure::Identifier,
-                    "a".to_string()
+                    "a".to_string(),
+                    pos(),
                 ),
                 AstType::Variable(
                     Type::Char,
                     Structure::Identifier,
-                    "x".to_string()
+                    "x".to_string(),
+                    pos(),
                 ),])
             );
             assert_eq!(
@@ -5958,7 +7954,8 @@ mod tests {
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![AstType::Return(Box::new(
                         AstType::Factor(1)
-                    ),)]))
+                    ),
+                        pos(),)]))
                 )
             );
         }
@@ -5996,15 +7993,17 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Assign(
+                        AstType::Init(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
                             Box::new(AstType::Factor(10)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6046,19 +8045,21 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Plus(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Pointer,
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(2)),
                             )),)),
                             Box::new(AstType::Factor(10)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6101,19 +8102,21 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Array(vec![10]), "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Array(vec![10]), "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Plus(
                                 Box::new(AstType::Variable(
                                     Type::Int,
                                     Structure::Array(vec![10]),
-                                    "a".to_string()
+                                    "a".to_string(),
+                                    pos(),
                                 )),
                                 Box::new(AstType::Factor(2)),
                             )),)),
                             Box::new(AstType::Factor(10)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6165,26 +8168,26 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Array(vec![10, 2]),
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Plus(
-                                Box::new(AstType::Variable(
-                                    Type::Int,
-                                    Structure::Array(vec![10, 2]),
-                                    "a".to_string()
-                                )),
-                                Box::new(AstType::Plus(
-                                    Box::new(AstType::Multiple(
-                                        Box::new(AstType::Factor(2)),
-                                        Box::new(AstType::Factor(2)),
+                                Box::new(AstType::Indirect(Box::new(AstType::Plus(
+                                    Box::new(AstType::Variable(
+                                        Type::Int,
+                                        Structure::Array(vec![10, 2]),
+                                        "a".to_string(),
+                                        pos(),
                                     )),
-                                    Box::new(AstType::Factor(1)),
-                                )),
+                                    Box::new(AstType::Factor(2)),
+                                )))),
+                                Box::new(AstType::Factor(1)),
                             )),)),
                             Box::new(AstType::Factor(10)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6242,32 +8245,29 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Array(vec![10, 8, 2]),
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::Assign(
                             Box::new(AstType::Indirect(Box::new(AstType::Plus(
-                                Box::new(AstType::Variable(
-                                    Type::Int,
-                                    Structure::Array(vec![10, 8, 2]),
-                                    "a".to_string()
-                                )),
-                                Box::new(AstType::Plus(
-                                    Box::new(AstType::Multiple(
-                                        Box::new(AstType::Factor(2)),
-                                        Box::new(AstType::Factor(8)),
-                                    )),
-                                    Box::new(AstType::Plus(
-                                        Box::new(AstType::Multiple(
-                                            Box::new(AstType::Factor(4)),
-                                            Box::new(AstType::Factor(2)),
+                                Box::new(AstType::Indirect(Box::new(AstType::Plus(
+                                    Box::new(AstType::Indirect(Box::new(AstType::Plus(
+                                        Box::new(AstType::Variable(
+                                            Type::Int,
+                                            Structure::Array(vec![10, 8, 2]),
+                                            "a".to_string(),
+                                            pos(),
                                         )),
-                                        Box::new(AstType::Factor(1)),
-                                    ))
-                                )),
+                                        Box::new(AstType::Factor(2)),
+                                    )))),
+                                    Box::new(AstType::Factor(4)),
+                                )))),
+                                Box::new(AstType::Factor(1)),
                             )),)),
                             Box::new(AstType::Factor(10)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6307,13 +8307,15 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::PostInc(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )),),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6349,13 +8351,15 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::PostDec(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )),),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6395,13 +8399,15 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::PreInc(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )),),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6437,13 +8443,15 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
                         AstType::PreDec(Box::new(AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
-                        )),),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                            "a".to_string(),
+                            pos(),
+                        )),
+                            pos(),),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6480,8 +8488,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Identifier, "a".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Char, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6517,8 +8525,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Array(vec![3]), "a".to_string()),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Variable(Type::Char, Structure::Array(vec![3]), "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6540,6 +8548,7 @@ mod tests {
                 create_token(Token::Variable, "a".to_string()),
                 create_token(Token::Assign, "=".to_string()),
                 create_token(Token::StringLiteral, "testaaaa".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
                 create_token(Token::Return, "return".to_string()),
                 create_token(Token::Number, "1".to_string()),
                 create_token(Token::SemiColon, ";".to_string()),
@@ -6558,16 +8567,18 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Char,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::StringLiteral("testaaaa".to_string(), 0))
+                            Box::new(AstType::StringLiteral("testaaaa".to_string(), 0)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6604,16 +8615,18 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Char,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::StringLiteral("test, aaaa".to_string(), 0))
+                            Box::new(AstType::StringLiteral("test, aaaa".to_string(), 0)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6657,25 +8670,29 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "a".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Char,
                                 Structure::Pointer,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::StringLiteral("test, aaaa".to_string(), 0))
+                            Box::new(AstType::StringLiteral("test, aaaa".to_string(), 0)),
+                            pos(),
                         ),
-                        AstType::Variable(Type::Char, Structure::Pointer, "b".to_string()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "b".to_string(), pos()),
                         AstType::Assign(
                             Box::new(AstType::Variable(
                                 Type::Char,
                                 Structure::Pointer,
-                                "b".to_string()
+                                "b".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::StringLiteral("test, bbbb".to_string(), 1))
+                            Box::new(AstType::StringLiteral("test, bbbb".to_string(), 1)),
+                            pos(),
                         ),
-                        AstType::Return(Box::new(AstType::Factor(1)),)
+                        AstType::Return(Box::new(AstType::Factor(1)), pos())
                     ]))
                 )
             );
@@ -6715,8 +8732,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Char, Structure::Identifier, "a".to_string()),
-                        AstType::Return(Box::new(AstType::SizeOf(1)),)
+                        AstType::Variable(Type::Char, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::SizeOf(1)), pos())
                     ]))
                 )
             );
@@ -6749,7 +8766,7 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Return(Box::new(AstType::SizeOf(4)),)
+                        AstType::Return(Box::new(AstType::SizeOf(4)), pos())
                     ]))
                 )
             );
@@ -6782,7 +8799,7 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Return(Box::new(AstType::SizeOf(1)),)
+                        AstType::Return(Box::new(AstType::SizeOf(1)), pos())
                     ]))
                 )
             );
@@ -6815,7 +8832,7 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Return(Box::new(AstType::SizeOf(8)),)
+                        AstType::Return(Box::new(AstType::SizeOf(8)), pos())
                     ]))
                 )
             );
@@ -6848,7 +8865,7 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Return(Box::new(AstType::SizeOf(8)),)
+                        AstType::Return(Box::new(AstType::SizeOf(8)), pos())
                     ]))
                 )
             );
@@ -6884,8 +8901,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-                        AstType::Return(Box::new(AstType::SizeOf(4)),)
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::SizeOf(4)), pos())
                     ]))
                 )
             );
@@ -6924,8 +8941,8 @@ mod tests {
                     "main".to_string(),
                     Box::new(AstType::Argment(vec![])),
                     Box::new(AstType::Statement(vec![
-                        AstType::Variable(Type::Int, Structure::Array(vec![3]), "a".to_string()),
-                        AstType::Return(Box::new(AstType::SizeOf(24)),)
+                        AstType::Variable(Type::Int, Structure::Array(vec![3]), "a".to_string(), pos()),
+                        AstType::Return(Box::new(AstType::SizeOf(12)), pos())
                     ]))
                 )
             );
@@ -6966,15 +8983,18 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::PlusAssign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         )
                     ])),
                 )
@@ -7016,15 +9036,18 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::MinusAssign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         )
                     ,])),
                 )
@@ -7066,15 +9089,18 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::MultipleAssign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         )
                     ,])),
                 )
@@ -7116,15 +9142,18 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::DivisionAssign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         )
                     ,])),
                 )
@@ -7166,15 +9195,18 @@ mod tests {
                         AstType::Variable(
                             Type::Int,
                             Structure::Identifier,
-                            "a".to_string()
+                            "a".to_string(),
+                            pos(),
                         ),
                         AstType::RemainderAssign(
                             Box::new(AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             )),
-                            Box::new(AstType::Factor(3))
+                            Box::new(AstType::Factor(3)),
+                            pos(),
                         )
                     ,])),
                 )
@@ -7213,7 +9245,7 @@ mod tests {
                     Box::new(
                         AstType::Statement(vec![
                             AstType::Struct(
-                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string())),
+                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string(), pos())),
                                 vec![]
                             )
                         ])
@@ -7253,12 +9285,13 @@ mod tests {
                     Box::new(
                         AstType::Statement(vec![
                             AstType::Struct(
-                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string())),
+                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string(), pos())),
                                 vec![
                                     AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "a".to_string()
+                                        "a".to_string(),
+                                        pos(),
                                     )
                                 ]
                             )
@@ -7302,17 +9335,19 @@ mod tests {
                     Box::new(
                         AstType::Statement(vec![
                             AstType::Struct(
-                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string())),
+                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string(), pos())),
                                 vec![
                                     AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "a".to_string()
+                                        "a".to_string(),
+                                        pos(),
                                     ),
                                     AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "b".to_string()
+                                        "b".to_string(),
+                                        pos(),
                                     )
                                 ]
                             )
@@ -7356,17 +9391,19 @@ mod tests {
                     Box::new(
                         AstType::Statement(vec![
                             AstType::Struct(
-                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string())),
+                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string(), pos())),
                                 vec![
                                     AstType::Variable(
                                         Type::Int,
                                         Structure::Identifier,
-                                        "a".to_string()
+                                        "a".to_string(),
+                                        pos(),
                                     ),
                                     AstType::Variable(
                                         Type::Char,
                                         Structure::Identifier,
-                                        "b".to_string()
+                                        "b".to_string(),
+                                        pos(),
                                     )
                                 ]
                             )
@@ -7409,20 +9446,23 @@ mod tests {
                     AstType::Variable(
                         Type::Int,
                         Structure::Identifier,
-                        "a".to_string()
+                        "a".to_string(),
+                        pos(),
                     ),
                     AstType::Struct(
-                        Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string())),
+                        Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string(), pos())),
                         vec![
                             AstType::Variable(
                                 Type::Int,
                                 Structure::Identifier,
-                                "a".to_string()
+                                "a".to_string(),
+                                pos(),
                             ),
                             AstType::Variable(
                                 Type::Char,
                                 Structure::Identifier,
-                                "b".to_string()
+                                "b".to_string(),
+                                pos(),
                             )
                         ]
                     )
@@ -7478,11 +9518,12 @@ mod tests {
                     Box::new(
                         AstType::Statement(vec![
                             AstType::Struct(
-                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string())),
+                                Box::new(AstType::Variable(Type::Struct("Test".to_string()), Structure::Struct, "Test".to_string(), pos())),
                                 vec![]
                             ),
                             AstType::Variable(
-                                Type::Struct("Test".to_string()), Structure::Struct, "test".to_string()
+                                Type::Struct("Test".to_string()), Structure::Struct, "test".to_string(),
+                                pos(),
                             ),
                         ])
                     ),
@@ -7490,4 +9531,1374 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_init_list() {
+        {
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::Number, "3".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![
+                    AstType::Init(
+                        Box::new(AstType::Variable(
+                            Type::Int,
+                            Structure::Array(vec![2]),
+                            "a".to_string(),
+                            pos(),
+                        )),
+                        Box::new(AstType::InitList(vec![
+                            AstType::Factor(1),
+                            AstType::Plus(
+                                Box::new(AstType::Factor(2)),
+                                Box::new(AstType::Factor(3)),
+                            ),
+                        ])),
+                        pos(),
+                    ),
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn test_const_value() {
+        let ast = AstType::Plus(
+            Box::new(AstType::Multiple(Box::new(AstType::Factor(2)), Box::new(AstType::Factor(3)))),
+            Box::new(AstType::UnMinus(Box::new(AstType::SizeOf(4)))),
+        );
+        assert_eq!(Some(2), ast.const_value());
+
+        let ast = AstType::Division(Box::new(AstType::Factor(2)), Box::new(AstType::Factor(0)));
+        assert_eq!(None, ast.const_value());
+
+        let ast = AstType::Plus(
+            Box::new(AstType::Factor(2)),
+            Box::new(AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos())),
+        );
+        assert_eq!(None, ast.const_value());
+    }
+
+    #[test]
+    fn test_storage_class() {
+        {
+            let data = vec![
+                create_token(Token::Static, "static".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Extern, "extern".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Static, "static".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Static, "static".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "c".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![
+                    AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                    AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), pos()),
+                ])
+            );
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![AstType::StaticVar(Box::new(
+                        AstType::Init(
+                            Box::new(AstType::Variable(
+                                Type::Int,
+                                Structure::Identifier,
+                                "c".to_string(),
+                                pos(),
+                            )),
+                            Box::new(AstType::Factor(1)),
+                            pos(),
+                        )
+                    ))])),
+                )
+            );
+
+            let sym = ast.get_symbol();
+            assert_eq!(Storage::Static, sym.search(&Scope::Global, "a").unwrap().storage);
+            assert_eq!(Storage::Extern, sym.search(&Scope::Global, "b").unwrap().storage);
+            assert_eq!(Storage::Static, sym.search(&Scope::Func, "f").unwrap().storage);
+            assert_eq!(
+                Storage::Static,
+                sym.search(&Scope::Local("f".to_string()), "c").unwrap().storage
+            );
+        }
+        {
+            // extern宣言後の定義
+            let data = vec![
+                create_token(Token::Extern, "extern".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            ast.parse();
+            assert_eq!(Storage::Auto, ast.get_symbol().search(&Scope::Global, "a").unwrap().storage);
+        }
+    }
+
+    #[test]
+    fn test_func_decl() {
+        {
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::CharPointer, "char*".to_string()),
+                create_token(Token::Variable, "s".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "x".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::CharPointer, "char*".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認（グローバル変数は先頭にまとめる）.
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![
+                    AstType::Variable(Type::Int, Structure::Identifier, "x".to_string(), pos()),
+                ])
+            );
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDecl(
+                    Type::Int,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "".to_string(), pos()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "s".to_string(), pos()),
+                    ])),
+                )
+            );
+            assert_eq!(
+                result.get_tree()[2],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "b".to_string(), pos()),
+                    ])),
+                    Box::new(AstType::Statement(vec![])),
+                )
+            );
+
+            // 宣言の仮引数はシンボル登録しない
+            assert_eq!(None, ast.get_symbol().search(&Scope::Local("f".to_string()), "s"));
+        }
+    }
+
+    #[test]
+    fn test_compound_assign_indirect() {
+        {
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::IntPointer, "int*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::PlusAssign, "+=".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::MinusAssign, "-=".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            let p = AstType::Variable(Type::Int, Structure::Pointer, "p".to_string(), pos());
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![
+                        p.clone(),
+                        AstType::PlusAssign(
+                            Box::new(AstType::Indirect(Box::new(p.clone()))),
+                            Box::new(AstType::Factor(2)),
+                            pos(),
+                        ),
+                        AstType::MinusAssign(Box::new(p.clone()), Box::new(AstType::Factor(1)), pos()),
+                    ])),
+                )
+            );
+
+            // ポインタ同士の差は整数
+            let diff = AstType::Minus(Box::new(p.clone()), Box::new(p.clone()));
+            assert_eq!(Some((Type::Long, Structure::Identifier)), diff.value_type());
+            assert_eq!(
+                Some((Type::Int, Structure::Pointer)),
+                AstType::Plus(Box::new(AstType::Factor(1)), Box::new(p.clone())).value_type()
+            );
+        }
+    }
+
+    #[test]
+    fn test_eval_const() {
+        let f = |n| Box::new(AstType::Factor(n));
+        let v = || Box::new(AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()));
+
+        // 算術、ビット、比較、条件演算、sizeof
+        assert_eq!(Ok(7), AstType::Plus(f(3), Box::new(AstType::Multiple(f(2), f(2)))).eval_const());
+        assert_eq!(Ok(-2), AstType::Division(f(-7), f(3)).eval_const());
+        assert_eq!(Ok(-1), AstType::Remainder(f(-7), f(3)).eval_const());
+        assert_eq!(Ok(6), AstType::BitXor(Box::new(AstType::BitOr(f(4), f(3))), f(1)).eval_const());
+        assert_eq!(Ok(16), AstType::LeftShift(Box::new(AstType::SizeOf(4)), f(2)).eval_const());
+        assert_eq!(Ok(1), AstType::LessThanEqual(Box::new(AstType::UnMinus(f(1))), f(0)).eval_const());
+        assert_eq!(Ok(5), AstType::Condition(Box::new(AstType::Not(f(0))), f(5), v()).eval_const());
+
+        // 評価されない被演算子は無視
+        assert_eq!(Ok(0), AstType::LogicalAnd(f(0), Box::new(AstType::Division(f(1), f(0)))).eval_const());
+        assert_eq!(Ok(1), AstType::LogicalOr(f(2), v()).eval_const());
+
+        // 診断
+        assert_eq!(
+            Err("division by zero in constant expression".to_string()),
+            AstType::Remainder(f(1), Box::new(AstType::Minus(f(1), f(1)))).eval_const()
+        );
+        assert_eq!(
+            Err("integer overflow in constant expression".to_string()),
+            AstType::Multiple(f(65536), f(65536)).eval_const()
+        );
+        assert_eq!(
+            Err("integer overflow in constant expression".to_string()),
+            AstType::Division(f(-2147483648), f(-1)).eval_const()
+        );
+        assert_eq!(Ok(4294967296), AstType::Multiple(f(4294967296), f(1)).eval_const());
+        let shift = || Box::new(AstType::LeftShift(f(1), f(31)));
+        assert_eq!(Err("integer overflow in constant expression".to_string()), shift().eval_const());
+        assert_eq!(
+            Err("integer overflow in constant expression".to_string()),
+            AstType::Division(shift(), Box::new(AstType::UnMinus(f(1)))).eval_const()
+        );
+        assert_eq!(
+            Err("shift count 32 is out of range in constant expression".to_string()),
+            AstType::LeftShift(f(1), f(32)).eval_const()
+        );
+
+        // キャストは変換先の幅で切り詰め、longは演算の幅を広げる
+        let long = |n| Box::new(AstType::Cast(Type::Long, Structure::Identifier, f(n), pos()));
+        assert_eq!(Ok(-32768), AstType::Cast(Type::Short, Structure::Identifier, f(32768), pos()).eval_const());
+        assert_eq!(Ok(-1), AstType::Cast(Type::Char, Structure::Identifier, f(255), pos()).eval_const());
+        assert_eq!(Ok(1 << 40), AstType::LeftShift(long(1), f(40)).eval_const());
+        assert_eq!(Ok(4294967296), AstType::Multiple(long(65536), f(65536)).eval_const());
+        assert_eq!(Ok(0), AstType::Cast(Type::Int, Structure::Identifier, Box::new(AstType::LeftShift(long(1), f(32))), pos()).eval_const());
+        assert_eq!(
+            Err("shift count -1 is out of range in constant expression".to_string()),
+            AstType::RightShift(f(1), f(-1)).eval_const()
+        );
+        assert_eq!(Err("expression is not a constant".to_string()), AstType::Plus(f(1), v()).eval_const());
+        assert_eq!(None, AstType::Plus(f(1), v()).const_value());
+    }
+
+    #[test]
+    fn test_pointer_to_pointer() {
+        {
+            // int main(int argc, char **argv) { return argv[1][0]; }
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "argc".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::CharPointer, "char*".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Variable, "argv".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "argv".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "0".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            let argv = || Box::new(AstType::Variable(Type::Pointer(Box::new(Type::Char)), Structure::Pointer, "argv".to_string(), pos()));
+            let index = |a, n| Box::new(AstType::Indirect(Box::new(AstType::Plus(a, Box::new(AstType::Factor(n))))));
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "argc".to_string(), pos()),
+                        *argv(),
+                    ])),
+                    Box::new(AstType::Statement(vec![AstType::Return(index(index(argv(), 1), 0), pos())])),
+                )
+            );
+            assert_eq!(Some((Type::Char, Structure::Identifier)), index(index(argv(), 1), 0).value_type());
+        }
+    }
+
+    #[test]
+    fn test_bit_field() {
+        {
+            // struct F { unsigned a : 3; int : 0; signed char b : 2 + 1; int c; };
+            let data = vec![
+                create_token(Token::Struct, "struct".to_string()),
+                create_token(Token::Variable, "F".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Unsigned, "unsigned".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Colon, ":".to_string()),
+                create_token(Token::Number, "3".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Colon, ":".to_string()),
+                create_token(Token::Number, "0".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Signed, "signed".to_string()),
+                create_token(Token::Char, "char".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::Colon, ":".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "c".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            let bit = |width, signed| Structure::BitField(BitField { width, pos: 0, signed });
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![AstType::Struct(
+                    Box::new(AstType::Variable(Type::Struct("F".to_string()), Structure::Struct, "F".to_string(), pos())),
+                    vec![
+                        AstType::Variable(Type::Int, bit(3, false), "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, bit(0, true), "".to_string(), pos()),
+                        AstType::Variable(Type::Char, bit(3, true), "b".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Identifier, "c".to_string(), pos()),
+                    ],
+                )])
+            );
+
+            // メンバー登録時にビット位置が決まる
+            let def = ast.get_symbol().search(&Scope::Global, "F").unwrap();
+            let places: Vec<(usize, Structure)> = def.members.iter().map(|m| (m.offset, m.strt.clone())).collect();
+            assert_eq!(
+                vec![
+                    (0, bit(3, false)),
+                    (4, bit(0, true)),
+                    (4, bit(3, true)),
+                    (8, Structure::Identifier),
+                ],
+                places
+            );
+            assert_eq!(12, def.type_size());
+        }
+    }
+
+    #[test]
+    fn test_static_assert() {
+        {
+            // struct S { int a; _Static_assert(sizeof(int) == 4, "int"); }; int main() { _Static_assert(1, "ok"); }
+            let data = vec![
+                create_token(Token::Struct, "struct".to_string()),
+                create_token(Token::Variable, "S".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::StaticAssert, "_Static_assert".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::SizeOf, "sizeof".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Equal, "==".to_string()),
+                create_token(Token::Number, "4".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::StringLiteral, "int".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::StaticAssert, "_Static_assert".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::StringLiteral, "ok".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // アサーションはメンバーとして登録しない
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![AstType::Struct(
+                    Box::new(AstType::Variable(Type::Struct("S".to_string()), Structure::Struct, "S".to_string(), pos())),
+                    vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::StaticAssert(
+                            Box::new(AstType::Equal(Box::new(AstType::SizeOf(4)), Box::new(AstType::Factor(4)))),
+                            "int".to_string(),
+                            ":0:0".to_string(),
+                        ),
+                    ],
+                )])
+            );
+            assert_eq!(1, ast.get_symbol().search(&Scope::Global, "S").map(|s| s.members.len()).unwrap_or(0));
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![AstType::StaticAssert(
+                        Box::new(AstType::Factor(1)),
+                        "ok".to_string(),
+                        ":0:0".to_string(),
+                    )])),
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_sizeof_expr() {
+        {
+            // int main() { int a[2][3]; int *p; return sizeof a[1] + sizeof *p + sizeof(int*[4]) + _Alignof(char); }
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "3".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::IntPointer, "int*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::SizeOf, "sizeof".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::SizeOf, "sizeof".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::SizeOf, "sizeof".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::IntPointer, "int*".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "4".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::AlignOf, "_Alignof".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Char, "char".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認（被演算子は評価しない）
+            let plus = |a: AstType, b: AstType| AstType::Plus(Box::new(a), Box::new(b));
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![
+                        AstType::Variable(Type::Int, Structure::Array(vec![2, 3]), "a".to_string(), pos()),
+                        AstType::Variable(Type::Int, Structure::Pointer, "p".to_string(), pos()),
+                        AstType::Return(Box::new(plus(
+                            plus(plus(AstType::SizeOf(12), AstType::SizeOf(4)), AstType::SizeOf(32)),
+                            AstType::SizeOf(1),
+                        )),
+                            pos(),),
+                    ])),
+                )
+            );
+        }
+        {
+            // int a[sizeof(int) * 2];
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::SizeOf, "sizeof".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 要素数は定数式
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![AstType::Variable(Type::Int, Structure::Array(vec![8]), "a".to_string(), pos())])
+            );
+        }
+    }
+
+    #[test]
+    fn test_comma() {
+        {
+            // int f(int a, int b); int main() { int i; int j; for (i = 0, j = 5; i < j; i++, j--) {} return f((i, j), 1); }
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "j".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::For, "for".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Number, "0".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Variable, "j".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Number, "5".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::LessThan, "<".to_string()),
+                create_token(Token::Variable, "j".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::Inc, "++".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Variable, "j".to_string()),
+                create_token(Token::Dec, "--".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Variable, "j".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認（実引数の区切りとは区別する）
+            let i = AstType::Variable(Type::Int, Structure::Identifier, "i".to_string(), pos());
+            let j = AstType::Variable(Type::Int, Structure::Identifier, "j".to_string(), pos());
+            let comma = |a: AstType, b: AstType| AstType::Comma(Box::new(a), Box::new(b));
+            let assign = |a: &AstType, n| AstType::Assign(Box::new(a.clone()), Box::new(AstType::Factor(n)), pos());
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![
+                        i.clone(),
+                        j.clone(),
+                        AstType::For(
+                            Box::new(Some(comma(assign(&i, 0), assign(&j, 5)))),
+                            Box::new(Some(AstType::LessThan(Box::new(i.clone()), Box::new(j.clone())))),
+                            Box::new(Some(comma(
+                                AstType::PostInc(Box::new(i.clone()), pos()),
+                                AstType::PostDec(Box::new(j.clone()), pos()),
+                            ))),
+                            Box::new(AstType::Statement(vec![])),
+                        ),
+                        AstType::Return(Box::new(AstType::FuncCall(
+                            Box::new(AstType::Variable(Type::Int, Structure::Identifier, "f".to_string(), pos())),
+                            Box::new(AstType::Argment(vec![comma(i.clone(), j.clone()), AstType::Factor(1)])),
+                            pos(),
+                        )),
+                            pos(),),
+                    ])),
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_bit_assign() {
+        {
+            // int main() { int i; int a[2]; a[i++] <<= 2; i ^= a[0] |= 1; }
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::Inc, "++".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::LeftShiftAssign, "<<=".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "i".to_string()),
+                create_token(Token::BitXorAssign, "^=".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "0".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::BitOrAssign, "|=".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認（複合代入は右結合）
+            let i = AstType::Variable(Type::Int, Structure::Identifier, "i".to_string(), pos());
+            let a = AstType::Variable(Type::Int, Structure::Array(vec![2]), "a".to_string(), pos());
+            let elem = |index: AstType| AstType::Indirect(Box::new(AstType::Plus(Box::new(a.clone()), Box::new(index))));
+            let shift = AstType::LeftShiftAssign(
+                Box::new(elem(AstType::PostInc(Box::new(i.clone()), pos()))),
+                Box::new(AstType::Factor(2)),
+                pos(),
+            );
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "main".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![
+                        i.clone(),
+                        a.clone(),
+                        shift.clone(),
+                        AstType::BitXorAssign(
+                            Box::new(i.clone()),
+                            Box::new(AstType::BitOrAssign(Box::new(elem(AstType::Factor(0))), Box::new(AstType::Factor(1)), pos())),
+                            pos(),
+                        ),
+                    ])),
+                )
+            );
+
+            // 複合代入の値は左辺の型
+            assert!(shift.is_compound_assign());
+            assert_eq!(Some((Type::Int, Structure::Identifier)), shift.value_type());
+        }
+    }
+
+    #[test]
+    fn test_array_decay() {
+        {
+            // int f(int m[][3], int *a[2]) { return m[1][2]; }
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "m".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "3".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::IntPointer, "int*".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "m".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::LeftBracket, "[".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::RightBracket, "]".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 配列型の仮引数はポインタへ調整
+            let m = AstType::Variable(Type::Array(Box::new(Type::Int), vec![3]), Structure::Pointer, "m".to_string(), pos());
+            let a = AstType::Variable(Type::Pointer(Box::new(Type::Int)), Structure::Pointer, "a".to_string(), pos());
+            let row = AstType::Indirect(Box::new(AstType::Plus(Box::new(m.clone()), Box::new(AstType::Factor(1)))));
+            let elem = AstType::Indirect(Box::new(AstType::Plus(Box::new(row.clone()), Box::new(AstType::Factor(2)))));
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![m, a])),
+                    Box::new(AstType::Statement(vec![AstType::Return(Box::new(elem.clone()), pos())])),
+                )
+            );
+            assert_eq!(Some((Type::Int, Structure::Array(vec![3]))), row.value_type());
+            assert_eq!(Some((Type::Int, Structure::Identifier)), elem.value_type());
+        }
+    }
+
+    #[test]
+    fn test_struct_member() {
+        {
+            let data = vec![
+                create_token(Token::Struct, "struct".to_string()),
+                create_token(Token::Variable, "P".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "x".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "y".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Struct, "struct".to_string()),
+                create_token(Token::Variable, "P".to_string()),
+                create_token(Token::Variable, "g".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Struct, "struct".to_string()),
+                create_token(Token::Variable, "P".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Struct, "struct".to_string()),
+                create_token(Token::Variable, "P".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::Arrow, "->".to_string()),
+                create_token(Token::Variable, "y".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::Variable, "g".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Dot, ".".to_string()),
+                create_token(Token::Variable, "x".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            let st = Type::Struct("P".to_string());
+            let p = AstType::Variable(st.clone(), Structure::Pointer, "p".to_string(), pos());
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![
+                    AstType::Struct(
+                        Box::new(AstType::Variable(st.clone(), Structure::Struct, "P".to_string(), pos())),
+                        vec![
+                            AstType::Variable(Type::Int, Structure::Identifier, "x".to_string(), pos()),
+                            AstType::Variable(Type::Int, Structure::Identifier, "y".to_string(), pos()),
+                        ]
+                    ),
+                ])
+            );
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDecl(
+                    st.clone(),
+                    Structure::Struct,
+                    "g".to_string(),
+                    Box::new(AstType::Argment(vec![
+                        AstType::Variable(st.clone(), Structure::Struct, "a".to_string(), pos()),
+                    ])),
+                )
+            );
+            assert_eq!(
+                result.get_tree()[2],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![p.clone()])),
+                    Box::new(AstType::Statement(vec![
+                        AstType::Return(Box::new(AstType::Plus(
+                            Box::new(AstType::Member(
+                                Box::new(AstType::Indirect(Box::new(p.clone()))),
+                                Type::Int,
+                                Structure::Identifier,
+                                "y".to_string(),
+                            )),
+                            Box::new(AstType::Member(
+                                Box::new(AstType::FuncCall(
+                                    Box::new(AstType::Variable(st.clone(), Structure::Struct, "g".to_string(), pos())),
+                                    Box::new(AstType::Argment(vec![AstType::Indirect(Box::new(p.clone()))])),
+                                    pos(),
+                                )),
+                                Type::Int,
+                                Structure::Identifier,
+                                "x".to_string(),
+                            )),
+                        )),
+                            pos(),),
+                    ])),
+                )
+            );
+
+            // メンバーは変数として登録しない
+            assert_eq!(None, ast.get_symbol().search(&Scope::Global, "x"));
+            assert!(AstType::Indirect(Box::new(p.clone())).is_struct_value());
+            assert!(!p.is_struct_value());
+        }
+    }
+
+    #[test]
+    fn test_func_pointer() {
+        {
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Multi, "*".to_string()),
+                create_token(Token::Variable, "fp".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::CharPointer, "char*".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::CharPointer, "char*".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Variable, "fp".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "fp".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Comma, ",".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            let sig = Signature {
+                ret: (Type::Int, Structure::Identifier),
+                params: vec![(Type::Int, Structure::Identifier), (Type::Char, Structure::Pointer)],
+            };
+            let fp = AstType::Variable(Type::Func(Box::new(sig.clone())), Structure::Pointer, "fp".to_string(), pos());
+            assert_eq!(result.get_tree()[0], AstType::Global(vec![fp.clone()]));
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![
+                        AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                        AstType::Variable(Type::Char, Structure::Pointer, "b".to_string(), pos()),
+                    ])),
+                    Box::new(AstType::Statement(vec![
+                        AstType::Assign(
+                            Box::new(fp.clone()),
+                            Box::new(AstType::Variable(Type::Func(Box::new(sig.clone())), Structure::Identifier, "f".to_string(), pos())),
+                            pos(),
+                        ),
+                        AstType::Return(Box::new(AstType::FuncCall(
+                            Box::new(fp.clone()),
+                            Box::new(AstType::Argment(vec![
+                                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()),
+                                AstType::Variable(Type::Char, Structure::Pointer, "b".to_string(), pos()),
+                            ])),
+                            pos(),
+                        )),
+                            pos(),),
+                    ])),
+                )
+            );
+            assert_eq!(Some(sig), fp.func_signature());
+            assert!(fp.is_func_pointer());
+        }
+    }
+
+    #[test]
+    fn test_qualifier() {
+        {
+            let data = vec![
+                create_token(Token::Const, "const".to_string()),
+                create_token(Token::CharPointer, "char*".to_string()),
+                create_token(Token::Volatile, "volatile".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Const, "const".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::Global(vec![
+                    AstType::Variable(Type::Char, Structure::Pointer, "p".to_string(), pos()),
+                ])
+            );
+
+            let sym = ast.get_symbol();
+            let p = sym.search(&Scope::Global, "p").unwrap();
+            assert_eq!(Qualifier { is_const: false, is_volatile: true }, p.qual);
+            assert_eq!(Qualifier { is_const: true, is_volatile: false }, p.ptr_qual);
+
+            let a = sym.search(&Scope::Local("main".to_string()), "a").unwrap();
+            assert_eq!(Qualifier { is_const: true, is_volatile: false }, a.qual);
+            let b = sym.search(&Scope::Local("main".to_string()), "b").unwrap();
+            assert_eq!(Qualifier::default(), b.qual);
+        }
+    }
+
+    #[test]
+    fn test_void() {
+        {
+            // (void)は引数なし、void*の引数、voidへのキャスト
+            let data = vec![
+                create_token(Token::Void, "void".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Void, "void".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "g".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::VoidPointer, "void*".to_string()),
+                create_token(Token::Variable, "p".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Void, "void".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Variable, "f".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Char, "char".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::Number, "300".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 期待値確認.
+            assert_eq!(
+                result.get_tree()[0],
+                AstType::FuncDef(
+                    Type::Void,
+                    Structure::Identifier,
+                    "f".to_string(),
+                    Box::new(AstType::Argment(vec![])),
+                    Box::new(AstType::Statement(vec![]))
+                )
+            );
+            let cast = AstType::Cast(Type::Char, Structure::Identifier, Box::new(AstType::Factor(300)), pos());
+            assert_eq!(Some(44), cast.const_value());
+            assert_eq!(
+                result.get_tree()[1],
+                AstType::FuncDef(
+                    Type::Int,
+                    Structure::Identifier,
+                    "g".to_string(),
+                    Box::new(AstType::Argment(vec![AstType::Variable(
+                        Type::Void,
+                        Structure::Pointer,
+                        "p".to_string(),
+                        pos(),
+                    )])),
+                    Box::new(AstType::Statement(vec![
+                        AstType::Cast(
+                            Type::Void,
+                            Structure::Identifier,
+                            Box::new(AstType::FuncCall(
+                                Box::new(AstType::Variable(Type::Void, Structure::Identifier, "f".to_string(), pos())),
+                                Box::new(AstType::Argment(vec![])),
+                                pos(),
+                            )),
+                            pos(),
+                        ),
+                        AstType::Return(Box::new(cast), pos()),
+                    ]))
+                )
+            );
+            assert!(!ast.get_symbol().search(&Scope::Func, "f").unwrap().no_proto);
+            assert!(ast.get_errors().is_empty());
+        }
+        {
+            // void型の変数は宣言できない
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Void, "void".to_string()),
+                create_token(Token::Variable, "x".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            ast.parse();
+            assert!(ast.get_symbol().search(&Scope::Func, "main").unwrap().no_proto);
+            assert_eq!(&vec![":0:0: variable \"x\" declared void".to_string()], ast.get_errors());
+        }
+    }
+
+    #[test]
+    fn test_warnings() {
+        {
+            // 条件式の代入、ジャンプ文の後の文、未使用変数
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::While, "while".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Break, "break".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Number, "0".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Inc, "++".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            ast.parse();
+
+            // 期待値確認.
+            assert_eq!(
+                ast.get_warnings().iter().map(|w| (w.kind, w.msg.clone())).collect::<Vec<_>>(),
+                vec![
+                    (WarningKind::Parentheses, "suggest parentheses around assignment used as truth value".to_string()),
+                    (WarningKind::UnreachableCode, "code will never be executed".to_string()),
+                    (WarningKind::UnusedVariable, "unused variable \"b\"".to_string()),
+                ]
+            );
+            assert!(ast.get_errors().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        {
+            // int g 5; int main() { int a; a = 1 + ; a = 2; return a; }
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "g".to_string()),
+                create_token(Token::Number, "5".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::Plus, "+".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::Assign, "=".to_string()),
+                create_token(Token::Number, "2".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "a".to_string()),
+                create_token(Token::SemiColon, ";".to_string()),
+                create_token(Token::RightBrace, "}".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // エラー箇所はエラーノードに置き換え、後続の文は解析を継続
+            let a = || Box::new(AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), pos()));
+            assert_eq!(
+                *result.get_tree(),
+                vec![
+                    AstType::Global(vec![AstType::Error()]),
+                    AstType::FuncDef(
+                        Type::Int,
+                        Structure::Identifier,
+                        "main".to_string(),
+                        Box::new(AstType::Argment(vec![])),
+                        Box::new(AstType::Statement(vec![
+                            *a(),
+                            AstType::Error(),
+                            AstType::Assign(a(), Box::new(AstType::Factor(2)), pos()),
+                            AstType::Return(a(), pos()),
+                        ])),
+                    ),
+                ]
+            );
+            assert_eq!(
+                *ast.get_errors(),
+                vec![
+                    ":0:0: expected ';' but found number".to_string(),
+                    ":0:0: expected expression but found ';'".to_string(),
+                ]
+            );
+        }
+        {
+            // int main() { if (1) { return b.c
+            let data = vec![
+                create_token(Token::Int, "int".to_string()),
+                create_token(Token::Variable, "main".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::If, "if".to_string()),
+                create_token(Token::LeftParen, "(".to_string()),
+                create_token(Token::Number, "1".to_string()),
+                create_token(Token::RightParen, ")".to_string()),
+                create_token(Token::LeftBrace, "{".to_string()),
+                create_token(Token::Return, "return".to_string()),
+                create_token(Token::Variable, "b".to_string()),
+                create_token(Token::Dot, ".".to_string()),
+                create_token(Token::Variable, "c".to_string()),
+                create_token(Token::End, "End".to_string()),
+            ];
+            let mut ast = AstGen::new(&data);
+            let result = ast.parse();
+
+            // 入力の終端は一度だけ報告し、関数定義は残す
+            assert_eq!(1, result.get_tree().len());
+            assert_eq!(
+                *ast.get_errors(),
+                vec![
+                    ":0:0: use of undeclared identifier \"b\"".to_string(),
+                    ":0:0: expected '}' but found end of file".to_string(),
+                ]
+            );
+        }
+    }
 }
@@ -0,0 +1,455 @@
+use ast::AstType;
+use symbol::type_name;
+
+#[doc = "基本ブロック"]
+#[derive(Debug, PartialEq)]
+pub struct BasicBlock<'a> {
+    pub id: usize,
+    pub items: Vec<&'a AstType>,   // 順に評価する文、式
+    pub cond: Option<&'a AstType>, // 分岐条件（itemsの後に評価し、後続は(真, 偽)の順）
+    pub succs: Vec<usize>,         // 後続ブロック
+    pub preds: Vec<usize>,         // 先行ブロック
+}
+
+#[doc = "制御フローグラフ"]
+// 関数定義ごとに作成する（入口はブロック0、出口はブロック1）
+// 分岐条件の&&、||は短絡評価に従ってブロックを分ける
+#[derive(Debug)]
+pub struct Cfg<'a> {
+    pub name: String,
+    pub blocks: Vec<BasicBlock<'a>>,
+    loops: Vec<(usize, usize)>, // ループ内のジャンプ先（continue先、break先）
+}
+
+pub const ENTRY: usize = 0;
+pub const EXIT: usize = 1;
+
+#[doc = "条件分岐のブロック構築"]
+// CFGと中間表現で、&&、||を同じ規則でブロックへ分ける
+pub trait Branch<'a> {
+    // ブロック追加
+    fn new_block(&mut self) -> usize;
+    // &&、||以外の条件式で、ブロックbから真、偽のブロックへ分岐
+    fn leaf(&mut self, b: usize, c: &'a AstType, t: usize, f: usize);
+}
+
+// 条件分岐
+//
+// a && bはaが真の場合、a || bはaが偽の場合のみbを評価するブロックへ分岐する
+pub fn branch<'a, B: Branch<'a>>(builder: &mut B, b: usize, c: &'a AstType, t: usize, f: usize) {
+    match c {
+        AstType::LogicalAnd(ref l, ref r) => {
+            let right = builder.new_block();
+            branch(builder, b, l, right, f);
+            branch(builder, right, r, t, f);
+        }
+        AstType::LogicalOr(ref l, ref r) => {
+            let right = builder.new_block();
+            branch(builder, b, l, t, right);
+            branch(builder, right, r, t, f);
+        }
+        _ => builder.leaf(b, c, t, f),
+    }
+}
+
+impl<'a> Branch<'a> for Cfg<'a> {
+    fn new_block(&mut self) -> usize {
+        let id = self.blocks.len();
+        self.blocks.push(BasicBlock { id, items: vec![], cond: None, succs: vec![], preds: vec![] });
+        id
+    }
+
+    // 定数条件は片側のみ
+    fn leaf(&mut self, b: usize, c: &'a AstType, t: usize, f: usize) {
+        self.blocks[b].cond = Some(c);
+        match c.const_value() {
+            Some(0) => self.edge(b, f),
+            Some(_) => self.edge(b, t),
+            None => {
+                self.edge(b, t);
+                self.edge(b, f);
+            }
+        }
+    }
+}
+
+impl<'a> Cfg<'a> {
+    // 関数定義から作成
+    pub fn build(func: &'a AstType) -> Option<Self> {
+        match func {
+            AstType::FuncDef(_, _, ref name, _, ref stmt) => {
+                let mut cfg = Cfg { name: name.clone(), blocks: vec![], loops: vec![] };
+                let entry = cfg.new_block();
+                let exit = cfg.new_block();
+                let last = cfg.lower(stmt, entry);
+                cfg.edge(last, exit);
+                Some(cfg)
+            }
+            _ => None,
+        }
+    }
+
+    // 辺の追加
+    fn edge(&mut self, from: usize, to: usize) {
+        self.blocks[from].succs.push(to);
+        self.blocks[to].preds.push(from);
+    }
+
+    // 文をブロックへ展開し、後続の文を置くブロックを返す
+    //
+    // ジャンプ文の後続は、先行ブロックのない（到達しない）ブロックとする
+    fn lower(&mut self, ast: &'a AstType, cur: usize) -> usize {
+        match ast {
+            AstType::Statement(ref stmt) => stmt.iter().fold(cur, |b, s| self.lower(s, b)),
+            AstType::If(ref c, ref t, ref e) => {
+                let (then_b, join) = (self.new_block(), self.new_block());
+                let else_b = match **e {
+                    Some(_) => self.new_block(),
+                    None => join,
+                };
+                branch(self, cur, c, then_b, else_b);
+                let then_end = self.lower(t, then_b);
+                self.edge(then_end, join);
+                if let Some(ref e) = **e {
+                    let else_end = self.lower(e, else_b);
+                    self.edge(else_end, join);
+                }
+                join
+            }
+            AstType::While(ref c, ref body) => {
+                let cond = self.new_block();
+                self.edge(cur, cond);
+                self.loop_body(Some(c), None, body, cond, cond)
+            }
+            AstType::Do(ref body, ref c) => {
+                let (body_b, cond, exit) = (self.new_block(), self.new_block(), self.new_block());
+                self.edge(cur, body_b);
+                self.loops.push((cond, exit));
+                let end = self.lower(body, body_b);
+                self.loops.pop();
+                self.edge(end, cond);
+                branch(self, cond, c, body_b, exit);
+                exit
+            }
+            AstType::For(ref init, ref c, ref step, ref body) => {
+                if let Some(ref i) = **init {
+                    self.blocks[cur].items.push(i);
+                }
+                let (cond, step_b) = (self.new_block(), self.new_block());
+                self.edge(cur, cond);
+                if let Some(ref s) = **step {
+                    self.blocks[step_b].items.push(s);
+                }
+                self.edge(step_b, cond);
+                self.loop_body((**c).as_ref(), Some(step_b), body, cond, step_b)
+            }
+            AstType::Return(_, _) | AstType::ReturnVoid(_) => {
+                self.blocks[cur].items.push(ast);
+                self.edge(cur, EXIT);
+                self.new_block()
+            }
+            AstType::Break() | AstType::Continue() => {
+                let (cont, brk) = match self.loops.last() {
+                    Some(l) => *l,
+                    None => return cur,
+                };
+                let target = if *ast == AstType::Break() { brk } else { cont };
+                self.edge(cur, target);
+                self.new_block()
+            }
+            _ => {
+                self.blocks[cur].items.push(ast);
+                cur
+            }
+        }
+    }
+
+    // while、forの本体
+    //
+    // 条件式のブロックから本体へ分岐し、本体の終端はcontinue先（条件式または更新式）へ戻る
+    fn loop_body(&mut self, c: Option<&'a AstType>, step: Option<usize>, body: &'a AstType, cond: usize, cont: usize) -> usize {
+        let (body_b, exit) = (self.new_block(), self.new_block());
+        match c {
+            Some(c) => branch(self, cond, c, body_b, exit),
+            None => self.edge(cond, body_b),
+        }
+        self.loops.push((cont, exit));
+        let end = self.lower(body, body_b);
+        self.loops.pop();
+        self.edge(end, step.unwrap_or(cond));
+        exit
+    }
+
+    // Graphviz形式の出力
+    //
+    // 到達しない空のブロック（ジャンプ文の後続）は出力しない
+    pub fn to_dot(&self) -> String {
+        let escape = |t: String| t.replace('\\', "\\\\").replace('"', "\\\"");
+        let hidden = |b: &BasicBlock| b.id != ENTRY && b.preds.is_empty() && b.items.is_empty() && b.cond.is_none();
+        let mut dot = format!("digraph {:?} {{\n    node [shape=box, fontname=\"monospace\"];\n", self.name);
+        for b in self.blocks.iter().filter(|b| !hidden(b)) {
+            let name = match b.id {
+                ENTRY => format!("B{} (entry)", b.id),
+                EXIT => format!("B{} (exit)", b.id),
+                _ => format!("B{}", b.id),
+            };
+            let lines = b.items.iter().map(|i| text(i)).chain(b.cond.iter().map(|c| format!("if {}", text(c))));
+            let label: String = std::iter::once(name).chain(lines).map(|l| format!("{}\\l", escape(l))).collect();
+            dot = format!("{}    B{} [label=\"{}\"];\n", dot, b.id, label);
+        }
+        for b in self.blocks.iter().filter(|b| !hidden(b)) {
+            for (i, succ) in b.succs.iter().enumerate() {
+                let label = match (b.succs.len(), i) {
+                    (2, 0) => " [label=\"T\"]",
+                    (2, _) => " [label=\"F\"]",
+                    _ => "",
+                };
+                dot = format!("{}    B{} -> B{}{};\n", dot, b.id, succ, label);
+            }
+        }
+        format!("{}}}\n", dot)
+    }
+}
+
+// 式のソース表記（CFGの出力用）
+fn text(ast: &AstType) -> String {
+    // 二項演算の被演算子は、優先順位に関わらず括弧で囲む
+    let operand = |a: &AstType| match a {
+        AstType::Factor(_)
+        | AstType::Variable(_, _, _, _)
+        | AstType::StringLiteral(_, _)
+        | AstType::SizeOf(_)
+        | AstType::FuncCall(_, _, _)
+        | AstType::Member(_, _, _, _)
+        | AstType::Indirect(_) => text(a),
+        _ => format!("({})", text(a)),
+    };
+    let binary = |a: &AstType, op: &str, b: &AstType| format!("{} {} {}", operand(a), op, operand(b));
+    match ast {
+        AstType::Factor(n) => n.to_string(),
+        AstType::SizeOf(n) => n.to_string(),
+        AstType::Variable(_, _, ref n, _) => n.clone(),
+        AstType::StringLiteral(ref s, _) => format!("{:?}", s),
+        AstType::Return(ref a, _) => format!("return {}", text(a)),
+        AstType::ReturnVoid(_) => "return".to_string(),
+        AstType::Condition(ref c, ref a, ref b) => format!("{} ? {} : {}", operand(c), operand(a), operand(b)),
+        AstType::Comma(ref a, ref b) => format!("{}, {}", text(a), text(b)),
+        AstType::LogicalAnd(ref a, ref b) => binary(a, "&&", b),
+        AstType::LogicalOr(ref a, ref b) => binary(a, "||", b),
+        AstType::BitAnd(ref a, ref b) => binary(a, "&", b),
+        AstType::BitOr(ref a, ref b) => binary(a, "|", b),
+        AstType::BitXor(ref a, ref b) => binary(a, "^", b),
+        AstType::Equal(ref a, ref b) => binary(a, "==", b),
+        AstType::NotEqual(ref a, ref b) => binary(a, "!=", b),
+        AstType::LessThan(ref a, ref b) => binary(a, "<", b),
+        AstType::GreaterThan(ref a, ref b) => binary(a, ">", b),
+        AstType::LessThanEqual(ref a, ref b) => binary(a, "<=", b),
+        AstType::GreaterThanEqual(ref a, ref b) => binary(a, ">=", b),
+        AstType::Plus(ref a, ref b) => binary(a, "+", b),
+        AstType::Minus(ref a, ref b) => binary(a, "-", b),
+        AstType::LeftShift(ref a, ref b) => binary(a, "<<", b),
+        AstType::RightShift(ref a, ref b) => binary(a, ">>", b),
+        AstType::Multiple(ref a, ref b) => binary(a, "*", b),
+        AstType::Division(ref a, ref b) => binary(a, "/", b),
+        AstType::Remainder(ref a, ref b) => binary(a, "%", b),
+        AstType::Assign(ref a, ref b, _) | AstType::Init(ref a, ref b, _) => format!("{} = {}", text(a), text(b)),
+        AstType::PlusAssign(ref a, ref b, _) => format!("{} += {}", text(a), text(b)),
+        AstType::MinusAssign(ref a, ref b, _) => format!("{} -= {}", text(a), text(b)),
+        AstType::MultipleAssign(ref a, ref b, _) => format!("{} *= {}", text(a), text(b)),
+        AstType::DivisionAssign(ref a, ref b, _) => format!("{} /= {}", text(a), text(b)),
+        AstType::RemainderAssign(ref a, ref b, _) => format!("{} %= {}", text(a), text(b)),
+        AstType::BitAndAssign(ref a, ref b, _) => format!("{} &= {}", text(a), text(b)),
+        AstType::BitOrAssign(ref a, ref b, _) => format!("{} |= {}", text(a), text(b)),
+        AstType::BitXorAssign(ref a, ref b, _) => format!("{} ^= {}", text(a), text(b)),
+        AstType::LeftShiftAssign(ref a, ref b, _) => format!("{} <<= {}", text(a), text(b)),
+        AstType::RightShiftAssign(ref a, ref b, _) => format!("{} >>= {}", text(a), text(b)),
+        AstType::UnPlus(ref a) => format!("+{}", operand(a)),
+        AstType::UnMinus(ref a) => format!("-{}", operand(a)),
+        AstType::Not(ref a) => format!("!{}", operand(a)),
+        AstType::BitReverse(ref a) => format!("~{}", operand(a)),
+        AstType::Address(ref a, _) => format!("&{}", operand(a)),
+        AstType::Indirect(ref a) => match **a {
+            AstType::Plus(ref a, ref i) => format!("{}[{}]", operand(a), text(i)),
+            _ => format!("*{}", operand(a)),
+        },
+        AstType::PreInc(ref a, _) => format!("++{}", operand(a)),
+        AstType::PreDec(ref a, _) => format!("--{}", operand(a)),
+        AstType::PostInc(ref a, _) => format!("{}++", operand(a)),
+        AstType::PostDec(ref a, _) => format!("{}--", operand(a)),
+        AstType::FuncCall(ref f, ref args, _) => {
+            let args: Vec<String> = args.children().iter().map(|a| text(a)).collect();
+            format!("{}({})", operand(f), args.join(", "))
+        }
+        AstType::Argment(ref v) | AstType::InitList(ref v) => {
+            let v: Vec<String> = v.iter().map(text).collect();
+            format!("{{{}}}", v.join(", "))
+        }
+        AstType::Member(ref a, _, _, ref m) => match **a {
+            AstType::Indirect(ref p) => format!("{}->{}", operand(p), m),
+            _ => format!("{}.{}", operand(a), m),
+        },
+        AstType::Cast(ref t, ref s, ref a, _) => format!("({}){}", type_name(t, s), operand(a)),
+        AstType::StaticVar(ref a) => format!("static {}", text(a)),
+        AstType::StaticAssert(ref a, ref msg, _) => format!("_Static_assert({}, {:?})", text(a), msg),
+        AstType::Struct(_, _) => "struct".to_string(),
+        AstType::Error() => "<error>".to_string(),
+        // 文はブロックへ展開済み
+        AstType::Global(_)
+        | AstType::FuncDef(_, _, _, _, _)
+        | AstType::FuncDecl(_, _, _, _)
+        | AstType::Statement(_)
+        | AstType::While(_, _)
+        | AstType::Do(_, _)
+        | AstType::If(_, _, _)
+        | AstType::For(_, _, _, _)
+        | AstType::Continue()
+        | AstType::Break() => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use symbol::{Structure, Type};
+
+    fn var(n: &str) -> AstType {
+        AstType::Variable(Type::Int, Structure::Identifier, n.to_string(), String::new())
+    }
+
+    fn func(stmt: Vec<AstType>) -> AstType {
+        AstType::FuncDef(
+            Type::Int,
+            Structure::Identifier,
+            "f".to_string(),
+            Box::new(AstType::Argment(vec![])),
+            Box::new(AstType::Statement(stmt)),
+        )
+    }
+
+    #[test]
+    fn test_if() {
+        let f = func(vec![
+            var("a"),
+            AstType::If(
+                Box::new(var("a")),
+                Box::new(AstType::Statement(vec![AstType::Return(Box::new(AstType::Factor(1)), String::new())])),
+                Box::new(Some(AstType::Statement(vec![var("b")]))),
+            ),
+            AstType::Return(Box::new(AstType::Factor(0)), String::new()),
+        ]);
+        let cfg = Cfg::build(&f).unwrap();
+
+        // 入口で条件分岐し、真側はreturnで出口へ、偽側は合流ブロックへ
+        assert_eq!("f", cfg.name);
+        assert_eq!(vec![&var("a")], cfg.blocks[ENTRY].items);
+        assert_eq!(Some(&var("a")), cfg.blocks[ENTRY].cond);
+        assert_eq!(vec![2, 4], cfg.blocks[ENTRY].succs);
+        assert_eq!(vec![EXIT], cfg.blocks[2].succs);
+        assert_eq!(vec![3], cfg.blocks[4].succs);
+        assert_eq!(vec![5, 4], cfg.blocks[3].preds);
+        assert_eq!(vec![2, 3, 6], cfg.blocks[EXIT].preds);
+
+        // returnの後続ブロックには到達しない
+        assert!(cfg.blocks[5].preds.is_empty());
+        assert_eq!(vec![3], cfg.blocks[5].succs);
+    }
+
+    #[test]
+    fn test_loop() {
+        let f = func(vec![AstType::For(
+            Box::new(Some(AstType::Assign(Box::new(var("i")), Box::new(AstType::Factor(0)), String::new()))),
+            Box::new(Some(AstType::LessThan(Box::new(var("i")), Box::new(AstType::Factor(3))))),
+            Box::new(Some(AstType::PostInc(Box::new(var("i")), String::new()))),
+            Box::new(AstType::Statement(vec![AstType::If(
+                Box::new(var("i")),
+                Box::new(AstType::Statement(vec![AstType::Break()])),
+                Box::new(None),
+            )])),
+        )]);
+        let cfg = Cfg::build(&f).unwrap();
+
+        // 入口 -> 条件(2) -> 本体(4) / 終了(5)、更新(3) -> 条件
+        assert_eq!(vec![2], cfg.blocks[ENTRY].succs);
+        assert_eq!(vec![4, 5], cfg.blocks[2].succs);
+        assert_eq!(vec![ENTRY, 3], cfg.blocks[2].preds);
+        assert_eq!(vec![2], cfg.blocks[3].succs);
+
+        // breakは終了ブロックへ
+        assert_eq!(vec![6, 7], cfg.blocks[4].succs);
+        assert_eq!(vec![5], cfg.blocks[6].succs);
+        assert_eq!(vec![2, 6], cfg.blocks[5].preds);
+        assert_eq!(vec![EXIT], cfg.blocks[5].succs);
+
+        // 定数条件のループは終了ブロックへ分岐しない
+        let f = func(vec![AstType::While(Box::new(AstType::Factor(1)), Box::new(AstType::Statement(vec![])))]);
+        let cfg = Cfg::build(&f).unwrap();
+        assert_eq!(vec![3], cfg.blocks[2].succs);
+        assert!(cfg.blocks[4].preds.is_empty());
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        let f = func(vec![AstType::If(
+            Box::new(AstType::LogicalOr(
+                Box::new(AstType::LogicalAnd(Box::new(var("a")), Box::new(var("b")))),
+                Box::new(var("c")),
+            )),
+            Box::new(AstType::Statement(vec![AstType::Assign(Box::new(var("x")), Box::new(AstType::Factor(1)), String::new())])),
+            Box::new(None),
+        )]);
+        let cfg = Cfg::build(&f).unwrap();
+
+        // aが偽、またはa、bが真でもcが偽なら合流ブロック(3)へ
+        assert_eq!(Some(&var("a")), cfg.blocks[ENTRY].cond);
+        assert_eq!(vec![5, 4], cfg.blocks[ENTRY].succs);
+        assert_eq!(Some(&var("b")), cfg.blocks[5].cond);
+        assert_eq!(vec![2, 4], cfg.blocks[5].succs);
+        assert_eq!(Some(&var("c")), cfg.blocks[4].cond);
+        assert_eq!(vec![2, 3], cfg.blocks[4].succs);
+        assert_eq!(vec![ENTRY, 5], cfg.blocks[4].preds);
+        assert_eq!(vec![5, 4], cfg.blocks[2].preds);
+
+        // 定数の左辺は右辺を評価しない
+        let f = func(vec![AstType::While(
+            Box::new(AstType::LogicalAnd(Box::new(AstType::Factor(0)), Box::new(var("a")))),
+            Box::new(AstType::Statement(vec![])),
+        )]);
+        let cfg = Cfg::build(&f).unwrap();
+        assert_eq!(vec![4], cfg.blocks[2].succs);
+        assert!(cfg.blocks[5].preds.is_empty());
+    }
+
+    #[test]
+    fn test_dot() {
+        let f = func(vec![
+            AstType::If(
+                Box::new(AstType::LessThan(Box::new(var("a")), Box::new(AstType::Factor(0)))),
+                Box::new(AstType::Statement(vec![AstType::Return(Box::new(AstType::UnMinus(Box::new(var("a")))), String::new())])),
+                Box::new(None),
+            ),
+            AstType::Return(Box::new(AstType::FuncCall(
+                Box::new(var("g")),
+                Box::new(AstType::Argment(vec![AstType::StringLiteral("s".to_string(), 0), var("a")])),
+                String::new(),
+            )),
+                String::new(),),
+        ]);
+        let cfg = Cfg::build(&f).unwrap();
+        assert_eq!(
+            concat!(
+                "digraph \"f\" {\n",
+                "    node [shape=box, fontname=\"monospace\"];\n",
+                "    B0 [label=\"B0 (entry)\\lif a < 0\\l\"];\n",
+                "    B1 [label=\"B1 (exit)\\l\"];\n",
+                "    B2 [label=\"B2\\lreturn -a\\l\"];\n",
+                "    B3 [label=\"B3\\lreturn g(\\\"s\\\", a)\\l\"];\n",
+                "    B0 -> B2 [label=\"T\"];\n",
+                "    B0 -> B3 [label=\"F\"];\n",
+                "    B2 -> B1;\n",
+                "    B3 -> B1;\n",
+                "}\n",
+            ),
+            cfg.to_dot()
+        );
+    }
+}
@@ -0,0 +1,1696 @@
+use ast::{AstTree, AstType};
+use cfg::{self, BasicBlock, Cfg};
+use std::fmt;
+use symbol::{align_to, BitField, Scope, Storage, Structure, Symbol, SymbolTable, Type};
+
+#[doc = "型"]
+// 仮想レジスタの値はI64かPtrのいずれかで、I8、I32はメモリアクセス、変換の幅としてのみ使用する
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ty {
+    I8,
+    I32,
+    I64,
+    Ptr,
+}
+
+impl Ty {
+    // 読み込んだ値（変換結果）の仮想レジスタの型
+    pub fn value(self) -> Ty {
+        match self {
+            Ty::Ptr => Ty::Ptr,
+            _ => Ty::I64,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Ty::I8 => "i8",
+            Ty::I32 => "i32",
+            Ty::I64 => "i64",
+            Ty::Ptr => "ptr",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// 仮想レジスタ（関数内で一度だけ定義される）
+pub type Reg = usize;
+
+#[doc = "アドレスの基点"]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Addr {
+    Local(usize),   // ローカル変数、一時領域（rbpからの距離）
+    Global(String), // 静的領域のラベル
+    Func(String),   // 関数
+    Str(usize),     // 文字列リテラル
+}
+
+impl fmt::Display for Addr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Addr::Local(n) => write!(f, "local {}", n),
+            Addr::Global(ref l) => write!(f, "global @{}", l),
+            Addr::Func(ref n) => write!(f, "func @{}", n),
+            Addr::Str(i) => write!(f, "str .LC{}", i),
+        }
+    }
+}
+
+#[doc = "二項演算子"]
+// 整数演算は64bitで行う（Sarは算術、Shrは論理右シフト）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Shl,
+    Sar,
+    Shr,
+    And,
+    Or,
+    Xor,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl BinOp {
+    // 比較演算子判定
+    pub fn is_compare(self) -> bool {
+        matches!(self, BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge)
+    }
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
+
+#[doc = "単項演算子"]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnOp {
+    Neg, // 符号反転
+    Not, // ビット反転
+}
+
+#[doc = "呼び出し先"]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Callee {
+    Direct(String), // 関数名
+    Indirect(Reg),  // 関数ポインタ
+}
+
+#[doc = "実引数"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arg {
+    pub reg: Reg,
+    pub size: Option<usize>, // 構造体の値渡しの場合、構造体サイズ（regは先頭アドレス）
+}
+
+#[doc = "命令"]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inst {
+    Const(Reg, i64),
+    Addr(Reg, Addr),
+    Load(Reg, Ty, Reg, bool),       // 格納先、読み込む幅、アドレス、volatile（I8、I32は符号拡張）
+    Store(Ty, Reg, Reg, bool),      // 書き込む幅、値、アドレス、volatile
+    Bin(Reg, BinOp, Reg, Reg),
+    Un(Reg, UnOp, Reg),
+    Conv(Reg, Ty, Reg),             // I8、I32は切り詰めて符号拡張、I64、Ptrは型のみ変換
+    Call {
+        dst: Option<Reg>,
+        callee: Callee,
+        args: Vec<Arg>,
+        sret: Option<(Reg, usize)>, // 構造体の戻り値の格納先、サイズ
+    },
+    Memcpy(Reg, Reg, usize),        // コピー先、コピー元、サイズ
+}
+
+impl Inst {
+    // 定義する仮想レジスタ
+    pub fn def(&self) -> Option<Reg> {
+        match *self {
+            Inst::Const(d, _)
+            | Inst::Addr(d, _)
+            | Inst::Load(d, _, _, _)
+            | Inst::Bin(d, _, _, _)
+            | Inst::Un(d, _, _)
+            | Inst::Conv(d, _, _) => Some(d),
+            Inst::Call { dst, .. } => dst,
+            Inst::Store(_, _, _, _) | Inst::Memcpy(_, _, _) => None,
+        }
+    }
+
+    // 使用する仮想レジスタ
+    pub fn uses(&self) -> Vec<Reg> {
+        match *self {
+            Inst::Const(_, _) | Inst::Addr(_, _) => vec![],
+            Inst::Load(_, _, a, _) | Inst::Un(_, _, a) | Inst::Conv(_, _, a) => vec![a],
+            Inst::Store(_, a, b, _) | Inst::Bin(_, _, a, b) | Inst::Memcpy(a, b, _) => vec![a, b],
+            Inst::Call { ref callee, ref args, sret, .. } => {
+                let mut v: Vec<Reg> = match *callee {
+                    Callee::Indirect(r) => vec![r],
+                    Callee::Direct(_) => vec![],
+                };
+                v.extend(args.iter().map(|a| a.reg));
+                v.extend(sret.map(|s| s.0));
+                v
+            }
+        }
+    }
+}
+
+#[doc = "終端命令"]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Jump(usize),
+    Branch(Reg, usize, usize), // 0以外であれば真
+    Ret(Option<Reg>),
+}
+
+impl Term {
+    // 後続ブロック
+    pub fn succs(&self) -> Vec<usize> {
+        match *self {
+            Term::Jump(b) => vec![b],
+            Term::Branch(_, t, f) => vec![t, f],
+            Term::Ret(_) => vec![],
+        }
+    }
+
+    // 使用する仮想レジスタ
+    pub fn uses(&self) -> Vec<Reg> {
+        match *self {
+            Term::Branch(r, _, _) | Term::Ret(Some(r)) => vec![r],
+            _ => vec![],
+        }
+    }
+}
+
+#[doc = "基本ブロック"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub insts: Vec<Inst>,
+    pub term: Option<Term>,
+}
+
+#[doc = "戻り値の種類"]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetKind {
+    Void,
+    Scalar,
+    Struct(usize), // 構造体サイズ（戻り値は構造体の先頭アドレス）
+}
+
+#[doc = "仮引数"]
+// 受け取った引数はローカル変数の領域へ格納する
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub offset: usize,       // 格納先（rbpからの距離）
+    pub size: Option<usize>, // 構造体の値渡しの場合、構造体サイズ
+}
+
+#[doc = "関数"]
+// 入口はブロック0
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub export: bool,
+    pub ret: RetKind,
+    pub params: Vec<Param>,
+    pub frame_size: usize, // ローカル変数、一時領域のサイズ
+    pub regs: Vec<Ty>,     // 仮想レジスタの型
+    pub blocks: Vec<Block>,
+}
+
+#[doc = "初期化値"]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitValue {
+    Int(i64),
+    Addr(Addr, i64), // アドレス定数（基点、オフセット）
+}
+
+#[doc = "初期化データ"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Init {
+    pub offset: usize,
+    pub size: usize,
+    pub value: InitValue,
+}
+
+#[doc = "静的領域のオブジェクト"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Object {
+    pub label: String,
+    pub size: usize,
+    pub align: usize,
+    pub export: bool,
+    pub readonly: bool,
+    pub init: Option<Vec<Init>>, // 初期化子がなければゼロ初期化
+}
+
+#[doc = "中間表現"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub strings: Vec<(usize, String)>,
+    pub objects: Vec<Object>,
+    pub funcs: Vec<Function>,
+}
+
+impl Module {
+    // 意味解析済みのASTから作成
+    pub fn build(tree: &AstTree, table: &SymbolTable) -> Self {
+        let mut lower = Lower::new(table);
+        tree.get_tree().iter().for_each(|a| lower.item(a));
+        lower.module
+    }
+}
+
+#[doc = "中間表現生成部"]
+struct Lower<'a> {
+    sym_table: &'a SymbolTable,
+    cur_scope: Scope,
+    module: Module,
+    regs: Vec<Ty>,
+    blocks: Vec<Block>,
+    cur: usize,      // 命令を追加するブロック
+    temp_pos: usize, // 一時領域の確保位置
+    ret: RetKind,    // 生成中の関数の戻り値
+}
+
+impl<'a> Lower<'a> {
+    // コンストラクタ.
+    fn new(table: &'a SymbolTable) -> Self {
+        Lower {
+            sym_table: table,
+            cur_scope: Scope::Unknown,
+            module: Module { strings: vec![], objects: vec![], funcs: vec![] },
+            regs: vec![],
+            blocks: vec![],
+            cur: 0,
+            temp_pos: 0,
+            ret: RetKind::Void,
+        }
+    }
+
+    // トップレベルの要素
+    fn item(&mut self, ast: &AstType) {
+        match *ast {
+            AstType::Global(ref a) => {
+                self.cur_scope = Scope::Global;
+                self.global(a);
+            }
+            AstType::FuncDef(ref t, ref s, ref a, ref b, _) => {
+                self.cur_scope = Scope::Local(a.clone());
+                self.function(ast, t, s, a, b);
+            }
+            AstType::FuncDecl(_, _, _, _) => {} // 宣言のみなので何もしない
+            _ => panic!("{} {}: not support expression {:?}", file!(), line!(), ast),
+        }
+    }
+
+    // シンボル情報取得
+    //
+    // ローカルスコープに存在しない場合、Globalから検索
+    fn get_var_symbol(&self, k: &str) -> Symbol {
+        self.sym_table
+            .search(&self.cur_scope, k)
+            .or_else(|| self.sym_table.search(&Scope::Global, k))
+            .unwrap_or_else(|| panic!("{} {}: not exists symbol {}", file!(), line!(), k))
+    }
+
+    // 静的領域のラベル取得
+    //
+    // 関数内static変数は、関数名で修飾したラベルとする
+    fn object_label(&self, sym: &Symbol) -> String {
+        match sym.scope {
+            Scope::Local(ref f) => format!("{}.{}", f, sym.var),
+            _ => sym.var.clone(),
+        }
+    }
+
+    // グローバル変数定義
+    //
+    // extern宣言、及び定義が別にある宣言は領域を確保しない
+    fn global(&mut self, a: &[AstType]) {
+        let mut defined: Vec<String> = a.iter().filter_map(|d| match d {
+            AstType::Init(ref v, _, _) => match **v {
+                AstType::Variable(_, _, ref name, _) => Some(name.clone()),
+                _ => None,
+            },
+            _ => None,
+        }).collect();
+        a.iter().for_each(|d| match d {
+            AstType::Variable(_, _, ref name, _) => {
+                if self.get_var_symbol(name).storage != Storage::Extern && !defined.contains(name) {
+                    defined.push(name.clone());
+                    self.object(name, None);
+                }
+            }
+            AstType::Struct(_, _) => {}, // 構造体定義のみなのでSKIP
+            AstType::StaticAssert(_, _, _) => {}, // 意味解析で評価済みなのでSKIP
+            _ => self.static_object(d),
+        });
+    }
+
+    // 静的領域の変数定義（初期化子付き、又は関数内static変数）
+    fn static_object(&mut self, a: &AstType) {
+        match a {
+            AstType::Init(ref v, ref init, _) => match **v {
+                AstType::Variable(_, _, ref name, _) => self.object(name, Some(init)),
+                _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), v),
+            },
+            AstType::Variable(_, _, ref name, _) => self.object(name, None),
+            _ => panic!("{} {}: cannot support AstType {:?}", file!(), line!(), a),
+        }
+    }
+
+    // 静的領域のオブジェクト生成
+    //
+    // static指定されていなければ、外部から参照可能とする
+    fn object(&mut self, name: &str, init: Option<&AstType>) {
+        let sym = self.get_var_symbol(name);
+        let init = init.map(|i| {
+            let mut data = vec![];
+            self.init_data(&sym, i, 0, &mut data);
+            data.sort_by_key(|d| d.offset);
+            data
+        });
+        self.module.objects.push(Object {
+            label: self.object_label(&sym),
+            size: sym.size,
+            align: sym.align(),
+            export: sym.storage != Storage::Static,
+            readonly: sym.qual.is_const,
+            init,
+        });
+    }
+
+    // 初期化データ作成
+    //
+    // 初期化子を(オフセット, サイズ, 値)の並びへ展開する
+    fn init_data(&mut self, sym: &Symbol, init: &AstType, offset: usize, data: &mut Vec<Init>) {
+        match (&sym.strt, init) {
+            // 文字列リテラルによる文字配列の初期化（配列に収まる分のみ）
+            (Structure::Array(ref dims), AstType::StringLiteral(ref s, _)) if sym.t == Type::Char && dims.len() == 1 => {
+                let stride = sym.storage_size() / dims[0];
+                s.bytes()
+                    .chain(std::iter::once(0))
+                    .take(dims[0])
+                    .enumerate()
+                    .for_each(|(i, c)| data.push(Init { offset: offset + i * stride, size: sym.type_size(), value: InitValue::Int(c as i64) }));
+            }
+            (Structure::Array(ref dims), AstType::InitList(ref items)) => {
+                // 多次元配列で波括弧が省略されている場合、一次元配列とみなす
+                let nested = items.iter().any(|i| matches!(i, AstType::InitList(_)));
+                let dims = if dims.len() > 1 && !nested { vec![dims.iter().product()] } else { dims.clone() };
+
+                let stride = sym.storage_size() / dims[0];
+                let mut elem = sym.clone();
+                elem.strt = match sym.t {
+                    _ if dims.len() > 1 => Structure::Array(dims[1..].to_vec()),
+                    Type::Struct(_) | Type::Union(_) => Structure::Struct,
+                    _ => Structure::Identifier,
+                };
+                items.iter().take(dims[0]).enumerate().for_each(|(i, item)| {
+                    self.init_data(&elem, item, offset + i * stride, data);
+                });
+            }
+            (Structure::Struct, AstType::InitList(ref items)) => {
+                // 無名ビットフィールドは初期化の対象外（共用体は先頭のメンバーのみ）
+                let count = match sym.t {
+                    Type::Union(_) => 1,
+                    _ => items.len(),
+                };
+                sym.members.iter().filter(|m| !m.var.is_empty()).zip(items.iter().take(count)).for_each(|(m, item)| {
+                    self.init_data(m, item, offset + m.offset, data)
+                });
+            }
+            // ビットフィールドは格納単位を共有するので、バイト毎に既存の値と合成
+            (Structure::BitField(ref b), _) => {
+                let v = init.const_value()
+                    .unwrap_or_else(|| panic!("{} {}: initializer is not constant {:?}", file!(), line!(), init));
+                let bits = (v & ((1i64 << b.width) - 1)) << b.pos;
+                (b.pos / 8..=(b.pos + b.width - 1) / 8).for_each(|i| {
+                    let byte = (bits >> (i * 8)) & 0xff;
+                    match data.iter_mut().find(|d| d.offset == offset + i) {
+                        Some(Init { value: InitValue::Int(n), .. }) => *n |= byte,
+                        _ => data.push(Init { offset: offset + i, size: 1, value: InitValue::Int(byte) }),
+                    }
+                });
+            }
+            // 波括弧で囲まれたスカラー
+            (_, AstType::InitList(ref items)) => {
+                if let Some(item) = items.first() {
+                    self.init_data(sym, item, offset, data);
+                }
+            }
+            _ => {
+                let value = self.init_value(init, sym.type_size());
+                data.push(Init { offset, size: sym.type_size(), value });
+            }
+        }
+    }
+
+    // 初期化値生成
+    //
+    // 定数式、グローバル変数のアドレス、文字列リテラルをサポート
+    fn init_value(&mut self, a: &AstType, size: usize) -> InitValue {
+        if let Some(v) = a.const_value() {
+            // 型のサイズに切り詰める
+            return InitValue::Int(match size {
+                1 => v as i8 as i64,
+                2 => v as i16 as i64,
+                4 => v as i32 as i64,
+                _ => v,
+            });
+        }
+        match a {
+            AstType::Address(ref v, _) => self.init_address(v),
+            AstType::Variable(Type::Func(_), Structure::Identifier, ref name, _) => InitValue::Addr(Addr::Func(name.clone()), 0),
+            AstType::Variable(_, Structure::Array(_), ref name, _) => {
+                InitValue::Addr(Addr::Global(self.object_label(&self.get_var_symbol(name))), 0)
+            }
+            AstType::StringLiteral(ref s, i) => {
+                self.add_string(s, *i);
+                InitValue::Addr(Addr::Str(*i), 0)
+            }
+            _ => panic!("{} {}: initializer is not constant {:?}", file!(), line!(), a),
+        }
+    }
+
+    // グローバル変数のアドレス定数生成（配列要素は添字毎に辿る）
+    fn init_address(&self, a: &AstType) -> InitValue {
+        match a {
+            AstType::Variable(Type::Func(_), Structure::Identifier, ref name, _) => InitValue::Addr(Addr::Func(name.clone()), 0),
+            AstType::Variable(_, _, ref name, _) => InitValue::Addr(Addr::Global(self.object_label(&self.get_var_symbol(name))), 0),
+            AstType::Indirect(ref e) => match **e {
+                AstType::Plus(ref v, ref index) => match (self.pointee_size(v), index.const_value(), self.init_address(v)) {
+                    (Some(n), Some(i), InitValue::Addr(base, offset)) => InitValue::Addr(base, offset + i * n as i64),
+                    _ => panic!("{} {}: initializer is not constant {:?}", file!(), line!(), a),
+                },
+                _ => panic!("{} {}: initializer is not constant {:?}", file!(), line!(), a),
+            },
+            _ => panic!("{} {}: initializer is not constant {:?}", file!(), line!(), a),
+        }
+    }
+
+    // 文字列リテラル登録
+    fn add_string(&mut self, s: &str, i: usize) {
+        if !self.module.strings.iter().any(|(n, _)| *n == i) {
+            self.module.strings.push((i, s.to_string()));
+        }
+    }
+
+    // 関数定義.
+    //
+    // 制御フローグラフのブロックを、同じ番号の基本ブロックとして生成する
+    fn function(&mut self, func: &AstType, t: &Type, s: &Structure, name: &str, args: &AstType) {
+        // ローカル変数の後ろに一時領域を確保.
+        let scope = Scope::Local(name.to_string());
+        self.temp_pos = std::cmp::max(self.sym_table.size(&scope), self.sym_table.frame_size(&scope));
+        self.regs = vec![];
+        self.blocks = vec![];
+
+        self.ret = match (t, s) {
+            (_, Structure::Struct) => RetKind::Struct(self.struct_size(t)),
+            (Type::Void, Structure::Identifier) => RetKind::Void,
+            _ => RetKind::Scalar,
+        };
+        let params = match *args {
+            AstType::Argment(ref v) => v.iter().map(|arg| {
+                let sym = match arg {
+                    AstType::Variable(_, _, ref n, _) => self.get_var_symbol(n),
+                    _ => panic!("{} {}: not support expr {:?}", file!(), line!(), arg),
+                };
+                let size = match sym.strt {
+                    Structure::Struct => Some(sym.type_size()),
+                    _ => None,
+                };
+                Param { offset: sym.frame_offset(), size }
+            }).collect(),
+            _ => panic!("{} {}: not support expr {:?}", file!(), line!(), args),
+        };
+
+        let cfg = Cfg::build(func).expect("ir.rs(function): not function definition");
+        cfg.blocks.iter().for_each(|_| {
+            self.new_block();
+        });
+        cfg.blocks.iter().for_each(|b| self.block(b, name));
+
+        let storage = self.sym_table.search(&Scope::Func, name).map(|s| s.storage);
+        let func = Function {
+            name: name.to_string(),
+            export: storage != Some(Storage::Static),
+            ret: self.ret,
+            params,
+            frame_size: self.temp_pos,
+            regs: std::mem::take(&mut self.regs),
+            blocks: std::mem::take(&mut self.blocks),
+        };
+        self.module.funcs.push(func);
+    }
+
+    // ブロック追加
+    fn new_block(&mut self) -> usize {
+        self.blocks.push(Block { insts: vec![], term: None });
+        self.blocks.len() - 1
+    }
+
+    // 仮想レジスタ追加
+    fn new_reg(&mut self, ty: Ty) -> Reg {
+        self.regs.push(ty);
+        self.regs.len() - 1
+    }
+
+    // 命令追加
+    fn emit(&mut self, inst: Inst) {
+        self.blocks[self.cur].insts.push(inst);
+    }
+
+    // 現在のブロックを終端
+    fn terminate(&mut self, term: Term) {
+        self.blocks[self.cur].term = Some(term);
+    }
+
+    // 一時領域確保（rbpからの距離を返す）.
+    fn alloc_temp(&mut self, size: usize) -> usize {
+        self.temp_pos += align_to(size, 8);
+        self.temp_pos
+    }
+
+    // 基本ブロック生成.
+    //
+    // 文、式を順に生成し、後続ブロックへの分岐で終端する（return文で終端済みの場合を除く）
+    fn block(&mut self, b: &BasicBlock, name: &str) {
+        self.cur = b.id;
+        b.items.iter().for_each(|a| self.stmt(a));
+        if self.blocks[self.cur].term.is_some() {
+            return;
+        }
+        let term = match (b.cond, b.succs.as_slice()) {
+            (Some(c), &[t, f]) => Term::Branch(self.expr(c), t, f),
+            (_, &[s, ..]) => Term::Jump(s),
+            // mainの終端に到達した場合は0を返す
+            _ => Term::Ret(if name == "main" { Some(self.konst(0)) } else { None }),
+        };
+        self.terminate(term);
+    }
+
+    // 文の生成（制御構造は制御フローグラフで展開済み）.
+    fn stmt(&mut self, ast: &AstType) {
+        match *ast {
+            AstType::Return(ref a, _) => {
+                let v = match self.ret {
+                    RetKind::Struct(_) => Some(self.ptr_expr(a)),
+                    RetKind::Scalar => self.value(a),
+                    RetKind::Void => {
+                        self.effect(a);
+                        None
+                    }
+                };
+                self.terminate(Term::Ret(v));
+            }
+            AstType::ReturnVoid(_) => self.terminate(Term::Ret(None)),
+            AstType::StaticVar(ref a) => self.static_object(a),
+            // 宣言のみなので何もしない
+            AstType::Variable(_, _, _, _)
+            | AstType::Struct(_, _)
+            | AstType::StaticAssert(_, _, _)
+            | AstType::FuncDecl(_, _, _, _) => {}
+            _ => self.effect(ast),
+        }
+    }
+
+    // 値を捨てる式の生成.
+    fn effect(&mut self, ast: &AstType) {
+        self.value(ast);
+    }
+
+    // 式の生成（void関数の呼び出しは値なし）.
+    fn value(&mut self, ast: &AstType) -> Option<Reg> {
+        match *ast {
+            AstType::FuncCall(ref f, ref args, _) => self.call(ast, f, args),
+            _ => Some(self.expr(ast)),
+        }
+    }
+
+    // 定数生成.
+    fn konst(&mut self, v: i64) -> Reg {
+        let r = self.new_reg(Ty::I64);
+        self.emit(Inst::Const(r, v));
+        r
+    }
+
+    // 二項演算生成.
+    fn bin(&mut self, ty: Ty, op: BinOp, a: Reg, b: Reg) -> Reg {
+        let r = self.new_reg(ty);
+        self.emit(Inst::Bin(r, op, a, b));
+        r
+    }
+
+    // 型変換（I64とPtrの相互変換のみ必要な場合に挿入）.
+    fn coerce(&mut self, r: Reg, ty: Ty) -> Reg {
+        if self.regs[r] == ty {
+            return r;
+        }
+        self.conv(r, ty)
+    }
+    fn conv(&mut self, r: Reg, ty: Ty) -> Reg {
+        let d = self.new_reg(ty.value());
+        self.emit(Inst::Conv(d, ty, r));
+        d
+    }
+
+    // 整数値の式生成.
+    fn int_expr(&mut self, ast: &AstType) -> Reg {
+        let r = self.expr(ast);
+        self.coerce(r, Ty::I64)
+    }
+
+    // アドレス値の式生成.
+    fn ptr_expr(&mut self, ast: &AstType) -> Reg {
+        let r = self.expr(ast);
+        self.coerce(r, Ty::Ptr)
+    }
+
+    // 式の生成.
+    //
+    // 構造体、配列の値は先頭アドレスとする
+    fn expr(&mut self, ast: &AstType) -> Reg {
+        match *ast {
+            AstType::Factor(v) => self.konst(v),
+            AstType::SizeOf(v) => self.konst(v as i64),
+            AstType::StringLiteral(ref s, i) => {
+                self.add_string(s, i);
+                self.addr(Addr::Str(i))
+            }
+            AstType::Variable(_, _, ref name, _) => {
+                let a = self.lvalue_address(ast);
+                match *ast {
+                    // 関数指定子は関数のアドレス
+                    AstType::Variable(Type::Func(_), Structure::Identifier, _, _) => a,
+                    _ => {
+                        let sym = self.get_var_symbol(name);
+                        self.load(&Some((sym.t, sym.strt)), a, sym.qual.is_volatile)
+                    }
+                }
+            }
+            AstType::Member(_, _, _, _) => {
+                let a = self.member_address(ast);
+                self.load(&Self::access_type(ast), a, self.is_volatile(ast))
+            }
+            AstType::Indirect(ref a) => {
+                let p = self.ptr_expr(a);
+                self.load(&a.deref_type(), p, self.is_volatile(ast))
+            }
+            AstType::Address(ref a, _) => self.address(a),
+            AstType::Assign(ref a, ref b, _) | AstType::Init(ref a, ref b, _) => self.assign(a, b),
+            AstType::PlusAssign(ref a, ref b, _)
+            | AstType::MinusAssign(ref a, ref b, _)
+            | AstType::MultipleAssign(ref a, ref b, _)
+            | AstType::DivisionAssign(ref a, ref b, _)
+            | AstType::RemainderAssign(ref a, ref b, _)
+            | AstType::BitAndAssign(ref a, ref b, _)
+            | AstType::BitOrAssign(ref a, ref b, _)
+            | AstType::BitXorAssign(ref a, ref b, _)
+            | AstType::LeftShiftAssign(ref a, ref b, _)
+            | AstType::RightShiftAssign(ref a, ref b, _) => self.compound_assign(ast, a, b),
+            AstType::PreInc(ref a, _) => self.inc_dec(a, true, false),
+            AstType::PreDec(ref a, _) => self.inc_dec(a, false, false),
+            AstType::PostInc(ref a, _) => self.inc_dec(a, true, true),
+            AstType::PostDec(ref a, _) => self.inc_dec(a, false, true),
+            AstType::Plus(ref a, ref b) => self.plus(a, b),
+            AstType::Minus(ref a, ref b) => self.minus(a, b),
+            AstType::Multiple(ref a, ref b) => self.operator(BinOp::Mul, a, b),
+            AstType::Division(ref a, ref b) => self.operator(BinOp::Div, a, b),
+            AstType::Remainder(ref a, ref b) => self.operator(BinOp::Rem, a, b),
+            AstType::LeftShift(ref a, ref b) => self.operator(BinOp::Shl, a, b),
+            AstType::RightShift(ref a, ref b) => self.operator(BinOp::Sar, a, b),
+            AstType::BitAnd(ref a, ref b) => self.operator(BinOp::And, a, b),
+            AstType::BitOr(ref a, ref b) => self.operator(BinOp::Or, a, b),
+            AstType::BitXor(ref a, ref b) => self.operator(BinOp::Xor, a, b),
+            AstType::Equal(ref a, ref b) => self.compare(BinOp::Eq, a, b),
+            AstType::NotEqual(ref a, ref b) => self.compare(BinOp::Ne, a, b),
+            AstType::LessThan(ref a, ref b) => self.compare(BinOp::Lt, a, b),
+            AstType::GreaterThan(ref a, ref b) => self.compare(BinOp::Gt, a, b),
+            AstType::LessThanEqual(ref a, ref b) => self.compare(BinOp::Le, a, b),
+            AstType::GreaterThanEqual(ref a, ref b) => self.compare(BinOp::Ge, a, b),
+            AstType::LogicalAnd(_, _) | AstType::LogicalOr(_, _) => self.logical(ast),
+            AstType::Condition(ref a, ref b, ref c) => self.condition(ast, a, b, c),
+            // 左辺の値は捨て、右辺の値を結果とする
+            AstType::Comma(ref a, ref b) => {
+                self.effect(a);
+                self.expr(b)
+            }
+            AstType::UnPlus(ref a) => self.expr(a),
+            AstType::UnMinus(ref a) => self.unary(UnOp::Neg, a),
+            AstType::BitReverse(ref a) => self.unary(UnOp::Not, a),
+            AstType::Not(ref a) => {
+                let r = self.expr(a);
+                let zero = self.new_reg(self.regs[r]);
+                self.emit(Inst::Const(zero, 0));
+                self.bin(Ty::I64, BinOp::Eq, r, zero)
+            }
+            AstType::Cast(ref t, ref s, ref a, _) => self.cast(t, s, a),
+            AstType::FuncCall(ref f, ref args, _) => match self.call(ast, f, args) {
+                Some(r) => r,
+                None => self.konst(0),
+            },
+            _ => panic!("{} {}: not support expression {:?}", file!(), line!(), ast),
+        }
+    }
+
+    // アドレス生成.
+    fn addr(&mut self, a: Addr) -> Reg {
+        let r = self.new_reg(Ty::Ptr);
+        self.emit(Inst::Addr(r, a));
+        r
+    }
+
+    // 左辺値変数アドレス取得
+    fn lvalue_address(&mut self, a: &AstType) -> Reg {
+        let base = match *a {
+            AstType::Variable(Type::Func(_), Structure::Identifier, ref name, _) => Addr::Func(name.clone()),
+            AstType::Variable(_, _, ref name, _) => {
+                let sym = self.get_var_symbol(name);
+                match sym.scope {
+                    Scope::Global => Addr::Global(self.object_label(&sym)),
+                    _ if sym.storage == Storage::Static => Addr::Global(self.object_label(&sym)),
+                    _ => Addr::Local(sym.frame_offset()),
+                }
+            }
+            _ => panic!("{} {}: not support AstType {:?}", file!(), line!(), a),
+        };
+        self.addr(base)
+    }
+
+    // アドレス演算子.
+    fn address(&mut self, a: &AstType) -> Reg {
+        match *a {
+            AstType::Variable(_, _, _, _) => self.lvalue_address(a),
+            AstType::Member(_, _, _, _) => self.member_address(a),
+            // 間接参照のアドレスはポインタの値
+            AstType::Indirect(ref e) => self.ptr_expr(e),
+            _ => panic!("{} {}: not support AstType {:?}", file!(), line!(), a),
+        }
+    }
+
+    // 構造体サイズ取得.
+    fn struct_size(&self, t: &Type) -> usize {
+        match t {
+            Type::Struct(ref n) | Type::Union(ref n) => self.get_var_symbol(n).type_size(),
+            _ => panic!("{} {}: not struct type {:?}", file!(), line!(), t),
+        }
+    }
+
+    // 構造体の値のサイズ取得.
+    fn value_size(&self, a: &AstType) -> usize {
+        match a.value_type() {
+            Some((ref t, _)) => self.struct_size(t),
+            None => panic!("{} {}: cannot decide type {:?}", file!(), line!(), a),
+        }
+    }
+
+    // 構造体メンバー取得.
+    fn struct_member(&self, base: &AstType, name: &str) -> Symbol {
+        let def = match base.value_type() {
+            Some((Type::Struct(ref n) | Type::Union(ref n), Structure::Struct)) => self.get_var_symbol(n),
+            _ => panic!("{} {}: not struct {:?}", file!(), line!(), base),
+        };
+        def.members.into_iter()
+            .find(|m| m.var == name)
+            .unwrap_or_else(|| panic!("{} {}: not exists member {}", file!(), line!(), name))
+    }
+
+    // メンバーアドレス生成.
+    //
+    // 構造体の値は先頭アドレスなので、メンバーのオフセットを加算
+    fn member_address(&mut self, a: &AstType) -> Reg {
+        match *a {
+            AstType::Member(ref base, _, _, ref name) => {
+                let member = self.struct_member(base, name);
+                let p = self.ptr_expr(base);
+                if member.offset == 0 {
+                    return p;
+                }
+                let n = self.konst(member.offset as i64);
+                self.bin(Ty::Ptr, BinOp::Add, p, n)
+            }
+            _ => panic!("{} {}: not member {:?}", file!(), line!(), a),
+        }
+    }
+
+    // アクセスする型取得.
+    //
+    // ビットフィールドのメンバーは、読み書きでビット位置を考慮する為に構造を残す
+    fn access_type(a: &AstType) -> Option<(Type, Structure)> {
+        match *a {
+            AstType::Member(_, ref t, ref s @ Structure::BitField(_), _) => Some((t.clone(), s.clone())),
+            _ => a.value_type(),
+        }
+    }
+
+    // メモリアクセスの幅.
+    fn mem_ty(vt: &Option<(Type, Structure)>) -> Ty {
+        match *vt {
+            Some((_, Structure::Pointer)) | Some((Type::Func(_), _)) => Ty::Ptr,
+            Some((Type::Int, _)) => Ty::I32,
+            Some((Type::Char, _)) => Ty::I8,
+            _ => Ty::I64,
+        }
+    }
+
+    // volatileなオブジェクトへのアクセスか判定.
+    //
+    // 間接参照は基点となるポインタ、配列の参照先の修飾子、メンバーは構造体側の修飾子も考慮する
+    fn is_volatile(&self, a: &AstType) -> bool {
+        match *a {
+            AstType::Variable(_, _, ref n, _) => self.get_var_symbol(n).qual.is_volatile,
+            AstType::Member(ref base, _, _, ref n) => self.struct_member(base, n).qual.is_volatile || self.is_volatile(base),
+            AstType::Indirect(ref e) => match self.pointer_base(e) {
+                Some(ref sym) if sym.strt == Structure::Pointer => sym.ptr_qual.is_volatile,
+                Some(ref sym) => sym.qual.is_volatile,
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    // ポインタ演算の基点となるポインタ、配列取得.
+    fn pointer_base(&self, a: &AstType) -> Option<Symbol> {
+        match *a {
+            AstType::Variable(_, Structure::Pointer, ref n, _)
+            | AstType::Variable(_, Structure::Array(_), ref n, _) => Some(self.get_var_symbol(n)),
+            AstType::Member(ref base, _, Structure::Pointer, ref n)
+            | AstType::Member(ref base, _, Structure::Array(_), ref n) => Some(self.struct_member(base, n)),
+            AstType::Plus(ref a, ref b) => self.pointer_base(a).or_else(|| self.pointer_base(b)),
+            AstType::Minus(ref a, _)
+            | AstType::PreInc(ref a, _)
+            | AstType::PreDec(ref a, _)
+            | AstType::PostInc(ref a, _)
+            | AstType::PostDec(ref a, _) => self.pointer_base(a),
+            _ => None,
+        }
+    }
+
+    // 型に応じた読み込み.
+    //
+    // 構造体、配列は先頭アドレスを値とするので、読み込まない
+    fn load(&mut self, vt: &Option<(Type, Structure)>, a: Reg, volatile: bool) -> Reg {
+        match *vt {
+            Some((_, Structure::Struct)) | Some((_, Structure::Array(_))) => a,
+            // 格納単位を読み込み、ビットフィールドを取り出す
+            Some((ref t, Structure::BitField(ref b))) => {
+                let unit = self.load(&Some((t.clone(), Structure::Identifier)), a, volatile);
+                self.extract_bit_field(b, unit)
+            }
+            _ => {
+                let ty = Self::mem_ty(vt);
+                let r = self.new_reg(ty.value());
+                self.emit(Inst::Load(r, ty, a, volatile));
+                r
+            }
+        }
+    }
+
+    // 型に応じた幅で書き込み、代入式の値を返す.
+    fn store(&mut self, vt: &Option<(Type, Structure)>, v: Reg, a: Reg, volatile: bool) -> Reg {
+        match *vt {
+            Some((ref t, Structure::BitField(ref b))) => self.store_bit_field(t, b, v, a, volatile),
+            _ => {
+                let ty = Self::mem_ty(vt);
+                let v = self.coerce(v, ty.value());
+                self.emit(Inst::Store(ty, v, a, volatile));
+                v
+            }
+        }
+    }
+
+    // ビットフィールドの取り出し.
+    //
+    // 最上位ビットまで左シフトし、幅に応じて算術（符号なしは論理）右シフトする
+    fn extract_bit_field(&mut self, b: &BitField, r: Reg) -> Reg {
+        let left = self.konst((64 - b.pos - b.width) as i64);
+        let r = self.bin(Ty::I64, BinOp::Shl, r, left);
+        let right = self.konst((64 - b.width) as i64);
+        let op = if b.signed { BinOp::Sar } else { BinOp::Shr };
+        self.bin(Ty::I64, op, r, right)
+    }
+
+    // ビットフィールドへの書き込み.
+    //
+    // 格納単位を読み込んで該当ビットのみ置き換え（read-modify-write）、
+    // 代入式の値として、格納した値を取り出し直す
+    fn store_bit_field(&mut self, t: &Type, b: &BitField, v: Reg, a: Reg, volatile: bool) -> Reg {
+        let unit = Some((t.clone(), Structure::Identifier));
+        let mask = (1i64 << b.width) - 1;
+        let clear = !(mask << b.pos) as i32 as i64;
+
+        let v = self.coerce(v, Ty::I64);
+        let m = self.konst(mask as i32 as i64);
+        let v = self.bin(Ty::I64, BinOp::And, v, m);
+        let pos = self.konst(b.pos as i64);
+        let v = self.bin(Ty::I64, BinOp::Shl, v, pos);
+        let cur = self.load(&unit, a, volatile);
+        let c = self.konst(clear);
+        let cur = self.bin(Ty::I64, BinOp::And, cur, c);
+        let cur = self.bin(Ty::I64, BinOp::Or, cur, v);
+        self.store(&unit, cur, a, volatile);
+        self.extract_bit_field(b, v)
+    }
+
+    // 代入生成.
+    fn assign(&mut self, a: &AstType, b: &AstType) -> Reg {
+        match *a {
+            // 右辺の構造体を左辺の領域へコピーし、左辺のアドレスを値とする
+            _ if a.is_struct_value() => {
+                let size = self.value_size(a);
+                let dst = self.ptr_expr(a);
+                let src = self.ptr_expr(b);
+                self.emit(Inst::Memcpy(dst, src, size));
+                dst
+            }
+            AstType::Member(_, _, _, _) | AstType::Variable(_, _, _, _) | AstType::Indirect(_) => {
+                let vt = match *a {
+                    AstType::Indirect(ref p) => p.deref_type(),
+                    _ => Self::access_type(a),
+                };
+                let addr = self.address(a);
+                let v = self.expr(b);
+                self.store(&vt, v, addr, self.is_volatile(a))
+            }
+            _ => self.expr(b),
+        }
+    }
+
+    // 複合代入生成.
+    //
+    // 左辺のアドレスを一度だけ評価し、現在値と右辺の演算結果を左辺の型で格納する
+    fn compound_assign(&mut self, ast: &AstType, a: &AstType, b: &AstType) -> Reg {
+        let (vt, volatile) = (Self::access_type(a), self.is_volatile(a));
+        let addr = self.address(a);
+        let cur = self.load(&vt, addr, volatile);
+        let r = self.expr(b);
+        let v = match (ast, self.pointee_size(a)) {
+            // ポインタの加減算は、右辺を指す型のサイズ倍する
+            (AstType::PlusAssign(_, _, _), Some(n)) => {
+                let r = self.scale(b, r, n);
+                self.bin(Ty::Ptr, BinOp::Add, cur, r)
+            }
+            (AstType::MinusAssign(_, _, _), Some(n)) => {
+                let r = self.scale(b, r, n);
+                self.bin(Ty::Ptr, BinOp::Sub, cur, r)
+            }
+            _ => {
+                let op = match ast {
+                    AstType::PlusAssign(_, _, _) => BinOp::Add,
+                    AstType::MinusAssign(_, _, _) => BinOp::Sub,
+                    AstType::MultipleAssign(_, _, _) => BinOp::Mul,
+                    AstType::DivisionAssign(_, _, _) => BinOp::Div,
+                    AstType::RemainderAssign(_, _, _) => BinOp::Rem,
+                    AstType::BitAndAssign(_, _, _) => BinOp::And,
+                    AstType::BitOrAssign(_, _, _) => BinOp::Or,
+                    AstType::BitXorAssign(_, _, _) => BinOp::Xor,
+                    AstType::LeftShiftAssign(_, _, _) => BinOp::Shl,
+                    AstType::RightShiftAssign(_, _, _) => BinOp::Sar,
+                    _ => panic!("{} {}: not support AstType {:?}", file!(), line!(), ast),
+                };
+                let (cur, r) = (self.coerce(cur, Ty::I64), self.coerce(r, Ty::I64));
+                self.bin(Ty::I64, op, cur, r)
+            }
+        };
+        self.store(&vt, v, addr, volatile)
+    }
+
+    // インクリメント、デクリメント生成.
+    //
+    // ポインタは指す型のサイズ分増減し、後置の場合は更新前の値を返す
+    fn inc_dec(&mut self, a: &AstType, inc: bool, post: bool) -> Reg {
+        let (vt, volatile) = (Self::access_type(a), self.is_volatile(a));
+        let step = self.pointee_size(a).unwrap_or(1);
+        let addr = self.address(a);
+        let old = self.load(&vt, addr, volatile);
+        let n = self.konst(step as i64);
+        let op = if inc { BinOp::Add } else { BinOp::Sub };
+        let new = self.bin(self.regs[old], op, old, n);
+        let stored = self.store(&vt, new, addr, volatile);
+        if post { old } else { stored }
+    }
+
+    // ポインタの指す型のサイズ取得.
+    //
+    // ポインタ、配列以外はNoneを返す
+    fn pointee_size(&self, a: &AstType) -> Option<usize> {
+        match a.value_type() {
+            // 関数ポインタの加減算は1バイト単位（GNU拡張と同様）
+            Some((Type::Func(_), Structure::Pointer)) => Some(1),
+            Some((ref t, Structure::Pointer)) => Some(self.elem_size(t)),
+            // 多次元配列は一行分のサイズ
+            Some((ref t, Structure::Array(ref dims))) => {
+                Some(dims[1..].iter().fold(self.elem_size(t), |acc, d| acc * d))
+            }
+            _ => None,
+        }
+    }
+
+    // 要素の型のサイズ取得.
+    fn elem_size(&self, t: &Type) -> usize {
+        match t {
+            Type::Struct(_) | Type::Union(_) => self.struct_size(t),
+            Type::Array(ref t, ref dims) => dims.iter().fold(self.elem_size(t), |acc, d| acc * d),
+            Type::Char => 1,
+            Type::Short => 2,
+            Type::Int => 4,
+            // void*の加減算は1バイト単位（GNU拡張と同様）
+            Type::Void => 1,
+            _ => 8,
+        }
+    }
+
+    // 整数をポインタの指す型のサイズ倍する.
+    fn scale(&mut self, b: &AstType, r: Reg, n: usize) -> Reg {
+        let r = match b.value_type() {
+            Some((Type::Int, _)) | Some((Type::Char, _)) => self.conv(r, Ty::I32),
+            _ => self.coerce(r, Ty::I64),
+        };
+        match n {
+            1 => r,
+            _ => {
+                let n = self.konst(n as i64);
+                self.bin(Ty::I64, BinOp::Mul, r, n)
+            }
+        }
+    }
+
+    // 加算
+    //
+    // ポインタと整数の加算は、整数をポインタの指す型のサイズ倍する
+    fn plus(&mut self, a: &AstType, b: &AstType) -> Reg {
+        match (self.pointee_size(a), self.pointee_size(b)) {
+            (Some(_), Some(_)) => panic!("{} {}: cannot add pointers {:?} {:?}", file!(), line!(), a, b),
+            // 整数 + ポインタは入れ替えて評価
+            (None, Some(_)) => self.plus(b, a),
+            (Some(n), None) => {
+                let p = self.ptr_expr(a);
+                let r = self.expr(b);
+                let r = self.scale(b, r, n);
+                self.bin(Ty::Ptr, BinOp::Add, p, r)
+            }
+            (None, None) => self.operator(BinOp::Add, a, b),
+        }
+    }
+
+    // 減算
+    //
+    // ポインタ同士の減算は、差を要素のサイズで割った要素数とする
+    fn minus(&mut self, a: &AstType, b: &AstType) -> Reg {
+        match (self.pointee_size(a), self.pointee_size(b)) {
+            (Some(n), Some(_)) => {
+                let l = self.ptr_expr(a);
+                let r = self.ptr_expr(b);
+                let d = self.bin(Ty::I64, BinOp::Sub, l, r);
+                let n = self.konst(n as i64);
+                self.bin(Ty::I64, BinOp::Div, d, n)
+            }
+            (Some(n), None) => {
+                let p = self.ptr_expr(a);
+                let r = self.expr(b);
+                let r = self.scale(b, r, n);
+                self.bin(Ty::Ptr, BinOp::Sub, p, r)
+            }
+            (None, Some(_)) => panic!("{} {}: cannot subtract pointer from integer {:?}", file!(), line!(), b),
+            (None, None) => self.operator(BinOp::Sub, a, b),
+        }
+    }
+
+    // 整数の二項演算生成.
+    fn operator(&mut self, op: BinOp, a: &AstType, b: &AstType) -> Reg {
+        let l = self.int_expr(a);
+        let r = self.int_expr(b);
+        self.bin(Ty::I64, op, l, r)
+    }
+
+    // 比較演算生成.
+    //
+    // どちらかがポインタであれば、アドレスとして比較する
+    fn compare(&mut self, op: BinOp, a: &AstType, b: &AstType) -> Reg {
+        let l = self.expr(a);
+        let r = self.expr(b);
+        let ty = if self.regs[l] == Ty::Ptr || self.regs[r] == Ty::Ptr { Ty::Ptr } else { Ty::I64 };
+        let (l, r) = (self.coerce(l, ty), self.coerce(r, ty));
+        self.bin(Ty::I64, op, l, r)
+    }
+
+    // 単項演算生成.
+    fn unary(&mut self, op: UnOp, a: &AstType) -> Reg {
+        let r = self.int_expr(a);
+        let d = self.new_reg(Ty::I64);
+        self.emit(Inst::Un(d, op, r));
+        d
+    }
+
+    // キャスト生成.
+    //
+    // int、charへの変換は変換先の幅で切り詰めて符号拡張する
+    fn cast(&mut self, t: &Type, s: &Structure, a: &AstType) -> Reg {
+        let r = self.expr(a);
+        match (t, s) {
+            (Type::Int, Structure::Identifier) => self.conv(r, Ty::I32),
+            (Type::Char, Structure::Identifier) => self.conv(r, Ty::I8),
+            (Type::Void, Structure::Identifier) => r,
+            (_, Structure::Pointer) => self.coerce(r, Ty::Ptr),
+            _ => self.coerce(r, Ty::I64),
+        }
+    }
+
+    // 値の仮想レジスタの型.
+    fn value_ty(vt: &Option<(Type, Structure)>) -> Ty {
+        match *vt {
+            Some((_, Structure::Pointer))
+            | Some((_, Structure::Array(_)))
+            | Some((_, Structure::Struct))
+            | Some((Type::Func(_), _)) => Ty::Ptr,
+            _ => Ty::I64,
+        }
+    }
+
+    // 分岐の結果を一時領域で受け渡す.
+    fn merge_slot(&mut self) -> Reg {
+        let pos = self.alloc_temp(8);
+        self.addr(Addr::Local(pos))
+    }
+
+    // &&、||の値生成.
+    fn logical(&mut self, ast: &AstType) -> Reg {
+        let slot = self.merge_slot();
+        let (t, f, join) = (self.new_block(), self.new_block(), self.new_block());
+        cfg::branch(self, self.cur, ast, t, f);
+        [(t, 1), (f, 0)].iter().for_each(|&(b, v)| {
+            self.cur = b;
+            let r = self.konst(v);
+            self.emit(Inst::Store(Ty::I64, r, slot, false));
+            self.terminate(Term::Jump(join));
+        });
+        self.cur = join;
+        self.load(&None, slot, false)
+    }
+
+    // 三項演算子生成.
+    fn condition(&mut self, ast: &AstType, a: &AstType, b: &AstType, c: &AstType) -> Reg {
+        let ty = Self::value_ty(&ast.value_type());
+        let slot = self.merge_slot();
+        let (t, f, join) = (self.new_block(), self.new_block(), self.new_block());
+        cfg::branch(self, self.cur, a, t, f);
+        [(t, b), (f, c)].iter().for_each(|&(blk, e)| {
+            self.cur = blk;
+            let r = match self.value(e) {
+                Some(r) => r,
+                None => self.konst(0),
+            };
+            let r = self.coerce(r, ty);
+            self.emit(Inst::Store(ty, r, slot, false));
+            self.terminate(Term::Jump(join));
+        });
+        self.cur = join;
+        let r = self.new_reg(ty);
+        self.emit(Inst::Load(r, ty, slot, false));
+        r
+    }
+
+    // 関数ポインタ評価
+    //
+    // 関数ポインタへの間接参照は関数指定子となり、同じアドレスを指すので取り除く
+    fn func_pointer(&mut self, a: &AstType) -> Reg {
+        match *a {
+            AstType::Indirect(ref e) if e.is_func_pointer() => self.func_pointer(e),
+            _ => self.ptr_expr(a),
+        }
+    }
+
+    // 関数コール生成.
+    //
+    // 引数は逆順で評価し、構造体は先頭アドレスを渡す
+    fn call(&mut self, call: &AstType, f: &AstType, args: &AstType) -> Option<Reg> {
+        // 関数名であれば直接呼び出し、それ以外は関数ポインタ経由で呼び出し
+        let callee = match *f {
+            AstType::Variable(_, _, ref n, _) if self.sym_table.search(&Scope::Func, n).is_some() => Callee::Direct(n.clone()),
+            _ if f.is_func_pointer() => Callee::Indirect(self.func_pointer(f)),
+            _ => panic!("{} {}: not exists function name", file!(), line!()),
+        };
+        let args = match *args {
+            AstType::Argment(ref v) => v,
+            _ => panic!("{} {}: not function argment", file!(), line!()),
+        };
+        let mut regs: Vec<Arg> = args.iter().rev().map(|d| match d.is_struct_value() {
+            true => Arg { reg: self.ptr_expr(d), size: Some(self.value_size(d)) },
+            false => Arg { reg: self.expr(d), size: None },
+        }).collect();
+        regs.reverse();
+
+        // 構造体の戻り値は一時領域へ格納し、そのアドレスを値とする
+        let sret = match call.is_struct_value() {
+            true => {
+                let size = self.value_size(call);
+                let pos = self.alloc_temp(size);
+                Some((self.addr(Addr::Local(pos)), size))
+            }
+            false => None,
+        };
+        let dst = match call.value_type() {
+            _ if sret.is_some() => None,
+            Some((Type::Void, Structure::Identifier)) => None,
+            vt => Some(self.new_reg(Self::value_ty(&vt))),
+        };
+        self.emit(Inst::Call { dst, callee, args: regs, sret });
+        sret.map(|s| s.0).or(dst)
+    }
+}
+
+impl<'a, 't> cfg::Branch<'t> for Lower<'a> {
+    fn new_block(&mut self) -> usize {
+        Lower::new_block(self)
+    }
+
+    fn leaf(&mut self, b: usize, c: &'t AstType, t: usize, f: usize) {
+        self.cur = b;
+        let r = self.expr(c);
+        self.terminate(Term::Branch(r, t, f));
+    }
+}
+
+// volatileなメモリアクセスの表示
+fn volatile(v: bool) -> &'static str {
+    if v { "volatile " } else { "" }
+}
+
+impl fmt::Display for Inst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Inst::Const(_, v) => write!(f, "const {}", v),
+            Inst::Addr(_, ref a) => write!(f, "addr {}", a),
+            Inst::Load(_, ty, a, v) => write!(f, "load {}{} %{}", volatile(v), ty, a),
+            Inst::Store(ty, r, a, v) => write!(f, "store {}{} %{}, %{}", volatile(v), ty, r, a),
+            Inst::Bin(_, op, a, b) => write!(f, "{} %{}, %{}", op, a, b),
+            Inst::Un(_, op, a) => write!(f, "{} %{}", format!("{:?}", op).to_lowercase(), a),
+            Inst::Conv(_, ty, a) => write!(f, "conv {} %{}", ty, a),
+            Inst::Call { ref callee, ref args, sret, .. } => {
+                let callee = match *callee {
+                    Callee::Direct(ref n) => format!("@{}", n),
+                    Callee::Indirect(r) => format!("%{}", r),
+                };
+                let args: Vec<String> = args.iter().map(|a| match a.size {
+                    Some(n) => format!("%{} struct {}", a.reg, n),
+                    None => format!("%{}", a.reg),
+                }).collect();
+                write!(f, "call {}({})", callee, args.join(", "))?;
+                match sret {
+                    Some((r, n)) => write!(f, " sret %{} struct {}", r, n),
+                    None => Ok(()),
+                }
+            }
+            Inst::Memcpy(d, s, n) => write!(f, "memcpy %{}, %{}, {}", d, s, n),
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Term::Jump(b) => write!(f, "jmp bb{}", b),
+            Term::Branch(r, t, e) => write!(f, "br %{}, bb{}, bb{}", r, t, e),
+            Term::Ret(Some(r)) => write!(f, "ret %{}", r),
+            Term::Ret(None) => write!(f, "ret"),
+        }
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ret = match self.ret {
+            RetKind::Void => "void".to_string(),
+            RetKind::Scalar => "scalar".to_string(),
+            RetKind::Struct(n) => format!("struct {}", n),
+        };
+        let params: Vec<String> = self.params.iter().map(|p| match p.size {
+            Some(n) => format!("local {} struct {}", p.offset, n),
+            None => format!("local {}", p.offset),
+        }).collect();
+        writeln!(
+            f,
+            "func @{}({}) -> {}, frame {}{}",
+            self.name,
+            params.join(", "),
+            ret,
+            self.frame_size,
+            if self.export { ", global" } else { "" }
+        )?;
+        self.blocks.iter().enumerate().try_for_each(|(i, b)| {
+            writeln!(f, "bb{}:", i)?;
+            b.insts.iter().try_for_each(|inst| match inst.def() {
+                Some(d) => writeln!(f, "  %{}:{} = {}", d, self.regs[d], inst),
+                None => writeln!(f, "  {}", inst),
+            })?;
+            match b.term {
+                Some(ref t) => writeln!(f, "  {}", t),
+                None => writeln!(f, "  <no terminator>"),
+            }
+        })
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.strings.iter().try_for_each(|(i, s)| writeln!(f, "string .LC{} \"{}\"", i, s))?;
+        self.objects.iter().try_for_each(|o| {
+            let section = match o.init {
+                _ if o.readonly => "rodata",
+                Some(_) => "data",
+                None => "bss",
+            };
+            writeln!(
+                f,
+                "object @{}: size {}, align {}, {}{}",
+                o.label,
+                o.size,
+                o.align,
+                section,
+                if o.export { ", global" } else { "" }
+            )?;
+            o.init.iter().flatten().try_for_each(|i| {
+                let value = match i.value {
+                    InitValue::Int(v) => v.to_string(),
+                    InitValue::Addr(ref a, 0) => a.to_string(),
+                    InitValue::Addr(ref a, n) => format!("{}+{}", a, n),
+                };
+                writeln!(f, "  +{}: {} bytes {}", i.offset, i.size, value)
+            })
+        })?;
+        self.funcs.iter().try_for_each(|func| write!(f, "{}", func))
+    }
+}
+
+// 中間表現の検査.
+//
+// ブロックの終端、分岐先、仮想レジスタの定義と使用、型の整合性を確認する
+pub fn verify(m: &Module) -> Result<(), Vec<String>> {
+    let errs: Vec<String> = m.funcs.iter().flat_map(verify_function).collect();
+    if errs.is_empty() {
+        Ok(())
+    } else {
+        Err(errs)
+    }
+}
+
+// 関数の検査.
+fn verify_function(func: &Function) -> Vec<String> {
+    let mut errs = vec![];
+    let nregs = func.regs.len();
+    let nblocks = func.blocks.len();
+    let mut err = |b: usize, msg: String| errs.push(format!("{}: bb{}: {}", func.name, b, msg));
+
+    if nblocks == 0 {
+        err(0, "function has no blocks".to_string());
+    }
+    func.regs.iter().enumerate().filter(|(_, t)| !matches!(t, Ty::I64 | Ty::Ptr)).for_each(|(r, t)| {
+        err(0, format!("register %{} has non-register type {}", r, t))
+    });
+
+    // 仮想レジスタは一度だけ定義する
+    let mut defined = vec![false; nregs];
+    func.blocks.iter().enumerate().for_each(|(b, block)| {
+        block.insts.iter().filter_map(|i| i.def()).for_each(|d| match defined.get(d) {
+            None => err(b, format!("register %{} is not declared", d)),
+            Some(true) => err(b, format!("register %{} is defined more than once", d)),
+            Some(false) => defined[d] = true,
+        });
+        match block.term {
+            None => err(b, "block has no terminator".to_string()),
+            Some(ref t) => t.succs().iter().filter(|s| **s >= nblocks).for_each(|s| {
+                err(b, format!("branch to unknown block bb{}", s))
+            }),
+        }
+    });
+
+    // 型の検査
+    let ty = |r: Reg| func.regs.get(r).cloned();
+    func.blocks.iter().enumerate().for_each(|(b, block)| {
+        block.insts.iter().for_each(|inst| {
+            let ok = match *inst {
+                Inst::Const(_, _) => true,
+                Inst::Addr(d, _) => ty(d) == Some(Ty::Ptr),
+                Inst::Load(d, t, a, _) => ty(d) == Some(t.value()) && ty(a) == Some(Ty::Ptr),
+                Inst::Store(t, v, a, _) => ty(v) == Some(t.value()) && ty(a) == Some(Ty::Ptr),
+                Inst::Bin(d, op, a, c) => match (op, ty(d), ty(a), ty(c)) {
+                    (BinOp::Add, Some(Ty::Ptr), Some(Ty::Ptr), Some(Ty::I64))
+                    | (BinOp::Sub, Some(Ty::Ptr), Some(Ty::Ptr), Some(Ty::I64))
+                    | (BinOp::Sub, Some(Ty::I64), Some(Ty::Ptr), Some(Ty::Ptr)) => true,
+                    (op, Some(Ty::I64), Some(l), Some(r)) if op.is_compare() => l == r,
+                    (_, d, l, r) => d == Some(Ty::I64) && l == d && r == d,
+                },
+                Inst::Un(d, _, a) => ty(d) == Some(Ty::I64) && ty(a) == Some(Ty::I64),
+                Inst::Conv(d, t, a) => ty(d) == Some(t.value()) && ty(a).is_some(),
+                Inst::Call { ref callee, ref args, sret, .. } => {
+                    let f = match *callee {
+                        Callee::Indirect(r) => ty(r) == Some(Ty::Ptr),
+                        Callee::Direct(_) => true,
+                    };
+                    f && args.iter().all(|a| a.size.is_none() || ty(a.reg) == Some(Ty::Ptr))
+                        && sret.iter().all(|s| ty(s.0) == Some(Ty::Ptr))
+                }
+                Inst::Memcpy(d, s, _) => ty(d) == Some(Ty::Ptr) && ty(s) == Some(Ty::Ptr),
+            };
+            if !ok {
+                err(b, format!("type mismatch in '{}'", inst));
+            }
+        });
+        let ok = match (&block.term, func.ret) {
+            (Some(Term::Ret(Some(_))), RetKind::Void) => false,
+            (Some(Term::Ret(Some(r))), RetKind::Struct(_)) => ty(*r) == Some(Ty::Ptr),
+            _ => true,
+        };
+        if !ok {
+            err(b, "return value does not match the function".to_string());
+        }
+    });
+
+    // 全ての経路で定義済みの仮想レジスタのみ使用できる（入口以外の初期値は全て定義済み）
+    let mut preds = vec![vec![]; nblocks];
+    func.blocks.iter().enumerate().for_each(|(b, block)| {
+        block.term.iter().flat_map(|t| t.succs()).filter(|s| *s < nblocks).for_each(|s| preds[s].push(b))
+    });
+    let defs = |b: usize, mut v: Vec<bool>| {
+        func.blocks[b].insts.iter().filter_map(|i| i.def()).filter(|d| *d < nregs).for_each(|d| v[d] = true);
+        v
+    };
+    let mut outs: Vec<Vec<bool>> = (0..nblocks).map(|b| match b {
+        0 => defs(0, vec![false; nregs]),
+        _ => vec![true; nregs],
+    }).collect();
+    let entry_in = |b: usize, outs: &[Vec<bool>]| match b {
+        0 => vec![false; nregs],
+        _ => preds[b].iter().fold(vec![true; nregs], |acc, p| {
+            acc.iter().zip(outs[*p].iter()).map(|(x, y)| *x && *y).collect()
+        }),
+    };
+    let mut changed = true;
+    while changed {
+        changed = false;
+        (1..nblocks).for_each(|b| {
+            let out = defs(b, entry_in(b, &outs));
+            if out != outs[b] {
+                outs[b] = out;
+                changed = true;
+            }
+        });
+    }
+    (0..nblocks).for_each(|b| {
+        let mut avail = entry_in(b, &outs);
+        let block = &func.blocks[b];
+        block.insts.iter().for_each(|inst| {
+            inst.uses().iter().filter(|u| !avail.get(**u).cloned().unwrap_or(false)).for_each(|u| {
+                err(b, format!("register %{} is used before definition", u))
+            });
+            if let Some(d) = inst.def().filter(|d| *d < nregs) {
+                avail[d] = true;
+            }
+        });
+        block.term.iter().flat_map(|t| t.uses()).filter(|u| !avail.get(*u).cloned().unwrap_or(false)).for_each(|u| {
+            err(b, format!("register %{} is used before definition", u))
+        });
+    });
+    errs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ast::AstGen;
+    use lexer::LexicalAnalysis;
+
+    // ソースから中間表現を作成
+    fn build(src: &str) -> Module {
+        let mut lexer = LexicalAnalysis::new("test.c".to_string(), src);
+        lexer.read_token();
+        let mut ast = AstGen::new(lexer.get_tokens());
+        let tree = ast.parse();
+        Module::build(&tree, ast.get_symbol())
+    }
+
+    // 関数の命令を全て取得
+    fn insts(f: &Function) -> Vec<&Inst> {
+        f.blocks.iter().flat_map(|b| b.insts.iter()).collect()
+    }
+
+    fn func(regs: Vec<Ty>, blocks: Vec<Block>) -> Function {
+        Function {
+            name: "f".to_string(),
+            export: true,
+            ret: RetKind::Scalar,
+            params: vec![],
+            frame_size: 8,
+            regs,
+            blocks,
+        }
+    }
+
+    fn block(insts: Vec<Inst>, term: Option<Term>) -> Block {
+        Block { insts, term }
+    }
+
+    #[test]
+    fn test_verify_lowered() {
+        let data = [
+            "int main() { int i; int s = 0; for (i = 0; i < 10; i++) { if (i == 3) { continue; } if (i > 7 || s > 20) { break; } s += i; } return s; }",
+            "int main() { int i = 0; do { i++; } while (i < 3 && i != 2); return i ? i : -1; }",
+            "struct P { int x; char c; }; struct P f(int v) { struct P p; p.x = v; p.c = 1; return p; } int main() { struct P a; a = f(2); return a.x + a.c; }",
+            "struct B { int a; int b; int c; int d; int e; }; struct B f() { struct B b; b.e = 5; return b; } int g(struct B b) { return b.e; } int main() { return g(f()); }",
+            "struct S { int a:3; unsigned b:4; }; int main() { struct S s; s.a = -1; s.b = 9; s.b += 1; return s.a + s.b; }",
+            "int add(int a, int b) { return a + b; } int main() { int (*fp)(int, int) = add; int (*t[1])(int, int); t[0] = fp; return (*fp)(1, 2) + t[0](3, 4); }",
+            "int a[3] = {1, 2, 3}; char *s = \"ab\"; int *p = &a[1]; int main() { static int n = 1; char *q = s; q++; return *p + n + (q - s) + (char)300 + !q; }",
+            "void f(int *p) { *p = 3; return; } int main() { int x; f(&x); return x << 2 >> 1; }",
+        ];
+        data.iter().for_each(|src| assert_eq!(Ok(()), verify(&build(src)), "\tFail Test: inst: {}", src));
+    }
+
+    #[test]
+    fn test_lower() {
+        let m = build("char *s = \"ab\"; int main() { int a[2]; char *p = \"ab\"; a[1] = 2; return a[1] + *p + (p == 0); }");
+
+        // 文字列リテラル、静的領域
+        assert_eq!(vec![(0, "ab".to_string()), (1, "ab".to_string())], m.strings);
+        assert_eq!(
+            Some(vec![Init { offset: 0, size: 8, value: InitValue::Addr(Addr::Str(0), 0) }]),
+            m.objects[0].init
+        );
+
+        // 配列の添字はint幅で符号拡張してから要素サイズ倍し、ポインタとの比較はアドレスとして比較する
+        let main = &m.funcs[0];
+        assert!(insts(main).iter().any(|i| matches!(i, Inst::Conv(_, Ty::I32, _))));
+        assert!(insts(main).iter().any(|i| matches!(i, Inst::Load(_, Ty::I8, _, false))));
+        assert!(insts(main).iter().any(|i| match **i {
+            Inst::Bin(_, BinOp::Eq, a, b) => main.regs[a] == Ty::Ptr && main.regs[b] == Ty::Ptr,
+            _ => false,
+        }));
+
+        // mainの終端（制御フローグラフの出口）は0を返す
+        let exit = &main.blocks[cfg::EXIT];
+        assert!(matches!(exit.insts.as_slice(), [Inst::Const(_, 0)]));
+        assert!(matches!(exit.term, Some(Term::Ret(Some(_)))));
+
+        // 構造体の代入はコピー、戻り値は一時領域経由
+        let m = build("struct P { int x; int y; }; struct P f(); int main() { struct P a; struct P b; a = b; b = f(); return 0; }");
+        let main = &m.funcs[0];
+        assert_eq!(2, insts(main).iter().filter(|i| matches!(i, Inst::Memcpy(_, _, 8))).count());
+        assert!(insts(main).iter().any(|i| matches!(i, Inst::Call { dst: None, sret: Some((_, 8)), .. })));
+        assert_eq!(24, main.frame_size);
+    }
+
+    #[test]
+    fn test_volatile() {
+        let m = build("struct S { volatile int a; int b; }; volatile int g; int main() { int *volatile p = 0; volatile char *q = 0; struct S s; g++; s.b = 2; return *q + s.a + *p; }");
+        let main = &m.funcs[0];
+        let dump: Vec<String> = insts(main).iter().map(|i| i.to_string()).collect();
+
+        // volatile変数、参照先、メンバーへのアクセスのみvolatile
+        assert_eq!(
+            vec![
+                "store volatile ptr %2, %0",
+                "store ptr %5, %3",
+                "load volatile i32 %6",
+                "store volatile i32 %9, %6",
+                "store i32 %13, %12",
+                "load ptr %14",
+                "load volatile i8 %15",
+                "load volatile i32 %17",
+                "load volatile ptr %20",
+                "load i32 %21",
+            ],
+            dump.iter().filter(|d| d.starts_with("load") || d.starts_with("store")).map(|d| d.as_str()).collect::<Vec<_>>()
+        );
+
+        // 宣言子リストの後続の変数もvolatile
+        let m = build("int main() { volatile int a = 1, b = 2; return 0; }");
+        assert_eq!(2, insts(&m.funcs[0]).iter().filter(|i| matches!(i, Inst::Store(_, _, _, true))).count());
+    }
+
+    #[test]
+    fn test_verify() {
+        // 正常
+        let ok = func(
+            vec![Ty::I64, Ty::Ptr],
+            vec![
+                block(vec![Inst::Const(0, 1), Inst::Addr(1, Addr::Local(8))], Some(Term::Branch(0, 1, 1))),
+                block(vec![Inst::Store(Ty::I32, 0, 1, false)], Some(Term::Ret(Some(0)))),
+            ],
+        );
+        assert!(verify_function(&ok).is_empty());
+
+        // 終端命令なし、不明なブロックへの分岐
+        let f = func(
+            vec![Ty::I64],
+            vec![block(vec![Inst::Const(0, 1)], Some(Term::Branch(0, 1, 2))), block(vec![], None)],
+        );
+        assert_eq!(
+            vec!["f: bb0: branch to unknown block bb2".to_string(), "f: bb1: block has no terminator".to_string()],
+            verify_function(&f)
+        );
+
+        // 二重定義、型の不一致
+        let f = func(
+            vec![Ty::I64, Ty::I64],
+            vec![block(vec![Inst::Const(0, 1), Inst::Const(0, 2), Inst::Load(1, Ty::I32, 0, false)], Some(Term::Ret(Some(1))))],
+        );
+        assert_eq!(
+            vec![
+                "f: bb0: register %0 is defined more than once".to_string(),
+                "f: bb0: type mismatch in 'load i32 %0'".to_string(),
+            ],
+            verify_function(&f)
+        );
+
+        // 一方の経路でのみ定義した値の使用
+        let f = func(
+            vec![Ty::I64, Ty::I64],
+            vec![
+                block(vec![Inst::Const(0, 1)], Some(Term::Branch(0, 1, 2))),
+                block(vec![Inst::Const(1, 2)], Some(Term::Jump(2))),
+                block(vec![], Some(Term::Ret(Some(1)))),
+            ],
+        );
+        assert_eq!(vec!["f: bb2: register %1 is used before definition".to_string()], verify_function(&f));
+
+        // void関数の戻り値
+        let mut f = func(vec![Ty::I64], vec![block(vec![Inst::Const(0, 1)], Some(Term::Ret(Some(0))))]);
+        f.ret = RetKind::Void;
+        assert_eq!(vec!["f: bb0: return value does not match the function".to_string()], verify_function(&f));
+    }
+}
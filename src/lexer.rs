@@ -12,7 +12,7 @@ pub struct LexicalAnalysis<'a> {
 
 impl<'a> LexicalAnalysis<'a> {
     // コンストラクタ.
-    pub fn new(n: String, i: &'a str) -> LexicalAnalysis {
+    pub fn new(n: String, i: &'a str) -> LexicalAnalysis<'a> {
         LexicalAnalysis {
             name: n,
             input: i,
@@ -44,12 +44,24 @@ impl<'a> LexicalAnalysis<'a> {
                     s if s.is_alphabetic() || s == '_' => {
                         if let Some(t) = self.generate_type(s) {
                             t
+                        } else if let Some(t) = self.generate_sign(s) {
+                            t
                         } else if let Some(t) = self.generate_statement(s) {
                             t
                         } else if let Some(t) = self.generate_sizeof(s) {
                             t
                         } else if let Some(t) = self.generate_struct(s) {
                             t
+                        } else if let Some(t) = self.generate_union(s) {
+                            t
+                        } else if let Some(t) = self.generate_enum(s) {
+                            t
+                        } else if let Some(t) = self.generate_static_assert(s) {
+                            t
+                        } else if let Some(t) = self.generate_storage(s) {
+                            t
+                        } else if let Some(t) = self.generate_qualifier(s) {
+                            t
                         } else {
                             self.generate_variable_token(s)
                         }
@@ -78,6 +90,10 @@ impl<'a> LexicalAnalysis<'a> {
                                 self.create_token(Token::GreaterThanEqual, ">=".to_string());
                             self.skip(1);
                             t
+                        } else if self.is_right_shift_assign(v) {
+                            let t = self.create_token(Token::RightShiftAssign, ">>=".to_string());
+                            self.skip(2);
+                            t
                         } else if self.is_right_shift(v) {
                             let t = self.create_token(Token::RightShift, ">>".to_string());
                             self.skip(1);
@@ -91,6 +107,10 @@ impl<'a> LexicalAnalysis<'a> {
                             let t = self.create_token(Token::LessThanEqual, "<=".to_string());
                             self.skip(1);
                             t
+                        } else if self.is_left_shift_assign(v) {
+                            let t = self.create_token(Token::LeftShiftAssign, "<<=".to_string());
+                            self.skip(2);
+                            t
                         } else if self.is_left_shift(v) {
                             let t = self.create_token(Token::LeftShift, "<<".to_string());
                             self.skip(1);
@@ -103,6 +123,10 @@ impl<'a> LexicalAnalysis<'a> {
                         if self.is_logical_and(v) {
                             self.skip(1);
                             self.create_token(Token::LogicalAnd, "&&".to_string())
+                        } else if self.is_bit_and_assign(v) {
+                            let token = self.create_token(Token::BitAndAssign, "&=".to_string());
+                            self.skip(1);
+                            token
                         } else {
                             self.create_token(Token::And, v.to_string())
                         }
@@ -111,6 +135,10 @@ impl<'a> LexicalAnalysis<'a> {
                         if self.is_logical_or(v) {
                             self.skip(1);
                             self.create_token(Token::LogicalOr, "||".to_string())
+                        } else if self.is_bit_or_assign(v) {
+                            let token = self.create_token(Token::BitOrAssign, "|=".to_string());
+                            self.skip(1);
+                            token
                         } else {
                             self.create_token(Token::BitOr, v.to_string())
                         }
@@ -137,6 +165,10 @@ impl<'a> LexicalAnalysis<'a> {
                             let token = self.create_token(Token::MinusAssign, "-=".to_string());
                             self.skip(1);
                             token
+                        } else if self.is_arrow(v) {
+                            let token = self.create_token(Token::Arrow, "->".to_string());
+                            self.skip(1);
+                            token
                         } else {
                             self.create_token(Token::Minus, v.to_string())
                         }
@@ -169,7 +201,15 @@ impl<'a> LexicalAnalysis<'a> {
                         }
                     }
                     '"' => self.generate_string(),
-                    '^' => self.create_token(Token::BitXor, v.to_string()),
+                    '^' => {
+                        if self.is_bit_xor_assign(v) {
+                            let token = self.create_token(Token::BitXorAssign, "^=".to_string());
+                            self.skip(1);
+                            token
+                        } else {
+                            self.create_token(Token::BitXor, v.to_string())
+                        }
+                    }
                     '~' => self.create_token(Token::BitReverse, v.to_string()),
                     '(' => self.create_token(Token::LeftParen, v.to_string()),
                     ')' => self.create_token(Token::RightParen, v.to_string()),
@@ -181,6 +221,7 @@ impl<'a> LexicalAnalysis<'a> {
                     ':' => self.create_token(Token::Colon, v.to_string()),
                     ';' => self.create_token(Token::SemiColon, v.to_string()),
                     ',' => self.create_token(Token::Comma, v.to_string()),
+                    '.' => self.create_token(Token::Dot, v.to_string()),
                     '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
                         self.generate_number_token(v)
                     }
@@ -238,12 +279,12 @@ impl<'a> LexicalAnalysis<'a> {
     fn skip_ascii_whitespace(&mut self) {
         while !self.is_eof() && self.read().is_ascii_whitespace() {
             let next = self.read_string(1);
+            self.skip(1);
             if self.is_linefeed(&next) {
                 // 行とカラムを更新
                 self.row += 1;
                 self.col = 0;
             }
-            self.skip(1);
         }
     }
 
@@ -287,7 +328,7 @@ impl<'a> LexicalAnalysis<'a> {
 
     // 変数候補チェック.
     fn is_variable(&self, c: char) -> bool {
-        c.is_alphabetic() || c == '_' || c.is_digit(10)
+        c.is_alphabetic() || c == '_' || c.is_ascii_digit()
     }
 
     // 文字列トークン生成
@@ -313,7 +354,7 @@ impl<'a> LexicalAnalysis<'a> {
         let mut s = String::new();
         s.push(v);
 
-        while !self.is_eof() && self.read().is_digit(10) {
+        while !self.is_eof() && self.read().is_ascii_digit() {
             let n = self.next();
             s.push(n.expect("lexer.rs(generate_number_token): cannot read next char"));
         }
@@ -394,6 +435,11 @@ impl<'a> LexicalAnalysis<'a> {
         v == '-' && self.read() == '='
     }
 
+    // ->演算子
+    fn is_arrow(&self, v: char) -> bool {
+        v == '-' && self.read() == '>'
+    }
+
     // multiple assign演算子
     fn is_multiple_assign(&self, v: char) -> bool {
         v == '*' && self.read() == '='
@@ -409,6 +455,31 @@ impl<'a> LexicalAnalysis<'a> {
         v == '%' && self.read() == '='
     }
 
+    // bit and assign演算子
+    fn is_bit_and_assign(&self, v: char) -> bool {
+        v == '&' && self.read() == '='
+    }
+
+    // bit or assign演算子
+    fn is_bit_or_assign(&self, v: char) -> bool {
+        v == '|' && self.read() == '='
+    }
+
+    // bit xor assign演算子
+    fn is_bit_xor_assign(&self, v: char) -> bool {
+        v == '^' && self.read() == '='
+    }
+
+    // left shift assign演算子
+    fn is_left_shift_assign(&mut self, v: char) -> bool {
+        v == '<' && self.read_string(2) == "<="
+    }
+
+    // right shift assign演算子
+    fn is_right_shift_assign(&mut self, v: char) -> bool {
+        v == '>' && self.read_string(2) == ">="
+    }
+
     // type int作成
     fn generate_type_int(&mut self) -> TokenInfo {
         let col = self.col;
@@ -449,17 +520,74 @@ impl<'a> LexicalAnalysis<'a> {
         }
     }
 
+    // type void作成
+    fn generate_type_void(&mut self) -> TokenInfo {
+        let col = self.col;
+        self.skip(3);
+
+        // ポインタ型であるかチェック.
+        if self.is_pointer() {
+            // 位置が先頭を指し示すように修正
+            self.skip(1);
+            let mut t = self.create_token(Token::VoidPointer, "void*".to_string());
+            t.pos.col = col;
+            t
+        } else {
+            // 位置が先頭を指し示すように修正
+            let mut t = self.create_token(Token::Void, "void".to_string());
+            t.pos.col = col;
+            t
+        }
+    }
+
     // type作成
     fn generate_type(&mut self, c: char) -> Option<TokenInfo> {
         if self.is_type_int(c) {
             Some(self.generate_type_int())
         } else if self.is_type_char(c) {
             Some(self.generate_type_char())
+        } else if self.is_type_void(c) {
+            Some(self.generate_type_void())
+        } else {
+            None
+        }
+    }
+
+    // 符号指定子作成
+    fn generate_sign(&mut self, c: char) -> Option<TokenInfo> {
+        if self.is_signed(c) {
+            let t = Some(self.create_token(Token::Signed, "signed".to_string()));
+            self.skip(5);
+            t
+        } else if self.is_unsigned(c) {
+            let t = Some(self.create_token(Token::Unsigned, "unsigned".to_string()));
+            self.skip(7);
+            t
         } else {
             None
         }
     }
 
+    // signed文字列判定
+    fn is_signed(&mut self, c: char) -> bool {
+        let s = self.read_string(6);
+        let l = s.chars().last();
+        c == 's'
+            && s.len() == 6
+            && "igned" == &s[0..5]
+            && !self.is_variable(l.expect("lexer.rs(is_signed): read error"))
+    }
+
+    // unsigned文字列判定
+    fn is_unsigned(&mut self, c: char) -> bool {
+        let s = self.read_string(8);
+        let l = s.chars().last();
+        c == 'u'
+            && s.len() == 8
+            && "nsigned" == &s[0..7]
+            && !self.is_variable(l.expect("lexer.rs(is_unsigned): read error"))
+    }
+
     // char型
     fn is_type_char(&mut self, c: char) -> bool {
         let s = self.read_string(4);
@@ -472,6 +600,18 @@ impl<'a> LexicalAnalysis<'a> {
             && !self.is_variable(l.expect("lexer.rs(is_type_char): read error"))
     }
 
+    // void型チェック
+    fn is_type_void(&mut self, c: char) -> bool {
+        let s = self.read_string(4);
+        let l = s.chars().last();
+
+        // voidx等の変数と区別する為、最後の文字をチェック
+        c == 'v'
+            && s.len() == 4
+            && &s[0..3] == "oid"
+            && !self.is_variable(l.expect("lexer.rs(is_type_void): read error"))
+    }
+
     // int型チェック
     fn is_type_int(&mut self, c: char) -> bool {
         let s = self.read_string(3);
@@ -484,17 +624,31 @@ impl<'a> LexicalAnalysis<'a> {
             && !self.is_variable(l.expect("lexer.rs(is_type_int): read error"))
     }
 
-    // sizeof演算
+    // sizeof、_Alignof演算
     fn generate_sizeof(&mut self, c: char) -> Option<TokenInfo> {
         if self.is_sizeof(c) {
             let t = Some(self.create_token(Token::SizeOf, "sizeof".to_string()));
             self.skip(5);
             t
+        } else if self.is_alignof(c) {
+            let t = Some(self.create_token(Token::AlignOf, "_Alignof".to_string()));
+            self.skip(7);
+            t
         } else {
             None
         }
     }
 
+    // _Alignof演算子チェック
+    fn is_alignof(&mut self, c: char) -> bool {
+        let s = self.read_string(8);
+        let l = s.chars().last();
+        c == '_'
+            && s.len() == 8
+            && "Alignof" == &s[0..7]
+            && !self.is_variable(l.expect("lexer.rs(is_alignof): read error"))
+    }
+
     // sizeof演算子チェック
     fn is_sizeof(&mut self, c: char) -> bool {
         let s = self.read_string(6);
@@ -527,6 +681,139 @@ impl<'a> LexicalAnalysis<'a> {
             && !self.is_variable(l.expect("lexer.rs(is_struct): read error"))
     }
 
+    // unionトークン作成
+    fn generate_union(&mut self, c: char) -> Option<TokenInfo> {
+        if self.is_union(c) {
+            let t = Some(self.create_token(Token::Union, "union".to_string()));
+            self.skip(4);
+            t
+        } else {
+            None
+        }
+    }
+
+    // union文字列判定
+    fn is_union(&mut self, c: char) -> bool {
+        let s = self.read_string(5);
+        let l = s.chars().last();
+        c == 'u'
+            && s.len() == 5
+            && "nion" == &s[0..4]
+            && !self.is_variable(l.expect("lexer.rs(is_union): read error"))
+    }
+
+    // enumトークン作成
+    fn generate_enum(&mut self, c: char) -> Option<TokenInfo> {
+        if self.is_enum(c) {
+            let t = Some(self.create_token(Token::Enum, "enum".to_string()));
+            self.skip(3);
+            t
+        } else {
+            None
+        }
+    }
+
+    // enum文字列判定
+    fn is_enum(&mut self, c: char) -> bool {
+        let s = self.read_string(4);
+        let l = s.chars().last();
+        c == 'e'
+            && s.len() == 4
+            && "num" == &s[0..3]
+            && !self.is_variable(l.expect("lexer.rs(is_enum): read error"))
+    }
+
+    // _Static_assertトークン作成
+    fn generate_static_assert(&mut self, c: char) -> Option<TokenInfo> {
+        if self.is_static_assert(c) {
+            let t = Some(self.create_token(Token::StaticAssert, "_Static_assert".to_string()));
+            self.skip(13);
+            t
+        } else {
+            None
+        }
+    }
+
+    // _Static_assert文字列判定
+    fn is_static_assert(&mut self, c: char) -> bool {
+        let s = self.read_string(14);
+        let l = s.chars().last();
+        c == '_'
+            && s.len() == 14
+            && "Static_assert" == &s[0..13]
+            && !self.is_variable(l.expect("lexer.rs(is_static_assert): read error"))
+    }
+
+    // 記憶域クラストークン作成
+    fn generate_storage(&mut self, c: char) -> Option<TokenInfo> {
+        if self.is_static(c) {
+            let t = Some(self.create_token(Token::Static, "static".to_string()));
+            self.skip(5);
+            t
+        } else if self.is_extern(c) {
+            let t = Some(self.create_token(Token::Extern, "extern".to_string()));
+            self.skip(5);
+            t
+        } else {
+            None
+        }
+    }
+
+    // static文字列判定
+    fn is_static(&mut self, c: char) -> bool {
+        let s = self.read_string(6);
+        let l = s.chars().last();
+        c == 's'
+            && s.len() == 6
+            && "tatic" == &s[0..5]
+            && !self.is_variable(l.expect("lexer.rs(is_static): read error"))
+    }
+
+    // extern文字列判定
+    fn is_extern(&mut self, c: char) -> bool {
+        let s = self.read_string(6);
+        let l = s.chars().last();
+        c == 'e'
+            && s.len() == 6
+            && "xtern" == &s[0..5]
+            && !self.is_variable(l.expect("lexer.rs(is_extern): read error"))
+    }
+
+    // 型修飾子トークン作成
+    fn generate_qualifier(&mut self, c: char) -> Option<TokenInfo> {
+        if self.is_const(c) {
+            let t = Some(self.create_token(Token::Const, "const".to_string()));
+            self.skip(4);
+            t
+        } else if self.is_volatile(c) {
+            let t = Some(self.create_token(Token::Volatile, "volatile".to_string()));
+            self.skip(7);
+            t
+        } else {
+            None
+        }
+    }
+
+    // const文字列判定
+    fn is_const(&mut self, c: char) -> bool {
+        let s = self.read_string(5);
+        let l = s.chars().last();
+        c == 'c'
+            && s.len() == 5
+            && "onst" == &s[0..4]
+            && !self.is_variable(l.expect("lexer.rs(is_const): read error"))
+    }
+
+    // volatile文字列判定
+    fn is_volatile(&mut self, c: char) -> bool {
+        let s = self.read_string(8);
+        let l = s.chars().last();
+        c == 'v'
+            && s.len() == 8
+            && "olatile" == &s[0..7]
+            && !self.is_variable(l.expect("lexer.rs(is_volatile): read error"))
+    }
+
     // ポインタ演算子が存在するか.
     fn is_pointer(&mut self) -> bool {
         // 空白は読み飛ばして、ポインタ型があるかチェック.
@@ -2186,7 +2473,7 @@ mod tests {
                 lexer.get_tokens()[6]
             );
             assert_eq!(
-                TokenInfo::new(Token::End, "End".to_string(), ("test.c".to_string(), 2, 13)),
+                TokenInfo::new(Token::End, "End".to_string(), ("test.c".to_string(), 2, 12)),
                 lexer.get_tokens()[7]
             );
         }
@@ -2331,6 +2618,65 @@ mod tests {
                 lexer.get_tokens()[5]
             );
         }
+        {
+            // 変数名とは区別する
+            let input = "_Alignof(int) + _Alignofx;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            let tokens: Vec<Token> = lexer.get_tokens().iter().map(|t| t.get_token_type()).collect();
+            assert_eq!(
+                vec![
+                    Token::AlignOf, Token::LeftParen, Token::Int, Token::RightParen, Token::Plus, Token::Variable,
+                    Token::SemiColon, Token::End
+                ],
+                tokens
+            );
+        }
+    }
+
+    #[test]
+    fn test_static_assert() {
+        {
+            // 改行後の位置情報
+            let input = "int a;\n_Static_assert(1, \"m\");".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(Token::StaticAssert, "_Static_assert".to_string(), ("test.c".to_string(), 2, 1)),
+                lexer.get_tokens()[3]
+            );
+            assert_eq!("test.c:2:1", lexer.get_tokens()[3].pos.to_string());
+        }
+        {
+            // 変数名とは区別する
+            let input = "_Static_assert _Static_asserts;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            let tokens: Vec<Token> = lexer.get_tokens().iter().map(|t| t.get_token_type()).collect();
+            assert_eq!(vec![Token::StaticAssert, Token::Variable, Token::SemiColon, Token::End], tokens);
+        }
+    }
+
+    #[test]
+    fn test_sign() {
+        {
+            let input = "unsigned a : 3; signed int b : 2; unsignedx signedx;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            let tokens: Vec<Token> = lexer.get_tokens().iter().map(|t| t.get_token_type()).collect();
+            assert_eq!(
+                vec![
+                    Token::Unsigned, Token::Variable, Token::Colon, Token::Number, Token::SemiColon,
+                    Token::Signed, Token::Int, Token::Variable, Token::Colon, Token::Number, Token::SemiColon,
+                    Token::Variable, Token::Variable, Token::SemiColon, Token::End
+                ],
+                tokens
+            );
+        }
     }
 
     #[test]
@@ -2563,6 +2909,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_enum() {
+        let input = "enum E enums;".to_string();
+        let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+        // enumで始まる識別子は変数とみなす
+        lexer.read_token();
+        assert_eq!(
+            TokenInfo::new(Token::Enum, "enum".to_string(), ("test.c".to_string(), 1, 1)),
+            lexer.get_tokens()[0]
+        );
+        assert_eq!(
+            TokenInfo::new(Token::Variable, "E".to_string(), ("test.c".to_string(), 1, 6)),
+            lexer.get_tokens()[1]
+        );
+        assert_eq!(
+            TokenInfo::new(Token::Variable, "enums".to_string(), ("test.c".to_string(), 1, 8)),
+            lexer.get_tokens()[2]
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        let input = "union U unions;".to_string();
+        let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+        // unionで始まる識別子は変数とみなす
+        lexer.read_token();
+        assert_eq!(
+            TokenInfo::new(Token::Union, "union".to_string(), ("test.c".to_string(), 1, 1)),
+            lexer.get_tokens()[0]
+        );
+        assert_eq!(
+            TokenInfo::new(Token::Variable, "U".to_string(), ("test.c".to_string(), 1, 7)),
+            lexer.get_tokens()[1]
+        );
+        assert_eq!(
+            TokenInfo::new(Token::Variable, "unions".to_string(), ("test.c".to_string(), 1, 9)),
+            lexer.get_tokens()[2]
+        );
+    }
+
     #[test]
     fn test_struct() {
         {
@@ -2676,4 +3064,246 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_storage_class() {
+        {
+            let input = "static int a; extern int b;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Static,
+                    "static".to_string(),
+                    ("test.c".to_string(), 1, 1)
+                ),
+                lexer.get_tokens()[0]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Int,
+                    "int".to_string(),
+                    ("test.c".to_string(), 1, 8)
+                ),
+                lexer.get_tokens()[1]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Extern,
+                    "extern".to_string(),
+                    ("test.c".to_string(), 1, 15)
+                ),
+                lexer.get_tokens()[4]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Int,
+                    "int".to_string(),
+                    ("test.c".to_string(), 1, 22)
+                ),
+                lexer.get_tokens()[5]
+            );
+        }
+        {
+            let input = "int statics; int externs;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Variable,
+                    "statics".to_string(),
+                    ("test.c".to_string(), 1, 5)
+                ),
+                lexer.get_tokens()[1]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Variable,
+                    "externs".to_string(),
+                    ("test.c".to_string(), 1, 18)
+                ),
+                lexer.get_tokens()[4]
+            );
+        }
+    }
+
+    #[test]
+    fn test_qualifier() {
+        {
+            let input = "const char* volatile p;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Const,
+                    "const".to_string(),
+                    ("test.c".to_string(), 1, 1)
+                ),
+                lexer.get_tokens()[0]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::CharPointer,
+                    "char*".to_string(),
+                    ("test.c".to_string(), 1, 7)
+                ),
+                lexer.get_tokens()[1]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Volatile,
+                    "volatile".to_string(),
+                    ("test.c".to_string(), 1, 13)
+                ),
+                lexer.get_tokens()[2]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Variable,
+                    "p".to_string(),
+                    ("test.c".to_string(), 1, 22)
+                ),
+                lexer.get_tokens()[3]
+            );
+        }
+        {
+            let input = "int constant;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Variable,
+                    "constant".to_string(),
+                    ("test.c".to_string(), 1, 5)
+                ),
+                lexer.get_tokens()[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_type_void() {
+        {
+            let input = "void *f(void);".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(
+                    Token::VoidPointer,
+                    "void*".to_string(),
+                    ("test.c".to_string(), 1, 1)
+                ),
+                lexer.get_tokens()[0]
+            );
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Void,
+                    "void".to_string(),
+                    ("test.c".to_string(), 1, 9)
+                ),
+                lexer.get_tokens()[3]
+            );
+        }
+        {
+            let input = "int voidp;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            assert_eq!(
+                TokenInfo::new(
+                    Token::Variable,
+                    "voidp".to_string(),
+                    ("test.c".to_string(), 1, 5)
+                ),
+                lexer.get_tokens()[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_member_access() {
+        {
+            let input = "a.b->c;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+            let expected = [
+                (Token::Variable, "a", 1),
+                (Token::Dot, ".", 2),
+                (Token::Variable, "b", 3),
+                (Token::Arrow, "->", 4),
+                (Token::Variable, "c", 6),
+                (Token::SemiColon, ";", 7),
+            ];
+
+            lexer.read_token();
+            expected.iter().enumerate().for_each(|(i, (t, v, col))| {
+                assert_eq!(
+                    TokenInfo::new(t.clone(), v.to_string(), ("test.c".to_string(), 1, *col)),
+                    lexer.get_tokens()[i]
+                );
+            });
+        }
+        {
+            // 減算、デクリメントとは区別する
+            let input = "a-->b-c".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            let tokens: Vec<Token> = lexer.get_tokens().iter().map(|t| t.get_token_type()).collect();
+            assert_eq!(
+                vec![Token::Variable, Token::Dec, Token::GreaterThan, Token::Variable, Token::Minus, Token::Variable, Token::End],
+                tokens
+            );
+        }
+    }
+
+    #[test]
+    fn test_bit_assign() {
+        {
+            let input = "a &= b |= c ^= d <<= e >>= 1;".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+            let expected = [
+                (Token::Variable, "a", 1),
+                (Token::BitAndAssign, "&=", 3),
+                (Token::Variable, "b", 6),
+                (Token::BitOrAssign, "|=", 8),
+                (Token::Variable, "c", 11),
+                (Token::BitXorAssign, "^=", 13),
+                (Token::Variable, "d", 16),
+                (Token::LeftShiftAssign, "<<=", 18),
+                (Token::Variable, "e", 22),
+                (Token::RightShiftAssign, ">>=", 24),
+                (Token::Number, "1", 28),
+                (Token::SemiColon, ";", 29),
+            ];
+
+            lexer.read_token();
+            expected.iter().enumerate().for_each(|(i, (t, v, col))| {
+                assert_eq!(
+                    TokenInfo::new(t.clone(), v.to_string(), ("test.c".to_string(), 1, *col)),
+                    lexer.get_tokens()[i]
+                );
+            });
+        }
+        {
+            // 論理演算子、シフト演算子、比較演算子とは区別する
+            let input = "a&&b<<c>>=d<=e&f".to_string();
+            let mut lexer = LexicalAnalysis::new("test.c".to_string(), &input);
+
+            lexer.read_token();
+            let tokens: Vec<Token> = lexer.get_tokens().iter().map(|t| t.get_token_type()).collect();
+            assert_eq!(
+                vec![
+                    Token::Variable, Token::LogicalAnd, Token::Variable, Token::LeftShift, Token::Variable,
+                    Token::RightShiftAssign, Token::Variable, Token::LessThanEqual, Token::Variable, Token::And,
+                    Token::Variable, Token::End
+                ],
+                tokens
+            );
+        }
+    }
 }
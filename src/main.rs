@@ -1,69 +1,144 @@
 mod arch;
 mod asm;
 mod ast;
+mod cfg;
 mod config;
+mod ir;
 mod lexer;
 mod semantic;
 mod symbol;
 mod token;
+mod warning;
 
 use asm::Asm;
 use ast::AstGen;
+use cfg::Cfg;
+use ir::Module;
 use lexer::LexicalAnalysis;
 use semantic::Semantic;
 use std::env;
 use std::fs::File;
 use std::io::Read;
+use std::process;
+use warning::WarningOptions;
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Emit {
+    Asm,    // アセンブリ（既定）
+    CfgDot, // 関数毎の制御フローグラフ（Graphviz形式）
+    Ir,     // 中間表現
+}
 
 /// コンパイルスタート
 ///
 /// 成功時、アセンブリを返す。失敗時はエラーのVecを返す
+#[cfg(test)]
 fn compile(inst: &str) -> Result<String, Vec<String>> {
+    compile_with(inst, &WarningOptions::default())
+}
+
+/// 警告オプションを指定してコンパイル
+#[cfg(test)]
+fn compile_with(inst: &str, opts: &WarningOptions) -> Result<String, Vec<String>> {
+    compile_to(inst, opts, Emit::Asm)
+}
+
+/// 警告オプション、出力形式を指定してコンパイル
+///
+/// 有効な警告は標準エラーへ出力する（-Werror指定時はエラーとして返す）
+fn compile_to(inst: &str, opts: &WarningOptions, emit: Emit) -> Result<String, Vec<String>> {
     // 字句解析
-    let mut p = LexicalAnalysis::new("stdin".to_string(), &inst);
+    let mut p = LexicalAnalysis::new("stdin".to_string(), inst);
     p.read_token();
 
     // AST作成
     let mut ast_gen = AstGen::new(p.get_tokens());
     let ast_tree = ast_gen.parse();
 
-    // 意味解析
+    // 意味解析（構文エラーがあっても、正しく解析できた部分は検査する）
     let sym = ast_gen.get_symbol();
-    let mut sem = Semantic::new(&ast_tree, &sym);
-    sem.exec()?;
+    let mut sem = Semantic::new(&ast_tree, sym);
+    let mut errs = ast_gen.get_errors().clone();
+    if let Err(ref mut e) = sem.exec() {
+        errs.append(e);
+    }
+    let warns = ast_gen.get_warnings().iter().chain(sem.get_warnings().iter()).filter(|w| opts.is_enabled(w.kind));
+    if opts.werror {
+        errs.extend(warns.map(|w| w.to_error()));
+    } else {
+        warns.for_each(|w| eprintln!("{}", w));
+    }
+    if !errs.is_empty() {
+        return Err(errs);
+    }
 
-    // アセンブラへ変換.
-    let mut asm = Asm::new(&sym);
-    asm.exec(&ast_tree);
-    Ok(asm.get_inst())
+    // 中間表現へ変換し、検査
+    let lower = || {
+        let module = Module::build(&ast_tree, sym);
+        ir::verify(&module).map(|_| module)
+    };
+    match emit {
+        // アセンブラへ変換.
+        Emit::Asm => {
+            let mut asm = Asm::new();
+            asm.exec(&lower()?);
+            Ok(asm.get_inst())
+        }
+        Emit::CfgDot => Ok(ast_tree.get_tree().iter().filter_map(Cfg::build).map(|c| c.to_dot()).collect()),
+        Emit::Ir => Ok(lower()?.to_string()),
+    }
+}
+
+#[doc = "オプションエラー出力"]
+///
+/// エラーを標準エラーへ出力し、異常終了する
+fn fatal(m: &str) -> ! {
+    eprintln!("error: {}", m);
+    process::exit(1)
 }
 
 #[doc = "メイン関数"]
 fn main() {
-    // コマンドライン引数評価
-    let args: Vec<String> = env::args().collect();
+    // コマンドライン引数評価（-Wで始まる引数は警告オプション、--dump-cfg=dotは制御フローグラフ出力、--emit=irは中間表現出力）
+    let (flags, args): (Vec<String>, Vec<String>) = env::args()
+        .skip(1)
+        .partition(|a| a.starts_with("-W") || a.starts_with("--dump-cfg=") || a.starts_with("--emit="));
+    let mut opts = WarningOptions::default();
+    let mut emit = Emit::Asm;
+    flags.iter().for_each(|f| match (f.strip_prefix("--dump-cfg="), f.strip_prefix("--emit=")) {
+        (Some("dot"), _) => emit = Emit::CfgDot,
+        (Some(format), _) => fatal(&format!("unsupported cfg format: {}", format)),
+        (_, Some("ir")) => emit = Emit::Ir,
+        (_, Some("asm")) => emit = Emit::Asm,
+        (_, Some(kind)) => fatal(&format!("unsupported emit kind: {}", kind)),
+        _ => opts.apply(f).unwrap_or_else(|e| fatal(&e)),
+    });
 
     // 引数チェック
-    if args.len() < 2 {
-        panic!("Usage: rcc [--input] [filename]")
+    if args.is_empty() {
+        panic!("Usage: rcc [-W<warning>...] [--dump-cfg=dot] [--emit=ir] [--input] [filename]")
     }
 
     // 入力ソースを決定
     let mut s = String::new();
-    match &*args[1] {
+    match &*args[0] {
         "--input" => {
             std::io::stdin().read_line(&mut s).unwrap();
         }
         _ => {
-            let mut f = File::open(&args[1]).unwrap_or_else(|_| panic!("not found file {}", args[1]));
+            let mut f = File::open(&args[0]).unwrap_or_else(|_| panic!("not found file {}", args[0]));
             f.read_to_string(&mut s).expect("read file error");
         }
     };
 
-    // コンパイル実行
-    match compile(&s) {
+    // コンパイル実行（エラーは標準エラーへ出力し、異常終了）
+    match compile_to(&s, &opts, emit) {
         Ok(inst) => println!("{}", inst),
-        Err(errs) => errs.iter().for_each(|e| println!("{:?}", e)),
+        Err(errs) => {
+            errs.iter().for_each(|e| eprintln!("{}", e));
+            process::exit(1);
+        }
     }
 }
 
@@ -98,15 +173,15 @@ mod test {
             Ok(inst) => {
                 // gccを使用して実行.
                 let _ = create_asm_file(&inst);
-                match Command::new("gcc").args(&["-g3", "-no-pie", "./test.s", "-o", "test"]).output() {
-                    Err(e) => panic!(e),
+                match Command::new("gcc").args(["-g3", "-no-pie", "./test.s", "-o", "test"]).output() {
+                    Err(e) => std::panic::panic_any(e),
                     Ok(_) => {
                         match Command::new("./test").status() {
                             Ok(r) => match r.code() {
                                 Some(r) => r,
                                 None => panic!("code() is failed"),
                             },
-                            Err(e) => panic!(e),
+                            Err(e) => std::panic::panic_any(e),
                         }
                     }
                 }
@@ -285,7 +360,7 @@ mod test {
             TestData { inst: "int main() { int a[10]; int i; for(i = 0 ; i < 10 ; i++) { a[i] = i; } return a[8] + a[9]; }", ex_ret: 17, },
             TestData { inst: "int main() { int a[10]; int i; for(i = 0 ; i < 10 ; i++) { a[i] = i * 2; } return a[7]; }", ex_ret: 14, },
             TestData { inst: "int main() { int a[10][10]; a[2][9] = 10; return a[2][9]; }", ex_ret: 10, },
-            TestData { inst: "int main() { int a[10][10]; a[2][9] = 10; a[1][7] = 7 return a[2][9] + a[1][7]; }", ex_ret: 17, },
+            TestData { inst: "int main() { int a[10][10]; a[2][9] = 10; a[1][7] = 7; return a[2][9] + a[1][7]; }", ex_ret: 17, },
             TestData { inst: "int main() { int a[10][10]; int i; for(i = 0 ; i < 10 ; i++) { a[7][i] = i; } return a[7][9] * a[7][2]; }", ex_ret: 18, },
             TestData { inst: "int main() { int a[10][8][2]; a[9][1][1] = 99; return a[9][1][1]; }", ex_ret: 99, },
             TestData { inst: "int main() { int a[10][8][2]; a[0][0][0] = 100; a[9][0][1] = 99; return a[0][0][0] + a[9][0][1]; }", ex_ret: 199, },
@@ -302,28 +377,28 @@ mod test {
             TestData { inst: "int* a; int main() { int b; b = 99; a = &b; return *a; }", ex_ret: 99 },
             TestData { inst: "int a[10]; int main() { int i; for (i = 0 ; i < 10 ; i++) { a[i] = i * 2; } return a[1] + a[4] + a[8]; }", ex_ret: 26 },
             TestData { inst: "int main() { int i = 2; i++; return i; }", ex_ret: 3 },
-            TestData { inst: "int main() { int i = 2; i-- return i; }", ex_ret: 1 },
+            TestData { inst: "int main() { int i = 2; i--; return i; }", ex_ret: 1 },
             TestData { inst: "int main() { int i = 2; return i++; }", ex_ret: 2 },
-            TestData { inst: "int main() { int i = 2; return i-- }", ex_ret: 2 },
+            TestData { inst: "int main() { int i = 2; return i--; }", ex_ret: 2 },
             TestData { inst: "int i; int main() { i = 2; i++; return i; }", ex_ret: 3 },
-            TestData { inst: "int i; int main() { i = 2; i-- return i; }", ex_ret: 1 },
+            TestData { inst: "int i; int main() { i = 2; i--; return i; }", ex_ret: 1 },
             TestData { inst: "int i; int main() { i = 2; return i++; }", ex_ret: 2 },
-            TestData { inst: "int i; int main() { i = 2; return i-- }", ex_ret: 2 },
+            TestData { inst: "int i; int main() { i = 2; return i--; }", ex_ret: 2 },
             TestData { inst: "int main() { int a[10]; int *b = a; b++; *b = 100; return a[1]; }", ex_ret: 100 },
             TestData { inst: "int main() { int a[10]; int *b = a; b++; *b = 99; return *b; }", ex_ret: 99 },
             TestData { inst: "int main() { int a[10]; int *b = a; b++; b++; b--; *b = 99; return a[1]; }", ex_ret: 99 },
             TestData { inst: "int main() { int a[10]; int *b = a; b++; b++; b--; *b = 99; return *b; }", ex_ret: 99 },
             TestData { inst: "int i; int main() { i = 2; ++i; return i; }", ex_ret: 3 },
-            TestData { inst: "int i; int main() { i = 2; --i return i; }", ex_ret: 1 },
+            TestData { inst: "int i; int main() { i = 2; --i; return i; }", ex_ret: 1 },
             TestData { inst: "int i; int main() { i = 2; return ++i; }", ex_ret: 3 },
-            TestData { inst: "int i; int main() { i = 2; return --i }", ex_ret: 1 },
+            TestData { inst: "int i; int main() { i = 2; return --i; }", ex_ret: 1 },
             TestData { inst: "int main() { int a[10]; int *b = a; ++b; *b = 100; return a[1]; }", ex_ret: 100 },
             TestData { inst: "int main() { int a[10]; int *b = a; ++b; *b = 99; return *b; }", ex_ret: 99 },
             TestData { inst: "int main() { int a[10]; int *b = a; ++b; ++b; --b; *b = 99; return a[1]; }", ex_ret: 99 },
             TestData { inst: "int main() { int a[10]; int *b = a; ++b; ++b; --b; *b = 99; return *b; }", ex_ret: 99 },
-            TestData { inst: "int main() { char i; i = 2; return i }", ex_ret: 2 },
+            TestData { inst: "int main() { char i; i = 2; return i; }", ex_ret: 2 },
             TestData { inst: "int main() { char i[2]; i[0] = 0; i[1] = 19; return i[0] + i[1]; }", ex_ret: 19 },
-            TestData { inst: "char i; int main() { i = 20; return i }", ex_ret: 20 },
+            TestData { inst: "char i; int main() { i = 20; return i; }", ex_ret: 20 },
             TestData { inst: "int main() { char* i; char y = 10; i = &y; return *i + 20; }", ex_ret: 30 },
             TestData { inst: "int main() { char* i; char y = 10; i = &y; *i = *i + 20; return *i; }", ex_ret: 30, },
             TestData { inst: "int main() { char* i; char y = 10; i = &y; *i = *i - 2; return *i; }", ex_ret: 8, },
@@ -334,13 +409,13 @@ mod test {
             TestData { inst: "int i; int main() { int i = 20; return i + 100; }", ex_ret: 120 },
             TestData { inst: "int main() { return sizeof(char); }", ex_ret: 1 },
             TestData { inst: "int main() { return sizeof(int); }", ex_ret: 4 },
-            TestData { inst: "int main() { return sizeof(10); }", ex_ret: 8 },
+            TestData { inst: "int main() { return sizeof(10); }", ex_ret: 4 },
             TestData { inst: "int main() { int a = 1; return sizeof(a); }", ex_ret: 4 },
             TestData { inst: "int main() { char a = 1; return sizeof(a); }", ex_ret: 1 },
             TestData { inst: "int main() { int* a; return sizeof(a); }", ex_ret: 8 },
             TestData { inst: "int main() { char* a; return sizeof(a); }", ex_ret: 8 },
-            TestData { inst: "int main() { int a[2]; return sizeof(a); }", ex_ret: 16 },
-            TestData { inst: "int main() { int a[2][10]; return sizeof(a); }", ex_ret: 160 },
+            TestData { inst: "int main() { int a[2]; return sizeof(a); }", ex_ret: 8 },
+            TestData { inst: "int main() { int a[2][10]; return sizeof(a); }", ex_ret: 80 },
             TestData { inst: "int main() { int a = 0; a += 102; return a; }", ex_ret: 102 },
             TestData { inst: "int main() { int a = 0; a += (10 * 2); return a; }", ex_ret: 20 },
             TestData { inst: "int main() { int a = 100; a -= 81; return a; }", ex_ret: 19 },
@@ -407,16 +482,149 @@ mod test {
             TestData { inst: "int main() { struct A { char a; char b; }; struct A c; return sizeof(c); }", ex_ret: 2 },
             TestData { inst: "int main() { struct A { char a; int b; int c; }; struct A d; return sizeof(d); }", ex_ret: 12 },
             TestData { inst: "struct A { char a; char b; char c; char d; char e; }; int main() { struct A x; return sizeof(x); }", ex_ret: 5 },
+            TestData { inst: "int b; int a[3]; int c; int main() { a[0] = 1; a[2] = 5; b = 3; c = 7; return a[0] + a[2] + b + c; }", ex_ret: 16 },
+            TestData { inst: "int a[3] = {1, 2, 3}; int main() { return a[0] + a[1] + a[2]; }", ex_ret: 6 },
+            TestData { inst: "int a[4] = {1, 2,}; int main() { return a[1] + a[2] + a[3]; }", ex_ret: 2 },
+            TestData { inst: "int a[2][3] = {{1, 2, 3}, {4, 5, 6}}; int main() { return a[1][2] + a[0][1]; }", ex_ret: 8 },
+            TestData { inst: "int a = 2 * 3 + 1; int main() { return a; }", ex_ret: 7 },
+            TestData { inst: "int a = sizeof(int) << 2; char b = -1; int main() { return a + b; }", ex_ret: 15 },
+            TestData { inst: "int x = 7; int *p = &x; int main() { return *p; }", ex_ret: 7 },
+            TestData { inst: "int a[3] = {1, 2, 3}; int *p = &a[2]; int main() { return *p; }", ex_ret: 3 },
+            TestData { inst: "struct A { char a; int b; }; struct A x = {1, 2}; int y = 9; int main() { return y; }", ex_ret: 9 },
+            TestData { inst: "struct A { char a; int b; char c; }; struct A x; int main() { return sizeof(x); }", ex_ret: 12 },
+            TestData { inst: "static int x = 3; static int f() { return x; } int main() { return f(); }", ex_ret: 3 },
+            TestData { inst: "int counter() { static int c = 10; c = c + 1; return c; } int main() { counter(); counter(); return counter(); }", ex_ret: 13 },
+            TestData { inst: "int f() { static int n; n++; return n; } int main() { f(); f(); return f(); }", ex_ret: 3 },
+            TestData { inst: "int f() { static int a[3] = {1, 2, 3}; a[0] = a[0] + 1; return a[0] + a[2]; } int main() { f(); return f(); }", ex_ret: 6 },
+            TestData { inst: "int f() { static int n = 1; return n; } int g() { static int n = 5; return n; } int main() { int a; a = f(); return a + g(); }", ex_ret: 6 },
+            TestData { inst: "int f() { static int a = 1, b = 2; a++; b += 10; return a + b; } int main() { f(); return f(); }", ex_ret: 25 },
+            TestData { inst: "extern int x; int x = 5; int main() { return x; }", ex_ret: 5 },
+            TestData { inst: "extern int y; int y; int main() { y = 4; return y; }", ex_ret: 4 },
+            TestData { inst: "int add(int a, int b); int main() { return add(1, 2); } int add(int a, int b) { return a * 10 + b; }", ex_ret: 12 },
+            TestData { inst: "static int sq(int); int main() { return sq(3); } int sq(int a) { return a * a; }", ex_ret: 9 },
+            TestData { inst: "int g; int main() { int *p = &g; *p = 6; return g; }", ex_ret: 6 },
+            TestData { inst: "const int x = 7; int main() { return x; }", ex_ret: 7 },
+            TestData { inst: "const int a[3] = {1, 2, 3}; int main() { return a[2]; }", ex_ret: 3 },
+            TestData { inst: "int main() { const int x = 3; int y; y = x + 1; return y; }", ex_ret: 4 },
+            TestData { inst: "int main() { int x; x = 2; const int *p; p = &x; return *p; }", ex_ret: 2 },
+            TestData { inst: "int main() { int x; x = 5; int * const p = &x; *p = 6; return x; }", ex_ret: 6 },
+            TestData { inst: "volatile int v; int main() { v = 3; return v; }", ex_ret: 3 },
+            TestData { inst: "int f() { static const int t = 4; return t; } int main() { return f(); }", ex_ret: 4 },
+            TestData { inst: "int main() { const int x = 3; x = 4; return x; }", ex_ret: -1 },
+            TestData { inst: "int add(int a, int b) { return a + b; } int main() { int (*fp)(int, int); fp = add; return fp(2, 3); }", ex_ret: 5 },
+            TestData { inst: "int add(int a, int b) { return a + b; } int main() { int (*fp)(int, int) = &add; return (*fp)(4, 3); }", ex_ret: 7 },
+            TestData { inst: "int add(int a, int b) { return a + b; } int sub(int a, int b) { return a - b; } int main() { int (*ops[2])(int, int); ops[0] = add; ops[1] = sub; return ops[0](5, 1) * ops[1](5, 1); }", ex_ret: 24 },
+            TestData { inst: "int twice(int x) { return x * 2; } int apply(int (*f)(int), int v) { return f(v); } int main() { return apply(twice, 21); }", ex_ret: 42 },
+            TestData { inst: "int inc(int x) { return x + 1; } int main() { return (inc)(4); }", ex_ret: 5 },
+            TestData { inst: "int inc(int x) { return x + 1; } int main() { int (*f)(int) = inc; return f(1, 2); }", ex_ret: -1 },
+            TestData { inst: "struct P { int x; int y; }; int main() { struct P a; a.x = 30; a.y = 4; return a.x + a.y; }", ex_ret: 34 },
+            TestData { inst: "struct P { int x; int y; }; int main() { struct P a; struct P b; a.x = 3; a.y = 4; b = a; return b.x + b.y; }", ex_ret: 7 },
+            TestData { inst: "struct P { int x; int y; }; int sum(struct P p) { return p.x + p.y; } int main() { struct P a; a.x = 5; a.y = 6; return sum(a); }", ex_ret: 11 },
+            TestData { inst: "struct P { int x; int y; }; struct P mk(int x, int y) { struct P p; p.x = x; p.y = y; return p; } int main() { struct P a; a = mk(7, 8); return a.x * a.y; }", ex_ret: 56 },
+            TestData { inst: "struct B { int a; int b; int c; int d; int e; }; struct B mk(int v) { struct B r; r.a = v; r.b = v; r.c = v; r.d = v; r.e = v; return r; } int sum(struct B b) { return b.a + b.b + b.c + b.d + b.e; } int main() { return sum(mk(3)); }", ex_ret: 15 },
+            TestData { inst: "struct P { int a; int b; int c; }; int main() { struct P s; struct P *p; p = &s; p->c = 40; (*p).a = 2; return s.a + p->c; }", ex_ret: 42 },
+            TestData { inst: "struct P { int x; int y; }; int f(int a, int b, int c, int d, int e, struct P p) { return a + b + c + d + e + p.x + p.y; } int main() { struct P p; p.x = 10; p.y = 20; return f(1, 2, 3, 4, 5, p); }", ex_ret: 45 },
+            TestData { inst: "struct C { char a; char b; char c; }; struct C id(struct C c) { return c; } int main() { struct C c; c.a = 10; c.b = 11; c.c = 12; c = id(c); return c.a + c.b + c.c; }", ex_ret: 33 },
+            TestData { inst: "struct P { int x; int y; }; int main() { struct P a; struct P b; struct P c; a.x = 1; a.y = 2; c = b = a; return c.x + c.y; }", ex_ret: 3 },
+            TestData { inst: "struct P { int x; int y; }; int main() { struct P a; int *q; q = &a.y; *q = 6; return a.y; }", ex_ret: 6 },
+            TestData { inst: "int main() { int x; int a[3]; x = 5; a[0] = 1; a[1] = 2; a[2] = 3; return x; }", ex_ret: 5 },
+            TestData { inst: "int main() { int x; char c; x = -1; c = -1; return (x < 0) + (c < 0); }", ex_ret: 2 },
+            TestData { inst: "int x; int y; int main() { y = 5; x = -1; return y; }", ex_ret: 5 },
+            TestData { inst: "int main() { int a[4]; int *p; int *q; p = &a[3]; q = a; return (p - q) * 10 + (q - p + 4); }", ex_ret: 31 },
+            TestData { inst: "int main() { char s[3]; char *p; s[0] = 10; s[1] = 20; s[2] = 30; p = s + 2; return (p - s) * 100 + *--p; }", ex_ret: 220 },
+            TestData { inst: "int main() { int a[3]; int *p; a[0] = 10; a[1] = 20; a[2] = 30; p = a; p += 2; p -= 1; return *p + *(1 + a); }", ex_ret: 40 },
+            TestData { inst: "int main() { int a[3]; int *p; a[0] = 10; a[1] = 20; a[2] = 30; p = a; p++; ++p; return *p-- + *p; }", ex_ret: 50 },
+            TestData { inst: "int main() { int a[5]; int *p; int i; a[0] = 9; p = a + 4; i = -2; return *(p + i + i); }", ex_ret: 9 },
+            TestData { inst: "int main() { int a[3]; int i; a[1] = 2; a[2] = -3; i = 2; a[1] += 5; a[i] -= 1; return a[1] + a[2] + (a[2] < 0) * 100; }", ex_ret: 103 },
+            TestData { inst: "int main() { int a[2]; int *p; a[0] = 1; a[1] = 2; p = a; *p += 10; *(p + 1) *= 3; return a[0] + a[1]; }", ex_ret: 17 },
+            TestData { inst: "struct P { int x; int y; }; int main() { struct P s; struct P *p; p = &s; s.x = 3; s.y = 1; p->x += 4; p->y++; ++s.y; return s.x * 10 + s.y; }", ex_ret: 73 },
+            TestData { inst: "struct P { int x; int y; int z; }; int main() { struct P s; struct P *p; struct P *q; p = &s; q = p + 2; return (q - p) * 10 + ((q - 1) - p); }", ex_ret: 21 },
+            TestData { inst: "int main() { int a[2]; int *p; int *q; p = a; q = a + 1; return p + q; }", ex_ret: -1 },
+            TestData { inst: "int main() { int a; int b; int c; a = 12; b = 5; c = 6; a &= 10; b |= 2; c ^= 3; return a * 100 + b * 10 + c; }", ex_ret: 107 },
+            TestData { inst: "int main() { int a; int b; a = 3; b = -64; a <<= 4; b >>= 2; return a + b + 100; }", ex_ret: 132 },
+            TestData { inst: "int main() { int a[4]; int i; i = 0; a[0] = 1; a[1] = 2; a[2] = 4; a[i++] |= 16; a[i++] <<= 3; a[i++] += 5; return a[0] + a[1] + a[2] + i * 100; }", ex_ret: 86 },
+            TestData { inst: "int main() { char c; int x; c = 1; c <<= 7; x = c; return x < 0; }", ex_ret: 1 },
+            TestData { inst: "int main() { int x; int y; x = 1; y = 2; x += y += 3; return x * 10 + y; }", ex_ret: 65 },
+            TestData { inst: "struct S { int f; }; int main() { struct S s; struct S *p; p = &s; s.f = 7; p->f &= 3; p->f ^= 8; return s.f; }", ex_ret: 11 },
+            TestData { inst: "int main() { int i; int j; int s; s = 0; for (i = 0, j = 5; i < j; i++, j--) { s = s * 10 + i + j; } return s % 256; }", ex_ret: 43 },
+            TestData { inst: "int main() { int a; int b; a = 1, b = 2; return (a, b) * 10 + (a += 3, a); }", ex_ret: 24 },
+            TestData { inst: "int f(int x, int y) { return x * 10 + y; } int main() { int a; a = 1; return f((a++, a), 3); }", ex_ret: 23 },
+            TestData { inst: "int main() { int a = 1, b = 2, c; c = 3; return a + b + c; }", ex_ret: 6 },
+            TestData { inst: "int main() { int i; int n; i = 0; n = 0; while (i++, i < 5) { n = n + i; } if (n = 3, n > 2) { return n; } return 0; }", ex_ret: 3 },
+            TestData { inst: "int g; int h() { return g = 5, g + 1; } int main() { int r; r = h(); return r * 10 + g; }", ex_ret: 65 },
+            TestData { inst: "int main() { int a[3]; int *p; p = a; return sizeof(a[0]) * 10 + sizeof *p; }", ex_ret: 44 },
+            TestData { inst: "int main() { return sizeof(int*[4]) + sizeof(char) + sizeof(char *); }", ex_ret: 41 },
+            TestData { inst: "int main() { int x; x = 3; return sizeof x + 1; }", ex_ret: 5 },
+            TestData { inst: "int main() { int x; x = 3; sizeof(x++); return x + sizeof(x = 10); }", ex_ret: 7 },
+            TestData { inst: "int main() { int a[sizeof(int) * 2]; int b[2][sizeof(a) / 4]; return sizeof(b) + sizeof a; }", ex_ret: 96 },
+            TestData { inst: "struct P { char c; int x; }; int main() { struct P s[3]; struct P *p; p = s; return sizeof(s) + sizeof(*p) + sizeof p->c + sizeof(struct P *) + _Alignof(struct P); }", ex_ret: 45 },
+            TestData { inst: "int main() { return _Alignof(char) * 100 + _Alignof(int) * 10 + _Alignof(int *); }", ex_ret: 148 },
+            TestData { inst: "int main() { int a[2][3]; return sizeof(a[1]) + sizeof a[1][2] + sizeof(\"abc\") + sizeof(a + 1); }", ex_ret: 28 },
+            TestData { inst: "int g[sizeof(int) + 1]; char c[_Alignof(int)]; int main() { return sizeof(g) + sizeof(c); }", ex_ret: 24 },
+            TestData { inst: "int a = 0 && 1 / 0; int b = 1 ? 2 : 1 / 0; int main() { int x = 1 ? 5 : 6; int *p; p = &x; *p = x > 3 && x < 9; return a * 100 + b * 10 + x; }", ex_ret: 21 },
+            TestData { inst: "int c = -2147483647 - 1; int d = (3 > 2) * 10 + (7 & 3) + (1 << 4) % 5; int main() { return c / 2147483647 + d; }", ex_ret: 13 },
+            TestData { inst: "int main() { int a; int b; a = -5; b = 2; return ((a + b) < 0) + (a * b < 0) * 10 + (a / b + 3) * 100; }", ex_ret: 111 },
+            TestData { inst: "int a = 1 / 0; int main() { return 0; }", ex_ret: -1 },
+            TestData { inst: "struct S { int a; char b; _Static_assert(sizeof(int) == 4, \"int\"); }; _Static_assert(sizeof(struct S) == 8, \"S\"); int main() { struct S s; _Static_assert(_Alignof(struct S) == 4, \"align\"); s.a = 3; s.b = 4; return s.a + s.b; }", ex_ret: 7 },
+            TestData { inst: "struct S { int a; char b; }; _Static_assert(sizeof(struct S) == 5, \"S layout\"); int main() { return 0; }", ex_ret: -1 },
+            TestData { inst: "struct F { unsigned ready:1; unsigned mode:3; int level:4; }; int main() { struct F f; f.ready = 1; f.mode = 6; f.level = -3; return f.ready + f.mode * 2 + (f.level + 3) * 100 + sizeof(f); }", ex_ret: 17 },
+            TestData { inst: "struct F { int a:2; }; int main() { struct F f; int *p; p = &f.a; return 0; }", ex_ret: -1 },
+            TestData { inst: "int main() { int x = 3; x = x + 1; }", ex_ret: 0 },
+            TestData { inst: "int f() { return 7; } int main(int argc, char **argv) { char **p; p = argv; if (argc == 1) { return f() + (p[1] == 0); } }", ex_ret: 8 },
+            TestData { inst: "int main() { char *a[2]; char **p; char ***pp; a[1] = \"xyz\"; p = a; pp = &p; return (*pp)[1][2] - 120 + sizeof(**pp); }", ex_ret: 10 },
+            TestData { inst: "int main() { int a; a = 1 + ; return a; }", ex_ret: -1 },
+            TestData { inst: "int g 5; int main() { return 0; }", ex_ret: -1 },
+            TestData { inst: "struct S { int a; int 5; }; int main() { struct S s; s.a = 1; return s.a; }", ex_ret: -1 },
+            TestData { inst: "int main() { if (1) { return 2; }", ex_ret: -1 },
+            TestData { inst: "int f(int a, int *p) { return a + *p; } int main() { int x = 1; return f(x, &x) + f(2, &x); }", ex_ret: 5 },
+            TestData { inst: "int f(int a, int *p) { return a + *p; } int main() { int x = 1; return f(&x, x); }", ex_ret: -1 },
+            TestData { inst: "int f(int a) { return a; } int main() { return f(1, 2); }", ex_ret: -1 },
+            TestData { inst: "int a[3]; int *f(int i) { if (i < 0) { return 0; } return a + i; } int main() { a[2] = 6; return *f(2) + (f(-1) == 0); }", ex_ret: 7 },
+            TestData { inst: "int f(int x) { if (x) { return; } return 1; } int main() { return f(0); }", ex_ret: -1 },
+            TestData { inst: "int *f(int x) { return x; } int main() { return 0; }", ex_ret: -1 },
+            TestData { inst: "int g; void set(int v) { g = v; return; } void *id(void *p) { return p; } int main() { int a; int *p; set(3); p = id(&a); *p = 4; (void)set; return g + a + (char)300 + (int)(char)257; }", ex_ret: 52 },
+            TestData { inst: "void f(void) { } int main() { int a; a = f(); return a; }", ex_ret: -1 },
+            TestData { inst: "int f(void) { return 1; } int main() { return f(2); }", ex_ret: -1 },
+            TestData { inst: "int main() { int a = 1; int a = 2; return a; }", ex_ret: -1 },
+            TestData { inst: "int f(int n) { int n; return n; } int main() { return f(1); }", ex_ret: -1 },
+            TestData { inst: "int x = 3; int x; int main() { int y = 4; return x + y; }", ex_ret: 7 },
+            TestData { inst: "int main() { int a = 1; int b = 2; a + b = 3; return a; }", ex_ret: -1 },
+            TestData { inst: "int main() { int a[2]; int b[2]; a = b; return 0; }", ex_ret: -1 },
+            TestData { inst: "int main() { int a = 1; int *p = &(a + 1); return *p; }", ex_ret: -1 },
+            TestData { inst: "int t[] = {1, 2, 3}; int u[][2] = {{1, 2}, {3}}; int main() { return t[2] + sizeof(t) + u[1][0] + sizeof(u); }", ex_ret: 34 },
+            TestData { inst: "int t[] = 5; int main() { return 0; }", ex_ret: -1 },
+            TestData { inst: "int main() { int x = 1; int r = 0; if (x) { int y = 2; r = r + y; } else { int y = 3; r = r + y; } for (int i = 0; i < 2; i = i + 1) { int t = i; r = r + t; } for (int i = 0; i < 3; i = i + 1) { int t = 10; r = r + t; } { int x = 100; r = r + x; } return r + x; }", ex_ret: 134 },
+            TestData { inst: "int main() { int x = 1; { int x = 2; { int x = 3; } x = x + 10; } return x; }", ex_ret: 1 },
+            TestData { inst: "int main() { if (1) { int y = 1; int y = 2; return y; } return 0; }", ex_ret: -1 },
+            TestData { inst: "enum E {A=3, B, C = A * 4}; int g[A]; _Static_assert(C == 12, \"C\"); int main() { enum E e = B; return sizeof(g) + e + C; }", ex_ret: 28 },
+            TestData { inst: "enum F {X, Y} f; int main() { enum {L = 7} *p; int a[L]; f = Y; p = 0; return sizeof(a) + f + X; }", ex_ret: 29 },
+            TestData { inst: "enum E {A, A}; int main() { return A; }", ex_ret: -1 },
+            TestData { inst: "int x; enum E {A = x}; int main() { return A; }", ex_ret: -1 },
         ];
 
         // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
-        if false == Config::is_mac() {
+        if !Config::is_mac() {
             data.push(
                 TestData { inst: "int main() { char* i; i = \"test\"; return 1; }", ex_ret: 1, }
             );
             data.push(
                 TestData { inst: "int main() { char* a; a = \"test\"; char* b; b = \"bbbb\"; return 9; }", ex_ret: 9, }
             );
+            data.push(
+                TestData { inst: "char *s = \"abc\"; int main() { return *s; }", ex_ret: 97, }
+            );
+            data.push(
+                TestData { inst: "char s[4] = \"abc\"; int main() { return s[2] + s[3]; }", ex_ret: 99, }
+            );
+            data.push(
+                TestData { inst: "char s[] = \"abc\"; int main() { return s[2] + sizeof(s); }", ex_ret: 103, }
+            );
+            data.push(
+                TestData { inst: "int add(int a, int b) { return a + b; } int sub(int a, int b) { return a - b; } int (*ops[2])(int, int) = {add, sub}; int main() { return ops[1](9, 2); }", ex_ret: 7, }
+            );
+            data.push(
+                TestData { inst: "int twice(int x) { return x * 2; } int (*g)(int) = twice; int main() { return g(4) + (*g)(1); }", ex_ret: 10, }
+            );
         }
 
         data.iter()
@@ -435,4 +643,620 @@ mod test {
         let _ = fs::remove_file("test.s");
         let _ = fs::remove_file("test");
     }
+    #[test]
+    fn test_linkage() {
+        let inst = compile(
+            "extern int e; static int x = 3; int y; static int f() { static int c; return x + c; } int main() { return f(); }"
+        ).expect("compile error");
+
+        // static以外は外部から参照可能
+        assert!(inst.contains(".global y\n"));
+        assert!(inst.contains(".global main\n"));
+        assert!(!inst.contains(".global x\n"));
+        assert!(!inst.contains(".global f\n"));
+
+        // extern宣言は領域を確保しない
+        assert!(!inst.contains("e:\n"));
+
+        // 関数内static変数は関数名で修飾
+        assert!(inst.contains("f.c:\n"));
+        assert!(!inst.contains(".global f.c\n"));
+    }
+
+    #[test]
+    fn test_rodata() {
+        let inst = compile("const int x = 7; int y = 1; int main() { return x; }").expect("compile error");
+        let section = |name: &str| {
+            inst.lines()
+                .take_while(|l| *l != format!("{}:", name))
+                .filter(|l| l.trim_start().starts_with('.') && (l.contains("data") || l.contains("bss")))
+                .last()
+                .map(|l| l.trim().to_string())
+        };
+
+        // constなグローバル変数は読み取り専用領域へ配置
+        if !Config::is_mac() {
+            assert_eq!(Some(".section .rodata".to_string()), section("x"));
+            assert_eq!(Some(".data".to_string()), section("y"));
+        }
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        let errs = compile(
+            "int main() {\n  int a = 1 +;\n  int b;\n  b = (2 * ;\n  return a + b + c;\n}\nint f( { return 1; }\nint g() { const int x = 1; x = 2; return x; }\nstruct S { foo m; int n; };\nint h() { struct S s; return s.m; }\nint k() {\n  int x = 1;\n  return x }\nint l() { int r; r = 3 4; return r; }\nint m() { struct S s, t; return 0; }"
+        ).expect_err("syntax error");
+
+        // 構文エラーを位置付きで複数報告し、正しい部分は意味解析も行う
+        assert_eq!(
+            errs,
+            vec![
+                "stdin:2:14: expected expression but found ';'".to_string(),
+                "stdin:4:12: expected expression but found ';'".to_string(),
+                "stdin:5:18: use of undeclared identifier \"c\"; did you mean \"a\"?".to_string(),
+                "stdin:7:8: expected ')' but found '{'".to_string(),
+                "stdin:9:12: unknown type name \"foo\"".to_string(),
+                "stdin:10:32: no member named \"m\" in struct S".to_string(),
+                "stdin:13:12: expected ';' but found '}'".to_string(),
+                "stdin:14:24: expected ';' but found number".to_string(),
+                "stdin:15:21: unexpected ',' after struct S".to_string(),
+                "stdin:8:30: assignment of read-only variable: \"x\"".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declaration_errors() {
+        let errs = compile(
+            "int g; int g; char g;\nint count(int n) { return n; }\nint f(int a) {\n  int a;\n  int b; int b[2];\n  return cout(1) + bb + xyzw;\n}\nint i; int main() { int i = 1; return i; }\nint h() { { int k = 1; int k = 2; return k; } }\nint p(int); char p(int);\nstruct S { int a; };\nstruct S { int c; };\nint q(int value) { { int idx; idx = 5; } return idx + valeu; }\nunion V { int a; }; union V { char c; };\nint r() { union V v; return v.b; }"
+        ).expect_err("declaration error");
+
+        // 再宣言は最初の宣言位置を付記し、未宣言の識別子は近い名前を提示する
+        assert_eq!(
+            errs,
+            vec![
+                "stdin:1:20: conflicting types for \"g\" (previous declaration at stdin:1:5)".to_string(),
+                "stdin:4:7: redefinition of \"a\" (previous declaration at stdin:3:11)".to_string(),
+                "stdin:5:14: redefinition of \"b\" (previous declaration at stdin:5:7)".to_string(),
+                "stdin:6:10: use of undeclared identifier \"cout\"; did you mean \"count\"?".to_string(),
+                "stdin:6:20: use of undeclared identifier \"bb\"; did you mean \"b\"?".to_string(),
+                "stdin:6:25: use of undeclared identifier \"xyzw\"".to_string(),
+                "stdin:9:28: redefinition of \"k\" (previous declaration at stdin:9:17)".to_string(),
+                "stdin:10:18: conflicting types for \"p\" (previous declaration at stdin:10:5)".to_string(),
+                "stdin:12:8: redefinition of 'struct S' (previous definition at stdin:11:8)".to_string(),
+                "stdin:13:49: use of undeclared identifier \"idx\"".to_string(),
+                "stdin:13:55: use of undeclared identifier \"valeu\"; did you mean \"value\"?".to_string(),
+                "stdin:14:27: redefinition of 'union V' (previous definition at stdin:14:7)".to_string(),
+                "stdin:15:31: no member named \"b\" in union V".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bit_field_errors() {
+        let errs = compile(
+            "struct S {\n  int a:33;\n  int b:-1;\n  char c:0;\n  int *d:2;\n  char e:9;\n};\nint main() { return 0; }"
+        ).expect_err("bit-field error");
+
+        // 不正な幅、型のビットフィールドは位置付きで報告
+        assert_eq!(
+            errs,
+            vec![
+                "stdin:2:8: width of bit-field \"a\" (33 bits) exceeds its type (32 bits)".to_string(),
+                "stdin:3:8: negative width in bit-field \"b\"".to_string(),
+                "stdin:4:9: named bit-field \"c\" has zero width".to_string(),
+                "stdin:5:9: bit-field \"d\" has non-integral type".to_string(),
+                "stdin:6:9: width of bit-field \"e\" (9 bits) exceeds its type (8 bits)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_void_pointer_errors() {
+        let errs = compile(
+            "int main() {\n  int a[2]; void *p; int *q;\n  p = a; q = p;\n  *p;\n  p = p + 1; p++; --p; p -= 1;\n  return p[0] + *q;\n}"
+        ).expect_err("void pointer error");
+
+        // void*は参照、算術演算できない（他のポインタへの変換のみ可能）
+        assert_eq!(
+            errs,
+            vec![
+                "stdin:4:3: dereferencing 'void *' pointer".to_string(),
+                "stdin:5:9: arithmetic on 'void *' pointer".to_string(),
+                "stdin:5:15: arithmetic on 'void *' pointer".to_string(),
+                "stdin:5:19: arithmetic on 'void *' pointer".to_string(),
+                "stdin:5:26: arithmetic on 'void *' pointer".to_string(),
+                "stdin:6:11: subscript of 'void *' pointer".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pointer_arith_errors() {
+        // ポインタと整数の加減算、同じ型のポインタ同士の減算は許可
+        assert!(compile("int main() { int a[2]; int *p; p = a + 1; p = 1 + p; p += 1; p -= 1; return (p - a) + (a - p); }").is_ok());
+
+        let errs = compile(
+            "int main() {\n  int a[2]; int *p; int *q; char *c;\n  p = p + q;\n  p += a;\n  c = 1 - c;\n  return p - c;\n}"
+        ).expect_err("pointer arithmetic error");
+
+        // ポインタ同士の加算、整数からのポインタの減算、型の異なるポインタ同士の減算は演算子の位置で報告
+        assert_eq!(
+            errs,
+            vec![
+                "stdin:3:9: invalid operands to binary +: int* and int*".to_string(),
+                "stdin:4:5: invalid operands to binary +=: int* and int[2]".to_string(),
+                "stdin:5:9: invalid operands to binary -: int and char*".to_string(),
+                "stdin:6:12: invalid operands to binary -: int* and char*".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warnings() {
+        let src = concat!(
+            "static int helper(int x) { return x; }\n",
+            "int f(int a, int b) {\n",
+            "  int unused; int c; char ch; int *p = &c;\n",
+            "  c = a; ch = c; ch = 5;\n",
+            "  if (c = 2) { return 1; }\n",
+            "  if ((c = 3)) { return 2; }\n",
+            "  while (p == 5) { break; c = 1; }\n",
+            "  return ch;\n",
+            "}\n",
+            "int g(int x) { if (x) { return 1; } }\n",
+            "int main() { return f(1, 2) + g(0); }"
+        );
+        let opts = |flags: &[&str]| {
+            let mut opts = WarningOptions::default();
+            flags.iter().for_each(|f| opts.apply(f).unwrap());
+            opts
+        };
+
+        // 警告のみではコンパイルは成功する
+        assert!(compile_with(src, &opts(&["-Wall", "-Wextra"])).is_ok());
+
+        // 既定の警告
+        assert_eq!(
+            compile_with(src, &opts(&["-Werror"])).expect_err("werror"),
+            vec![
+                "stdin:7:12: comparison between pointer and integer [-Werror=pointer-integer-compare]".to_string(),
+                "stdin:10:5: control reaches end of non-void function \"g\" [-Werror=return-type]".to_string(),
+            ]
+        );
+
+        // -Wall、-Wextra（個別に無効化可能）
+        assert_eq!(
+            compile_with(src, &opts(&["-Wall", "-Wextra", "-Wno-return-type", "-Werror"])).expect_err("werror"),
+            vec![
+                "stdin:4:13: conversion from int to char may change value [-Werror=conversion]".to_string(),
+                "stdin:5:7: suggest parentheses around assignment used as truth value [-Werror=parentheses]".to_string(),
+                "stdin:7:12: comparison between pointer and integer [-Werror=pointer-integer-compare]".to_string(),
+                "stdin:7:27: code will never be executed [-Werror=unreachable-code]".to_string(),
+                "stdin:1:12: \"helper\" defined but not used [-Werror=unused-function]".to_string(),
+                "stdin:2:18: unused parameter \"b\" [-Werror=unused-parameter]".to_string(),
+                "stdin:3:7: unused variable \"unused\" [-Werror=unused-variable]".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_excess_initializers() {
+        let src = "int a[2] = {1, 2, 3};\nchar s[2] = \"hello\";\nint b[2][2] = {1, 2, 3, 4, 5};\nchar t[3] = \"abc\";\nint main() { return a[1] + s[1] + b[1][1] + t[2]; }";
+
+        // 配列に収まらない初期化子は警告し、超過分は配置しない（終端文字のみ収まらない文字列は許容）
+        assert!(compile(src).is_ok());
+        let mut opts = WarningOptions::default();
+        opts.apply("-Werror").unwrap();
+        assert_eq!(
+            compile_with(src, &opts).expect_err("werror"),
+            vec![
+                "stdin:1:10: excess elements in array initializer [-Werror=excess-initializers]".to_string(),
+                "stdin:2:11: initializer-string for array is too long [-Werror=excess-initializers]".to_string(),
+                "stdin:3:13: excess elements in array initializer [-Werror=excess-initializers]".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bounds_warnings() {
+        let src = concat!(
+            "int main() {\n",
+            "  int a[4]; int m[2][3]; int *p; int x = 1;\n",
+            "  a[3] = 1; p = &a[4]; p = a + 4; p = &m[1][3];\n",
+            "  a[4] = 1; x = a[-1] + m[1][3];\n",
+            "  p = a + 5; p = a - 1; p = &m[2][0];\n",
+            "  x = x / 0; x = x % (1 - 1); x /= 0;\n",
+            "  return x / 2;\n",
+            "}\n"
+        );
+        let mut opts = WarningOptions::default();
+        opts.apply("-Werror").unwrap();
+
+        // 末尾の次の要素のアドレス、ポインタは許容
+        assert_eq!(
+            compile_with(src, &opts).expect_err("werror"),
+            vec![
+                "stdin:4:4: array index 4 is past the end of the array (which contains 4 elements) [-Werror=array-bounds]".to_string(),
+                "stdin:4:18: array index -1 is before the beginning of the array [-Werror=array-bounds]".to_string(),
+                "stdin:4:29: array index 3 is past the end of the array (which contains 3 elements) [-Werror=array-bounds]".to_string(),
+                "stdin:5:9: pointer arithmetic with offset 5 is outside the bounds of the array (which contains 4 elements) [-Werror=array-bounds]".to_string(),
+                "stdin:5:20: pointer arithmetic with offset -1 is outside the bounds of the array (which contains 4 elements) [-Werror=array-bounds]".to_string(),
+                "stdin:5:31: array index 2 is past the end of the array (which contains 2 elements) [-Werror=array-bounds]".to_string(),
+                "stdin:6:9: division by zero [-Werror=div-by-zero]".to_string(),
+                "stdin:6:20: modulo by zero [-Werror=div-by-zero]".to_string(),
+                "stdin:6:33: division by zero [-Werror=div-by-zero]".to_string(),
+            ]
+        );
+
+        // 警告のみではコンパイルは成功する
+        assert!(compile_with(src, &WarningOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_dump_cfg() {
+        let src = "int f(int n) { int s = 0; while (n > 0 && s < 10) { s += n--; } return s; }\nint main() { return f(3); }";
+        assert_eq!(
+            compile_to(src, &WarningOptions::default(), Emit::CfgDot).unwrap(),
+            concat!(
+                "digraph \"f\" {\n",
+                "    node [shape=box, fontname=\"monospace\"];\n",
+                "    B0 [label=\"B0 (entry)\\ls = 0\\l\"];\n",
+                "    B1 [label=\"B1 (exit)\\l\"];\n",
+                "    B2 [label=\"B2\\lif n > 0\\l\"];\n",
+                "    B3 [label=\"B3\\ls += n--\\l\"];\n",
+                "    B4 [label=\"B4\\lreturn s\\l\"];\n",
+                "    B5 [label=\"B5\\lif s < 10\\l\"];\n",
+                "    B0 -> B2;\n",
+                "    B2 -> B5 [label=\"T\"];\n",
+                "    B2 -> B4 [label=\"F\"];\n",
+                "    B3 -> B2;\n",
+                "    B4 -> B1;\n",
+                "    B5 -> B3 [label=\"T\"];\n",
+                "    B5 -> B4 [label=\"F\"];\n",
+                "}\n",
+                "digraph \"main\" {\n",
+                "    node [shape=box, fontname=\"monospace\"];\n",
+                "    B0 [label=\"B0 (entry)\\lreturn f(3)\\l\"];\n",
+                "    B1 [label=\"B1 (exit)\\l\"];\n",
+                "    B0 -> B1;\n",
+                "}\n",
+            )
+        );
+
+        // エラーがあれば出力しない
+        assert!(compile_to("int main() { return x; }", &WarningOptions::default(), Emit::CfgDot).is_err());
+    }
+
+    #[test]
+    fn test_emit_ir() {
+        let src = "int g = 2;\nint f(int n) { int s = 0; while (n > 0) { s += n--; } return s * g; }\nint main() { return f(3); }";
+        assert_eq!(
+            compile_to(src, &WarningOptions::default(), Emit::Ir).unwrap(),
+            concat!(
+                "object @g: size 4, align 4, data, global\n",
+                "  +0: 4 bytes 2\n",
+                "func @f(local 8) -> scalar, frame 16, global\n",
+                "bb0:\n",
+                "  %0:ptr = addr local 16\n",
+                "  %1:i64 = const 0\n",
+                "  store i32 %1, %0\n",
+                "  jmp bb2\n",
+                "bb1:\n",
+                "  ret\n",
+                "bb2:\n",
+                "  %2:ptr = addr local 8\n",
+                "  %3:i64 = load i32 %2\n",
+                "  %4:i64 = const 0\n",
+                "  %5:i64 = gt %3, %4\n",
+                "  br %5, bb3, bb4\n",
+                "bb3:\n",
+                "  %6:ptr = addr local 16\n",
+                "  %7:i64 = load i32 %6\n",
+                "  %8:ptr = addr local 8\n",
+                "  %9:i64 = load i32 %8\n",
+                "  %10:i64 = const 1\n",
+                "  %11:i64 = sub %9, %10\n",
+                "  store i32 %11, %8\n",
+                "  %12:i64 = add %7, %9\n",
+                "  store i32 %12, %6\n",
+                "  jmp bb2\n",
+                "bb4:\n",
+                "  %13:ptr = addr local 16\n",
+                "  %14:i64 = load i32 %13\n",
+                "  %15:ptr = addr global @g\n",
+                "  %16:i64 = load i32 %15\n",
+                "  %17:i64 = mul %14, %16\n",
+                "  ret %17\n",
+                "bb5:\n",
+                "  jmp bb1\n",
+                "func @main() -> scalar, frame 0, global\n",
+                "bb0:\n",
+                "  %0:i64 = const 3\n",
+                "  %1:i64 = call @f(%0)\n",
+                "  ret %1\n",
+                "bb1:\n",
+                "  %2:i64 = const 0\n",
+                "  ret %2\n",
+                "bb2:\n",
+                "  jmp bb1\n",
+            )
+        );
+
+        // エラーがあれば出力しない
+        assert!(compile_to("int main() { return x; }", &WarningOptions::default(), Emit::Ir).is_err());
+    }
+
+    #[test]
+    fn test_struct_abi() {
+        // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
+        if Config::is_mac() {
+            return;
+        }
+
+        // gccでコンパイルした関数と構造体を相互に受け渡し
+        let lib = concat!(
+            "struct Small { int a; int b; };\n",
+            "struct Mixed { char c; int x; char d; };\n",
+            "struct Big { int a; int b; int c; int d; int e; int f; };\n",
+            "struct Small lib_small(int a, int b) { struct Small s = { a, b }; return s; }\n",
+            "int lib_small_sum(struct Small s) { return s.a + s.b; }\n",
+            "struct Mixed lib_mixed(char c, int x, char d) { struct Mixed m = { c, x, d }; return m; }\n",
+            "int lib_mixed_sum(struct Mixed m) { return m.c + m.x + m.d; }\n",
+            "struct Big lib_big(int v) { struct Big b = { v, v + 1, v + 2, v + 3, v + 4, v + 5 }; return b; }\n",
+            "int lib_big_sum(struct Big b) { return b.a + b.b + b.c + b.d + b.e + b.f; }\n",
+            "int lib_many(int a, int b, int c, int d, int e, struct Small s, struct Big g, int h) { return a + b + c + d + e + s.a + s.b + g.f + h; }\n",
+            "struct Small rcc_small(int a, int b);\n",
+            "int rcc_big_sum(struct Big b);\n",
+            "struct Big rcc_big(int v);\n",
+            "int lib_callback(void) { struct Small s = rcc_small(3, 4); struct Big b = rcc_big(10); return s.a * s.b + rcc_big_sum(b) + b.f; }\n",
+        );
+        let src = concat!(
+            "struct Small { int a; int b; };\n",
+            "struct Mixed { char c; int x; char d; };\n",
+            "struct Big { int a; int b; int c; int d; int e; int f; };\n",
+            "struct Small lib_small(int a, int b);\n",
+            "int lib_small_sum(struct Small s);\n",
+            "struct Mixed lib_mixed(char c, int x, char d);\n",
+            "int lib_mixed_sum(struct Mixed m);\n",
+            "struct Big lib_big(int v);\n",
+            "int lib_big_sum(struct Big b);\n",
+            "int lib_many(int a, int b, int c, int d, int e, struct Small s, struct Big g, int h);\n",
+            "int lib_callback();\n",
+            "struct Small rcc_small(int a, int b) { struct Small s; s.a = a; s.b = b; return s; }\n",
+            "int rcc_big_sum(struct Big b) { return b.a + b.b + b.c + b.d + b.e + b.f; }\n",
+            "struct Big rcc_big(int v) { struct Big b; b.a = v; b.b = v; b.c = v; b.d = v; b.e = v; b.f = v + 1; return b; }\n",
+            "int main() {\n",
+            "  struct Small s;\n",
+            "  struct Mixed m;\n",
+            "  struct Big g;\n",
+            "  s = lib_small(2, 5);\n",
+            "  if (s.a != 2) return 1;\n",
+            "  if (lib_small_sum(s) != 7) return 2;\n",
+            "  m = lib_mixed(1, 20, 3);\n",
+            "  if (m.x != 20) return 3;\n",
+            "  if (m.d != 3) return 4;\n",
+            "  if (lib_mixed_sum(m) != 24) return 5;\n",
+            "  g = lib_big(1);\n",
+            "  if (g.f != 6) return 6;\n",
+            "  if (lib_big_sum(g) != 21) return 7;\n",
+            "  if (lib_many(1, 2, 3, 4, 5, s, g, 8) != 36) return 8;\n",
+            "  if (lib_callback() != 84) return 9;\n",
+            "  if (lib_big_sum(lib_big(2)) != 27) return 10;\n",
+            "  return 0;\n",
+            "}\n",
+        );
+        let inst = compile(src).expect("compile error");
+        fs::write("abi_lib.c", lib).expect("write error");
+        fs::write("abi_main.s", inst).expect("write error");
+        let out = Command::new("gcc")
+            .args(["-no-pie", "abi_main.s", "abi_lib.c", "-o", "abi_test"])
+            .output()
+            .expect("gcc error");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let status = Command::new("./abi_test").status().expect("exec error");
+
+        // ファイル削除
+        let _ = fs::remove_file("abi_lib.c");
+        let _ = fs::remove_file("abi_main.s");
+        let _ = fs::remove_file("abi_test");
+        assert_eq!(Some(0), status.code());
+    }
+
+    // gccでコンパイル、実行した結果と比較
+    fn eval_gcc(src: &str, name: &str) -> Option<i32> {
+        let (c, bin) = (format!("{}.c", name), format!("{}_gcc", name));
+        fs::write(&c, src).expect("write error");
+        let out = Command::new("gcc").args(["-w", &c, "-o", &bin]).output().expect("gcc error");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        Command::new(format!("./{}", bin)).status().expect("exec error").code()
+    }
+
+    fn eval_rcc(src: &str, name: &str) -> Option<i32> {
+        let (s, bin) = (format!("{}.s", name), format!("{}_rcc", name));
+        fs::write(&s, compile(src).expect("compile error")).expect("write error");
+        let out = Command::new("gcc").args(["-no-pie", &s, "-o", &bin]).output().expect("gcc error");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        Command::new(format!("./{}", bin)).status().expect("exec error").code()
+    }
+
+    #[test]
+    fn test_array_with_gcc() {
+        // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
+        if Config::is_mac() {
+            return;
+        }
+
+        let data = [
+            "int main() { int a[2][3][4]; int i; int j; int k; for (i = 0; i < 2; i++) { for (j = 0; j < 3; j++) { for (k = 0; k < 4; k++) { a[i][j][k] = i * 100 + j * 10 + k; } } } return a[1][2][3] - a[0][1][2] + a[1][0][1]; }",
+            "int main() { int a[3][2]; int *p; a[1][1] = 7; p = &a[1][1]; return *p + (&a[2][0] - &a[0][0]); }",
+            "int sum(int *p, int n) { int s = 0; int i; for (i = 0; i < n; i++) { s = s + p[i]; } return s; } int main() { int a[3][4]; int i; int j; for (i = 0; i < 3; i++) { for (j = 0; j < 4; j++) { a[i][j] = i + j; } } return sum(a[2], 4); }",
+            "int f(int m[][3], int n) { int s = 0; int i; for (i = 0; i < n; i++) { s = s + m[i][2]; } return s; } int main() { int a[2][3]; a[0][2] = 5; a[1][2] = 7; return f(a, 2); }",
+            "int g(int m[2][2][2]) { return m[1][1][0] + m[0][1][1]; } int main() { int a[2][2][2]; a[1][1][0] = 3; a[0][1][1] = 4; return g(a); }",
+            "int g(int a[], int n) { return a[n - 1] + sizeof(a); } int main() { int x[4]; x[3] = 9; return g(x, 4); }",
+            "struct P { int x; char c; }; int main() { struct P a[3]; int i; for (i = 0; i < 3; i++) { a[i].x = i * 3; a[i].c = i; } return a[2].x + a[1].c + sizeof(a); }",
+            "struct P { int x; int y; }; int main() { struct P a[2]; struct P *p = a; p[1].y = 4; (p + 1)->x = 3; return a[1].x * a[1].y; }",
+            "struct S { int a[3]; int n; }; int main() { struct S s; s.a[1] = 4; s.a[2] = 5; s.a[1]++; return s.a[1] * s.a[2]; }",
+            "int main() { int x = 1; int y = 2; int z = 3; int *a[3]; a[0] = &x; a[1] = &y; a[2] = &z; *a[1] = 10; return *a[0] + *a[1] + *a[2] + sizeof(a); }",
+            "int main() { char *s[2]; s[0] = \"ab\"; s[1] = \"cd\"; return s[1][1]; }",
+            "struct Q { int v; }; int main() { struct Q q1; struct Q q2; struct Q *a[2]; a[0] = &q1; a[1] = &q2; a[1]->v = 8; q1.v = 2; return a[0]->v + a[1]->v; }",
+            "int a[2][3] = {{1, 2, 3}, {4, 5, 6}}; int *p = &a[1][2]; int main() { return a[1][0] + *p; }",
+            "struct P { int x; int y; }; struct P ps[2] = {{1, 2}, {3, 4}}; int main() { return ps[1].x * 10 + ps[0].y; }",
+            "int x = 3; int y = 4; int *ptrs[2] = {&x, &y}; int main() { return *ptrs[0] + *ptrs[1]; }",
+        ];
+        data.iter().for_each(|src| assert_eq!(eval_gcc(src, "cmp"), eval_rcc(src, "cmp"), "\tFail Test: inst: {}", src));
+
+        // ファイル削除
+        ["cmp.c", "cmp.s", "cmp_gcc", "cmp_rcc"].iter().for_each(|f| {
+            let _ = fs::remove_file(f);
+        });
+    }
+
+    #[test]
+    fn test_main_args() {
+        // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
+        if Config::is_mac() {
+            return;
+        }
+
+        let src = concat!(
+            "int strlen(char *s);\n",
+            "int atoi(char *s);\n",
+            "int main(int argc, char **argv, char **envp) {\n",
+            "  int n = 0;\n",
+            "  int i;\n",
+            "  if (argv[argc] != 0) return 100;\n",
+            "  if (envp[0] == 0) return 101;\n",
+            "  for (i = 1; i < argc; i++) { n = n + strlen(argv[i]) * atoi(argv[i]); }\n",
+            "  if (argc == 1) return 102;\n",
+            "  n = n + argc;\n",
+            "  return n;\n",
+            "}\n",
+        );
+        fs::write("main_args.s", compile(src).expect("compile error")).expect("write error");
+        let out = Command::new("gcc")
+            .args(["-no-pie", "main_args.s", "-o", "main_args"])
+            .output()
+            .expect("gcc error");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+        // 引数なし、引数あり、環境変数なし
+        let run = |args: &[&str], clear_env: bool| {
+            let mut cmd = Command::new("./main_args");
+            cmd.args(args);
+            if clear_env {
+                cmd.env_clear();
+            }
+            cmd.status().expect("exec error")
+        };
+        assert_eq!(Some(102), run(&[], false).code());
+        assert_eq!(Some(30), run(&["12", "3"], false).code());
+        assert_eq!(Some(101), run(&["1"], true).code());
+
+        // ファイル削除
+        let _ = fs::remove_file("main_args.s");
+        let _ = fs::remove_file("main_args");
+    }
+
+    #[test]
+    fn test_bit_field_with_gcc() {
+        // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
+        if Config::is_mac() {
+            return;
+        }
+
+        let data = [
+            "struct S { char a; int b:4; int c:24; char d:3; unsigned e:5; unsigned :0; unsigned f:32; }; int main() { return sizeof(struct S) * 10 + _Alignof(struct S); }",
+            "struct S { char a:1; int :0; char b:2; int :9; }; int main() { return sizeof(struct S) * 10 + _Alignof(struct S); }",
+            "struct S { int a:3; unsigned b:3; char c:4; }; int main() { struct S s; s.a = 5; s.b = 13; s.c = 9; return (s.a + 10) * 100 + s.b * 10 + s.c + 8; }",
+            "struct S { char a; int b:4; int c:24; }; int main() { struct S s; struct S *p = &s; s.a = 1; s.c = 0; p->b = 7; p->b = p->b + 1; s.c--; return (s.b + 8) * 10 + s.a + (s.c == -1) * 100; }",
+            "struct S { unsigned lo:4; unsigned hi:4; }; int main() { struct S s; int x; s.lo = 15; s.hi = 0; s.lo += 2; x = (s.hi = 18); return s.lo * 100 + s.hi * 10 + x; }",
+            "struct R { char tag; unsigned lo:3; unsigned hi:5; int v:12; int :0; char c:2; }; struct R g = { 7, 5, 17, -100, -1 }; int main() { return g.tag + g.lo * 2 + g.hi * 4 + (g.v == -100) * 8 + (g.c == -1) * 16 + sizeof(g); }",
+        ];
+        data.iter().for_each(|src| assert_eq!(eval_gcc(src, "cmp_bit"), eval_rcc(src, "cmp_bit"), "\tFail Test: inst: {}", src));
+
+        // gccでコンパイルしたコードと、ビットフィールドを含む構造体を共有
+        let lib = concat!(
+            "struct Reg { char id; unsigned en:1; unsigned mode:3; int bias:6; unsigned :0; unsigned cnt:20; };\n",
+            "int lib_check(struct Reg *r) { return r->id == 9 && r->en == 1 && r->mode == 5 && r->bias == -17 && r->cnt == 1000000; }\n",
+            "void lib_set(struct Reg *r) { r->mode = 2; r->bias = 31; r->cnt = 12345; }\n",
+        );
+        let src = concat!(
+            "struct Reg { char id; unsigned en:1; unsigned mode:3; int bias:6; unsigned :0; unsigned cnt:20; };\n",
+            "int lib_check(struct Reg *r);\n",
+            "void lib_set(struct Reg *r);\n",
+            "int main() {\n",
+            "  struct Reg r;\n",
+            "  r.id = 9; r.en = 1; r.mode = 5; r.bias = -17; r.cnt = 1000000;\n",
+            "  if (lib_check(&r) != 1) return 1;\n",
+            "  lib_set(&r);\n",
+            "  if (r.id != 9) return 2;\n",
+            "  if (r.en != 1) return 3;\n",
+            "  if (r.mode != 2) return 4;\n",
+            "  if (r.bias != 31) return 5;\n",
+            "  if (r.cnt != 12345) return 6;\n",
+            "  return sizeof(r) != 8;\n",
+            "}\n",
+        );
+        fs::write("bit_lib.c", lib).expect("write error");
+        fs::write("bit_main.s", compile(src).expect("compile error")).expect("write error");
+        let out = Command::new("gcc")
+            .args(["-no-pie", "bit_main.s", "bit_lib.c", "-o", "bit_test"])
+            .output()
+            .expect("gcc error");
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let status = Command::new("./bit_test").status().expect("exec error");
+        assert_eq!(Some(0), status.code());
+
+        // ファイル削除
+        ["cmp_bit.c", "cmp_bit.s", "cmp_bit_gcc", "cmp_bit_rcc", "bit_lib.c", "bit_main.s", "bit_test"].iter().for_each(|f| {
+            let _ = fs::remove_file(f);
+        });
+    }
+
+    #[test]
+    fn test_union_with_gcc() {
+        // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
+        if Config::is_mac() {
+            return;
+        }
+
+        let data = [
+            "union U { char c; int i; char s[5]; }; union E { char a; }; int main() { return sizeof(union U) * 10 + _Alignof(union U) + sizeof(union E) * 100; }",
+            "union U { int i; char c[4]; }; int main() { union U u; u.i = 258; return u.c[0] * 10 + u.c[1]; }",
+            "union B { int a:3; unsigned b:5; char c; }; union Z { int :3; char c; }; int main() { union B u; u.c = 0; u.b = 13; return sizeof(union B) * 10 + (u.a + 3) + u.c + sizeof(union Z) * 100; }",
+            "union U { int i; char c[6]; }; struct S { char tag; union U u; }; int main() { struct S s; union U *p = &s.u; p->i = 0; s.u.c[5] = 9; return sizeof(struct S) * 10 + s.u.c[5] + _Alignof(struct S); }",
+            "union U { int i; char c[4]; }; union U g = { 770 }; union U h; int main() { h.c[0] = 5; return g.c[0] * 100 + g.c[1] * 10 + h.i; }",
+            "struct P { char x; char y; }; union U { struct P p; int i; }; int main() { union U a; union U b; a.i = 0; a.p.y = 3; b = a; return b.i / 256 + sizeof(union U); }",
+        ];
+        data.iter().for_each(|src| assert_eq!(eval_gcc(src, "cmp_union"), eval_rcc(src, "cmp_union"), "\tFail Test: inst: {}", src));
+
+        // ファイル削除
+        ["cmp_union.c", "cmp_union.s", "cmp_union_gcc", "cmp_union_rcc"].iter().for_each(|f| {
+            let _ = fs::remove_file(f);
+        });
+    }
+
+    #[test]
+    fn test_func_pointer_with_gcc() {
+        // Macの場合、位置独立形式でバイナリを生成できないので、Linux環境下でのみテスト
+        if Config::is_mac() {
+            return;
+        }
+
+        let data = [
+            "struct O { int (*op)(int, int); }; int sub(int a, int b) { return a - b; } int main() { struct O o; struct O *p = &o; o.op = sub; int r = p->op(20, 5); return r; }",
+            "struct O { int (*op)(int, int); }; int sub(int a, int b) { return a - b; } int main() { struct O o; o.op = sub; return o.op(20, 5) + (o.op)(2, 1); }",
+            "struct O { int (*op)(int, int); }; int sub(int a, int b) { return a - b; } int main() { struct O o; o.op = sub; return (*o.op)(20, 5); }",
+            "int sub(int a, int b) { return a - b; } int main() { return (&sub)(20, 5); }",
+            "struct O { int (*op)(int, int); }; int sub(int a, int b) { return a - b; } int call(struct O *p) { return p->op(7, 2); } int main() { struct O o; o.op = sub; return call(&o); }",
+            "struct O { int n; int (*ops[2])(int, int); }; int add(int a, int b) { return a + b; } int sub(int a, int b) { return a - b; } int main() { struct O o; o.ops[0] = add; o.ops[1] = sub; return o.ops[0](3, 4) * o.ops[1](9, 2); }",
+        ];
+        data.iter().for_each(|src| assert_eq!(eval_gcc(src, "cmp_fp"), eval_rcc(src, "cmp_fp"), "\tFail Test: inst: {}", src));
+
+        // ファイル削除
+        ["cmp_fp.c", "cmp_fp.s", "cmp_fp_gcc", "cmp_fp_rcc"].iter().for_each(|f| {
+            let _ = fs::remove_file(f);
+        });
+    }
 }
@@ -1,12 +1,23 @@
-use ast::{AstTree, AstType};
+use ast::{decay, AstTree, AstType};
+use cfg::{BasicBlock, Cfg, ENTRY};
 use std::result::Result;
-use symbol::{Scope, SymbolTable};
+use symbol::{source_name, type_name, Qualifier, Scope, Signature, Storage, Symbol, SymbolTable};
 use symbol::{Structure, Type};
+use warning::{Warning, WarningKind};
 
 #[doc = "意味解析部"]
 pub struct Semantic<'a> {
     ast: &'a AstTree,
     sym_table: &'a SymbolTable,
+    warnings: Vec<Warning>,
+}
+
+// 式の値カテゴリ
+#[derive(Debug, PartialEq)]
+enum ValueCategory {
+    Modifiable,    // 変更可能な左辺値
+    NonModifiable, // 変更できない左辺値（配列、関数指定子、文字列リテラル）
+    Rvalue,        // 右辺値
 }
 
 // 解析結果返却マクロ
@@ -23,15 +34,20 @@ macro_rules! analyzed {
 impl<'a> Semantic<'a> {
     pub fn new(a: &'a AstTree, s: &'a SymbolTable) -> Self {
         Semantic {
-            ast: a, sym_table: s,
+            ast: a, sym_table: s, warnings: vec![],
         }
     }
 
+    // 警告取得
+    pub fn get_warnings(&self) -> &Vec<Warning> {
+        &self.warnings
+    }
+
     // 解析開始
     pub fn exec(&mut self) -> Result<(), Vec<String>> {
         let tree = self.ast.get_tree();
-        let errs = tree.iter().fold(Vec::<String>::new(), |mut init, t| {
-            match self.analysis(&t) {
+        let mut errs = tree.iter().fold(Vec::<String>::new(), |mut init, t| {
+            match self.analysis(t) {
                 Err(ref mut r) => {
                     init.append(r);
                     init
@@ -39,6 +55,17 @@ impl<'a> Semantic<'a> {
                 Ok(_) => init,
             }
         });
+        tree.iter().for_each(|t| self.analysis_func_pointer(t, &mut errs));
+        tree.iter().for_each(|t| self.analysis_const_init(t, &mut errs));
+        tree.iter().for_each(|t| self.analysis_static_assert(t, &mut errs));
+        tree.iter().for_each(|t| self.analysis_bit_field_address(t, &mut errs));
+        tree.iter().for_each(|t| self.analysis_return_type(t, &mut errs));
+        tree.iter().for_each(|t| self.analysis_void_value(t, false, &mut errs));
+
+        let mut warns = vec![];
+        tree.iter().for_each(|t| self.analysis_fall_through(t, &mut warns));
+        tree.iter().for_each(|t| self.analysis_uninitialized(t, &mut warns));
+        self.warnings = warns;
         analyzed!(errs)
     }
 
@@ -48,12 +75,12 @@ impl<'a> Semantic<'a> {
             AstType::FuncDef(ref t, ref s, ref n, ref a, ref stmt) => {
                 self.analysis_funcdef(t, s, n, a, stmt)
             }
-            AstType::FuncCall(ref v, ref a) => self.analysis_funccall(v, a),
+            AstType::FuncCall(ref v, ref a, ref pos) => self.analysis_funccall(v, a, pos),
             AstType::Argment(ref args) => self.analysis_argment(args),
             AstType::Statement(ref stmt) => self.analysis_statement(stmt),
             AstType::Global(ref glb) => self.analysis_global(glb),
-            AstType::Return(ref s) => self.analysis_return(s),
-            AstType::Variable(ref t, ref s, ref n) => self.analysis_variable(t, s, n),
+            AstType::Return(ref s, _) => self.analysis_return(s),
+            AstType::Variable(ref t, ref s, ref n, _) => self.analysis_variable(t, s, n),
             AstType::Plus(ref a, ref b)
             | AstType::Minus(ref a, ref b)
             | AstType::Multiple(ref a, ref b)
@@ -67,7 +94,7 @@ impl<'a> Semantic<'a> {
         &self,
         t: &Type,
         _s: &Structure,
-        _name: &str,
+        name: &str,
         args: &AstType,
         stmt: &AstType,
     ) -> Result<(), Vec<String>> {
@@ -81,18 +108,33 @@ impl<'a> Semantic<'a> {
         if let Err(ref mut e) = self.analysis(stmt) {
             errs.append(e);
         }
+
+        // 型修飾子の解析
+        let scope = Scope::Local(name.to_string());
+        self.analysis_qualifier(&scope, args, &mut errs);
+        self.analysis_qualifier(&scope, stmt, &mut errs);
         analyzed!(errs)
     }
 
     // 関数コール解析
-    fn analysis_funccall(&self, v: &AstType, _a: &AstType) -> Result<(), Vec<String>> {
+    //
+    // ()で宣言された関数はプロトタイプなしとして、実引数をチェックしない
+    fn analysis_funccall(&self, v: &AstType, a: &AstType, pos: &str) -> Result<(), Vec<String>> {
         let mut errs = vec![];
         match v {
-            AstType::Variable(ref _t, ref _s, ref n) => {
-                if self.sym_table.search(&Scope::Func, n).is_none() {
-                    errs.push(format!("Not define function name: {:?}", n));
+            // 関数ポインタ経由の呼び出し
+            _ if v.is_func_pointer() => {}
+            AstType::Variable(ref _t, ref _s, ref n, _) => match self.sym_table.search(&Scope::Func, n) {
+                Some(ref f) if !f.no_proto => {
+                    if let AstType::Argment(ref args) = *a {
+                        let callee = format!("{:?}", n);
+                        let note = format!(" (declared at {})", f.decl);
+                        self.check_signature_args(&f.signature(), &callee, &note, args, pos, &mut errs);
+                    }
                 }
-            }
+                Some(_) => {}
+                None => errs.push(format!("{}: Not define function name: {:?}", pos, n)),
+            },
             _ => errs.push(format!("AstType is not Variable: {:?}", v)),
         }
         analyzed!(errs)
@@ -100,7 +142,7 @@ impl<'a> Semantic<'a> {
 
     // 関数引数解析
     fn analysis_argment(&self, args: &[AstType]) -> Result<(), Vec<String>> {
-        let mut errs = args.iter().fold(Vec::<String>::new(), |mut acc, ref a| {
+        let mut errs = args.iter().fold(Vec::<String>::new(), |mut acc, a| {
             match self.analysis(a) {
                 Ok(_) => acc,
                 Err(ref mut e) => {
@@ -119,7 +161,7 @@ impl<'a> Semantic<'a> {
 
     // global解析
     fn analysis_global(&self, glb: &[AstType]) -> Result<(), Vec<String>> {
-        let errs = glb.iter().fold(Vec::<String>::new(), |mut acc, ref s| {
+        let mut errs = glb.iter().fold(Vec::<String>::new(), |mut acc, s| {
             match self.analysis(s) {
                 Ok(_) => acc,
                 Err(ref mut e) => {
@@ -128,12 +170,13 @@ impl<'a> Semantic<'a> {
                 }
             }
         });
+        glb.iter().for_each(|g| self.analysis_qualifier(&Scope::Global, g, &mut errs));
         analyzed!(errs)
     }
 
     // statement解析
     fn analysis_statement(&self, stmt: &[AstType]) -> Result<(), Vec<String>> {
-        let errs = stmt.iter().fold(Vec::<String>::new(), |mut acc, ref s| {
+        let errs = stmt.iter().fold(Vec::<String>::new(), |mut acc, s| {
             match self.analysis(s) {
                 Ok(_) => acc,
                 Err(ref mut e) => {
@@ -163,6 +206,648 @@ impl<'a> Semantic<'a> {
         analyzed!(errs)
     }
 
+    // 関数ポインタ解析
+    //
+    // 関数ポインタ経由の呼び出しの引数、関数ポインタへの代入をシグネチャと照合する
+    fn analysis_func_pointer(&self, ast: &AstType, errs: &mut Vec<String>) {
+        match ast {
+            AstType::FuncCall(ref f, ref args, ref pos) => {
+                if let (Some(sig), AstType::Argment(ref args)) = (f.func_signature(), &**args) {
+                    self.check_signature_args(&sig, "function pointer call", "", args, pos, errs);
+                }
+            }
+            AstType::Assign(ref a, ref b, ref pos) | AstType::Init(ref a, ref b, ref pos) => {
+                // 変数、メンバーは左辺の型、配列の要素は配列の要素型で照合
+                let lhs = match (&**a, a.value_type()) {
+                    (AstType::Indirect(_), _) => a.func_signature(),
+                    (_, Some((Type::Func(sig), Structure::Pointer))) => Some(*sig),
+                    _ => None,
+                };
+                if let (Some(ref sig), Some(ref r)) = (lhs, b.func_signature()) {
+                    if *sig != *r {
+                        errs.push(format!(
+                            "{}: incompatible function pointer types assigning to {:?}: expected {}, got {}",
+                            pos,
+                            Self::lvalue_name(a),
+                            type_name(&Type::Func(Box::new(sig.clone())), &Structure::Pointer),
+                            type_name(&Type::Func(Box::new(r.clone())), &Structure::Pointer)
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        ast.children().iter().for_each(|c| self.analysis_func_pointer(c, errs));
+    }
+
+    // 代入先の名前（診断用）
+    //
+    // メンバーはメンバー名、配列の要素は配列名とする
+    fn lvalue_name(a: &AstType) -> String {
+        match a {
+            AstType::Variable(_, _, ref n, _) => source_name(n).to_string(),
+            AstType::Member(_, _, _, ref n) => n.clone(),
+            AstType::Indirect(ref e) => match **e {
+                AstType::Plus(ref v, _) => Self::lvalue_name(v),
+                _ => Self::lvalue_name(e),
+            },
+            _ => String::new(),
+        }
+    }
+
+    // 静的記憶域の初期化子解析
+    //
+    // グローバル変数、static変数の初期化子は定数式（アドレス定数を含む）でなければならない
+    fn analysis_const_init(&self, ast: &AstType, errs: &mut Vec<String>) {
+        match ast {
+            AstType::Global(ref glb) => glb.iter().for_each(|g| match g {
+                AstType::Init(_, ref init, ref pos) => self.check_const_init(init, pos, errs),
+                _ => self.analysis_const_init(g, errs),
+            }),
+            AstType::StaticVar(ref v) => {
+                if let AstType::Init(_, ref init, ref pos) = **v {
+                    self.check_const_init(init, pos, errs);
+                }
+            }
+            _ => ast.children().iter().for_each(|c| self.analysis_const_init(c, errs)),
+        }
+    }
+
+    // 静的アサーション解析
+    //
+    // 構造体定義内のアサーションも対象とする
+    fn analysis_static_assert(&self, ast: &AstType, errs: &mut Vec<String>) {
+        match ast {
+            AstType::StaticAssert(ref expr, ref msg, ref pos) => match expr.eval_const() {
+                Ok(0) => errs.push(format!("{}: static assertion failed: \"{}\"", pos, msg)),
+                Ok(_) => {}
+                Err(e) => errs.push(format!("{}: expression in static assertion is not constant: {}", pos, e)),
+            },
+            AstType::Struct(_, ref members) => members.iter().for_each(|m| self.analysis_static_assert(m, errs)),
+            _ => ast.children().iter().for_each(|c| self.analysis_static_assert(c, errs)),
+        }
+    }
+
+    // ビットフィールドのアドレス解析
+    //
+    // ビットフィールドはバイト単位に配置されないので、アドレスを取得できない
+    fn analysis_bit_field_address(&self, ast: &AstType, errs: &mut Vec<String>) {
+        if let AstType::Address(ref a, ref pos) = *ast {
+            if let AstType::Member(_, _, Structure::BitField(_), ref name) = **a {
+                errs.push(format!("{}: cannot take address of bit-field {:?}", pos, name));
+            }
+        }
+        ast.children().iter().for_each(|c| self.analysis_bit_field_address(c, errs));
+    }
+
+    // void値の使用解析
+    //
+    // void型の式は値を捨てる位置（式文、for文の初期化・更新式、カンマ演算子の左辺、voidへのキャスト）でのみ使用可能
+    fn analysis_void_value(&self, ast: &AstType, used: bool, errs: &mut Vec<String>) {
+        // void値を生むのは関数呼び出しとキャストのみ（void変数は宣言時にエラー）
+        let is_void = ast.value_type().is_some_and(|(t, s)| Self::is_void(&t, &s));
+        match ast {
+            AstType::FuncCall(_, _, ref pos) if used && is_void => {
+                errs.push(format!("{}: void value not ignored as it ought to be", pos));
+            }
+            AstType::Cast(_, _, _, ref pos) if used && is_void => {
+                errs.push(format!("{}: void value not ignored as it ought to be", pos));
+            }
+            _ => {}
+        }
+        match ast {
+            AstType::Global(ref v) | AstType::Statement(ref v) => {
+                v.iter().for_each(|c| self.analysis_void_value(c, false, errs));
+            }
+            AstType::FuncDef(_, _, _, _, ref a) | AstType::Cast(Type::Void, Structure::Identifier, ref a, _) => {
+                self.analysis_void_value(a, false, errs);
+            }
+            AstType::If(ref a, ref b, ref c) => {
+                self.analysis_void_value(a, true, errs);
+                self.analysis_void_value(b, false, errs);
+                c.iter().for_each(|c| self.analysis_void_value(c, false, errs));
+            }
+            AstType::While(ref a, ref b) | AstType::Do(ref b, ref a) => {
+                self.analysis_void_value(a, true, errs);
+                self.analysis_void_value(b, false, errs);
+            }
+            AstType::For(ref a, ref b, ref c, ref d) => {
+                a.iter().chain(c.iter()).for_each(|e| self.analysis_void_value(e, false, errs));
+                b.iter().for_each(|e| self.analysis_void_value(e, true, errs));
+                self.analysis_void_value(d, false, errs);
+            }
+            AstType::FuncCall(_, ref a, _) => self.analysis_void_value(a, true, errs),
+            AstType::Comma(ref a, ref b) => {
+                self.analysis_void_value(a, false, errs);
+                self.analysis_void_value(b, used, errs);
+            }
+            AstType::Condition(ref a, ref b, ref c) => {
+                self.analysis_void_value(a, true, errs);
+                self.analysis_void_value(b, used, errs);
+                self.analysis_void_value(c, used, errs);
+            }
+            _ => ast.children().iter().for_each(|c| self.analysis_void_value(c, true, errs)),
+        }
+    }
+
+    // return文と戻り値型の解析
+    //
+    // 戻り値は関数の戻り値型へ変換可能でなければならない
+    fn analysis_return_type(&self, ast: &AstType, errs: &mut Vec<String>) {
+        if let AstType::FuncDef(ref t, ref s, ref name, _, ref stmt) = *ast {
+            self.check_return(&(t.clone(), s.clone()), name, stmt, errs);
+        }
+    }
+
+    // 関数終端への到達解析
+    //
+    // 戻り値のある関数（暗黙に0を返すmainを除く）で、return文を経ずに終端へ到達し得る場合に警告
+    fn analysis_fall_through(&self, ast: &AstType, warns: &mut Vec<Warning>) {
+        if let AstType::FuncDef(ref t, ref s, ref name, _, ref stmt) = *ast {
+            if !Self::is_void(t, s) && name != "main" && Self::can_complete(stmt) {
+                let pos = self.sym_table.search(&Scope::Func, name).map(|f| f.decl).unwrap_or_default();
+                let msg = format!("control reaches end of non-void function {:?}", name);
+                warns.push(Warning::new(WarningKind::ReturnType, pos, msg));
+            }
+        }
+    }
+
+    // 未初期化変数の使用解析
+    //
+    // 制御フローグラフ上で各ブロックの入口までに確実に代入済みの変数を求め（先行ブロックの出口の積集合）、
+    // 代入前に参照され得るローカル変数を警告する
+    // 仮引数、static変数、配列、構造体、アドレスを取得した変数は対象外
+    fn analysis_uninitialized(&self, ast: &AstType, warns: &mut Vec<Warning>) {
+        let cfg = match Cfg::build(ast) {
+            Some(cfg) => cfg,
+            None => return,
+        };
+        let scope = Scope::Local(cfg.name.clone());
+        let vars = self.uninit_targets(&scope, &cfg.name, ast);
+        if vars.is_empty() {
+            return;
+        }
+
+        // 入口以外のブロックの出口は全変数代入済みとし、不動点まで反復
+        let mut outs: Vec<Vec<bool>> = cfg.blocks.iter().map(|b| vec![b.id != ENTRY; vars.len()]).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in cfg.blocks.iter() {
+                let out = Self::uninit_block(b, &outs, &vars, &mut vec![]);
+                if out != outs[b.id] {
+                    outs[b.id] = out;
+                    changed = true;
+                }
+            }
+        }
+
+        let mut reads = vec![];
+        cfg.blocks.iter().for_each(|b| {
+            Self::uninit_block(b, &outs, &vars, &mut reads);
+        });
+        reads.into_iter().for_each(|(n, pos)| {
+            let msg = format!("variable {:?} may be used uninitialized", source_name(&n));
+            warns.push(Warning::new(WarningKind::Uninitialized, pos, msg));
+        });
+    }
+
+    // 未初期化解析の対象変数
+    fn uninit_targets(&self, scope: &Scope, name: &str, ast: &AstType) -> Vec<String> {
+        let params = self.sym_table.search(&Scope::Func, name).map(|f| f.members).unwrap_or_default();
+        let mut escaped = vec![];
+        Self::address_taken(ast, &mut escaped);
+        self.sym_table
+            .symbols()
+            .iter()
+            .filter(|s| s.scope == *scope && matches!(s.strt, Structure::Identifier | Structure::Pointer))
+            .filter(|s| s.storage != Storage::Static && !params.iter().any(|p| p.var == s.var) && !escaped.contains(&s.var))
+            .map(|s| s.var.clone())
+            .collect()
+    }
+
+    // アドレスを取得した変数（ポインタ経由で代入され得る）
+    fn address_taken(ast: &AstType, escaped: &mut Vec<String>) {
+        match ast {
+            AstType::Address(ref a, _) => match **a {
+                AstType::Variable(_, _, ref n, _) => escaped.push(n.clone()),
+                _ => Self::address_taken(a, escaped),
+            },
+            _ => ast.children().iter().for_each(|c| Self::address_taken(c, escaped)),
+        }
+    }
+
+    // ブロック内の代入済み変数の更新
+    //
+    // 入口（先行ブロックの出口の積集合）から評価し、出口の代入済み変数を返す
+    // 先行ブロックのない到達しないブロックは全変数代入済みとする
+    fn uninit_block(b: &BasicBlock, outs: &[Vec<bool>], vars: &[String], reads: &mut Vec<(String, String)>) -> Vec<bool> {
+        let mut defined = match b.id {
+            ENTRY => vec![false; vars.len()],
+            _ => (0..vars.len()).map(|i| b.preds.iter().all(|p| outs[*p][i])).collect(),
+        };
+        b.items.iter().for_each(|i| Self::uninit_expr(i, false, vars, &mut defined, reads));
+        b.cond.iter().for_each(|c| Self::uninit_expr(c, true, vars, &mut defined, reads));
+        defined
+    }
+
+    // 式の代入、参照の解析
+    //
+    // 値を捨てる位置の変数（宣言、式文）は参照としない
+    // &&、||の右辺、?:の分岐は評価されない場合がある為、代入済みとしない
+    fn uninit_expr(ast: &AstType, used: bool, vars: &[String], defined: &mut Vec<bool>, reads: &mut Vec<(String, String)>) {
+        let index = |n: &str| vars.iter().position(|v| v == n);
+        match ast {
+            // 最初に参照された位置で報告
+            AstType::Variable(_, _, ref n, ref pos) => {
+                if let Some(i) = index(n) {
+                    if used && !defined[i] && !reads.iter().any(|(r, _)| r == n) {
+                        reads.push((n.clone(), pos.clone()));
+                    }
+                }
+            }
+            AstType::Assign(ref a, ref b, _) | AstType::Init(ref a, ref b, _) => {
+                Self::uninit_expr(b, true, vars, defined, reads);
+                match **a {
+                    AstType::Variable(_, _, ref n, _) => index(n).iter().for_each(|i| defined[*i] = true),
+                    _ => Self::uninit_expr(a, true, vars, defined, reads),
+                }
+            }
+            AstType::Comma(ref a, ref b) => {
+                Self::uninit_expr(a, false, vars, defined, reads);
+                Self::uninit_expr(b, used, vars, defined, reads);
+            }
+            AstType::LogicalAnd(ref a, ref b) | AstType::LogicalOr(ref a, ref b) => {
+                Self::uninit_expr(a, true, vars, defined, reads);
+                Self::uninit_expr(b, true, vars, &mut defined.clone(), reads);
+            }
+            AstType::Condition(ref c, ref a, ref b) => {
+                Self::uninit_expr(c, true, vars, defined, reads);
+                let (mut da, mut db) = (defined.clone(), defined.clone());
+                Self::uninit_expr(a, used, vars, &mut da, reads);
+                Self::uninit_expr(b, used, vars, &mut db, reads);
+                *defined = da.iter().zip(db.iter()).map(|(a, b)| *a && *b).collect();
+            }
+            AstType::Cast(Type::Void, Structure::Identifier, ref a, _) => Self::uninit_expr(a, false, vars, defined, reads),
+            AstType::StaticVar(_) | AstType::StaticAssert(_, _, _) => {}
+            _ => ast.children().iter().for_each(|c| Self::uninit_expr(c, true, vars, defined, reads)),
+        }
+    }
+
+    // return文の戻り値チェック
+    fn check_return(&self, ret: &(Type, Structure), name: &str, ast: &AstType, errs: &mut Vec<String>) {
+        let is_void = Self::is_void(&ret.0, &ret.1);
+        match ast {
+            AstType::Return(_, ref pos) if is_void => {
+                errs.push(format!("{}: 'return' with a value in function {:?} returning void", pos, name));
+            }
+            AstType::Return(ref e, ref pos) => {
+                // 不明な型、void値の使用は報告済み
+                let unknown = |t: &Type| matches!(t, Type::Unknown(_));
+                if let Some(ref t) = Self::expr_type(e) {
+                    if !unknown(&ret.0) && !unknown(&t.0) && !Self::is_void(&t.0, &t.1) && !Self::is_compatible(ret, t, e) {
+                        errs.push(format!(
+                            "{}: incompatible return type in function {:?}: expected {}, got {}",
+                            pos,
+                            name,
+                            type_name(&ret.0, &ret.1),
+                            type_name(&t.0, &t.1)
+                        ));
+                    }
+                }
+            }
+            AstType::ReturnVoid(ref pos) if !is_void => {
+                errs.push(format!("{}: 'return' with no value in function {:?} returning {}", pos, name, type_name(&ret.0, &ret.1)));
+            }
+            _ => ast.children().iter().for_each(|c| self.check_return(ret, name, c, errs)),
+        }
+    }
+
+    // void型判定
+    fn is_void(t: &Type, s: &Structure) -> bool {
+        *t == Type::Void && *s == Structure::Identifier
+    }
+
+    // 文が正常に完了し得るか判定（後続の文へ制御が移るか）
+    //
+    // 条件が定数で真のループは、breakがなければ完了しない
+    fn can_complete(ast: &AstType) -> bool {
+        let always = |c: &AstType| c.const_value().is_some_and(|v| v != 0);
+        match ast {
+            AstType::Statement(ref stmt) => stmt.iter().all(Self::can_complete),
+            AstType::Return(_, _) | AstType::ReturnVoid(_) | AstType::Break() | AstType::Continue() => false,
+            AstType::If(_, ref a, ref b) => match **b {
+                Some(ref b) => Self::can_complete(a) || Self::can_complete(b),
+                None => true,
+            },
+            AstType::While(ref c, ref body) => !always(c) || Self::has_jump(body, &AstType::Break()),
+            AstType::For(_, ref c, _, ref body) => match **c {
+                Some(ref c) if !always(c) => true,
+                _ => Self::has_jump(body, &AstType::Break()),
+            },
+            AstType::Do(ref body, ref c) => {
+                let next = Self::can_complete(body) || Self::has_jump(body, &AstType::Continue());
+                (next && !always(c)) || Self::has_jump(body, &AstType::Break())
+            }
+            _ => true,
+        }
+    }
+
+    // ループ本体のbreak、continue有無判定（内側のループは除く）
+    fn has_jump(ast: &AstType, jump: &AstType) -> bool {
+        match ast {
+            _ if ast == jump => true,
+            AstType::While(_, _) | AstType::For(_, _, _, _) | AstType::Do(_, _) => false,
+            _ => ast.children().iter().any(|c| Self::has_jump(c, jump)),
+        }
+    }
+
+    // 初期化子の定数式チェック
+    fn check_const_init(&self, init: &AstType, pos: &str, errs: &mut Vec<String>) {
+        match init {
+            AstType::InitList(ref items) => items.iter().for_each(|i| self.check_const_init(i, pos, errs)),
+            // アドレス定数
+            AstType::StringLiteral(_, _)
+            | AstType::Address(_, _)
+            | AstType::Variable(_, Structure::Array(_), _, _)
+            | AstType::Variable(Type::Func(_), Structure::Identifier, _, _) => {}
+            _ => {
+                if let Err(e) = init.eval_const() {
+                    errs.push(format!("{}: initializer element is not constant: {}", pos, e));
+                }
+            }
+        }
+    }
+
+    // シグネチャと実引数の照合
+    //
+    // calleeは呼び出し先、noteは宣言位置等の補足（診断用）
+    fn check_signature_args(&self, sig: &Signature, callee: &str, note: &str, args: &[AstType], pos: &str, errs: &mut Vec<String>) {
+        if sig.params.len() != args.len() {
+            errs.push(format!(
+                "{}: wrong number of arguments to {}: expected {}, got {}{}",
+                pos,
+                callee,
+                sig.params.len(),
+                args.len(),
+                note
+            ));
+            return;
+        }
+        sig.params.iter().zip(args.iter()).enumerate().for_each(|(i, (p, a))| {
+            if let Some(ref t) = Self::expr_type(a) {
+                if !Self::is_compatible(p, t, a) {
+                    errs.push(format!(
+                        "{}: incompatible type for argument {} of {}: expected {}, got {}{}",
+                        pos,
+                        i + 1,
+                        callee,
+                        type_name(&p.0, &p.1),
+                        type_name(&t.0, &t.1),
+                        note
+                    ));
+                }
+            }
+        });
+    }
+
+    // 式の型取得（判明しない場合はNone）
+    //
+    // 配列はポインタへ、関数指定子は関数ポインタへ変換する
+    fn expr_type(a: &AstType) -> Option<(Type, Structure)> {
+        match a {
+            AstType::Variable(Type::Func(ref sig), _, _, _) => Some((Type::Func(sig.clone()), Structure::Pointer)),
+            AstType::Address(ref v, _) => match **v {
+                AstType::Variable(Type::Func(ref sig), _, _, _) => Some((Type::Func(sig.clone()), Structure::Pointer)),
+                _ => a.value_type(),
+            },
+            _ => a.value_type().map(|(t, s)| decay(t, s)),
+        }
+    }
+
+    // 型の互換性判定
+    //
+    // 整数型同士は変換可能、ポインタは同じ型かvoid*のみ（定数0はポインタへ変換可能）
+    fn is_compatible(p: &(Type, Structure), a: &(Type, Structure), e: &AstType) -> bool {
+        let is_scalar = |t: &(Type, Structure)| t.1 == Structure::Identifier && matches!(t.0, Type::Int | Type::Char | Type::Short | Type::Long);
+        match (&p.1, &a.1) {
+            // void*は関数ポインタ以外のポインタと相互に変換可能
+            (Structure::Pointer, Structure::Pointer) => {
+                p.0 == a.0
+                    || (p.0 == Type::Void && !matches!(a.0, Type::Func(_)))
+                    || (a.0 == Type::Void && !matches!(p.0, Type::Func(_)))
+            }
+            (Structure::Pointer, _) => e.const_value() == Some(0),
+            _ if is_scalar(p) => is_scalar(a),
+            _ => p == a,
+        }
+    }
+
+    // 型修飾子、左辺値解析
+    //
+    // const変数への代入、修飾子を取り除く代入や関数呼び出しを検出する
+    // 代入、増減、アドレス演算子の被演算子が左辺値であることを検査する（宣言の初期化子は対象外）
+    fn analysis_qualifier(&self, scope: &Scope, ast: &AstType, errs: &mut Vec<String>) {
+        match ast {
+            AstType::Assign(ref a, ref b, ref pos) => {
+                self.check_lvalue(a, "assignment", "left operand of assignment", pos, errs);
+                self.check_modifiable(scope, a, pos, errs);
+                self.check_discard(scope, a, b, pos, errs);
+                ast.children().iter().for_each(|c| self.analysis_qualifier(scope, c, errs));
+            }
+            AstType::Init(ref a, ref b, ref pos) => {
+                self.check_discard(scope, a, b, pos, errs);
+                self.analysis_qualifier(scope, b, errs);
+            }
+            AstType::PlusAssign(ref a, _, ref pos)
+            | AstType::MinusAssign(ref a, _, ref pos)
+            | AstType::MultipleAssign(ref a, _, ref pos)
+            | AstType::DivisionAssign(ref a, _, ref pos)
+            | AstType::RemainderAssign(ref a, _, ref pos)
+            | AstType::BitAndAssign(ref a, _, ref pos)
+            | AstType::BitOrAssign(ref a, _, ref pos)
+            | AstType::BitXorAssign(ref a, _, ref pos)
+            | AstType::LeftShiftAssign(ref a, _, ref pos)
+            | AstType::RightShiftAssign(ref a, _, ref pos) => {
+                self.check_lvalue(a, "assignment", "left operand of assignment", pos, errs);
+                self.check_modifiable(scope, a, pos, errs);
+                ast.children().iter().for_each(|c| self.analysis_qualifier(scope, c, errs));
+            }
+            AstType::PreInc(ref a, ref pos) | AstType::PostInc(ref a, ref pos) => {
+                self.check_lvalue(a, "increment", "increment operand", pos, errs);
+                self.check_modifiable(scope, a, pos, errs);
+                ast.children().iter().for_each(|c| self.analysis_qualifier(scope, c, errs));
+            }
+            AstType::PreDec(ref a, ref pos) | AstType::PostDec(ref a, ref pos) => {
+                self.check_lvalue(a, "decrement", "decrement operand", pos, errs);
+                self.check_modifiable(scope, a, pos, errs);
+                ast.children().iter().for_each(|c| self.analysis_qualifier(scope, c, errs));
+            }
+            AstType::Address(ref a, ref pos) => {
+                if Self::value_category(a) == ValueCategory::Rvalue {
+                    errs.push(format!("{}: lvalue required as unary '&' operand", pos));
+                }
+                self.analysis_qualifier(scope, a, errs);
+            }
+            AstType::FuncCall(ref f, ref args, ref pos) => {
+                self.check_call_discard(scope, f, args, pos, errs);
+                ast.children().iter().for_each(|c| self.analysis_qualifier(scope, c, errs));
+            }
+            _ => ast.children().iter().for_each(|c| self.analysis_qualifier(scope, c, errs)),
+        }
+    }
+
+    // 変数シンボル検索（ローカルで見つからない場合はグローバル）
+    fn search_var(&self, scope: &Scope, n: &str) -> Option<Symbol> {
+        self.sym_table.search(scope, n).or_else(|| self.sym_table.search(&Scope::Global, n))
+    }
+
+    // ポインタ演算の基点となるポインタ、配列変数取得
+    fn pointer_base(&self, scope: &Scope, a: &AstType) -> Option<Symbol> {
+        match a {
+            AstType::Variable(_, Structure::Pointer, ref n, _)
+            | AstType::Variable(_, Structure::Array(_), ref n, _) => self.search_var(scope, n),
+            AstType::Plus(ref a, ref b) => {
+                self.pointer_base(scope, a).or_else(|| self.pointer_base(scope, b))
+            }
+            AstType::Minus(ref a, _)
+            | AstType::PreInc(ref a, _)
+            | AstType::PreDec(ref a, _)
+            | AstType::PostInc(ref a, _)
+            | AstType::PostDec(ref a, _) => self.pointer_base(scope, a),
+            _ => None,
+        }
+    }
+
+    // ポインタ、配列の参照先の型修飾子
+    fn pointee_qual(sym: &Symbol) -> Qualifier {
+        match sym.strt {
+            Structure::Pointer => sym.ptr_qual.clone(),
+            _ => sym.qual.clone(),
+        }
+    }
+
+    // 式が指す先の型修飾子
+    fn expr_pointee_qual(&self, scope: &Scope, a: &AstType) -> Qualifier {
+        match a {
+            AstType::Address(ref v, _) => match **v {
+                AstType::Variable(_, _, ref n, _) => {
+                    self.search_var(scope, n).map(|s| s.qual).unwrap_or_default()
+                }
+                AstType::Indirect(ref e) => {
+                    self.pointer_base(scope, e).map(|s| Self::pointee_qual(&s)).unwrap_or_default()
+                }
+                _ => Qualifier::default(),
+            },
+            AstType::Condition(_, ref b, ref c) => {
+                self.expr_pointee_qual(scope, b).merge(&self.expr_pointee_qual(scope, c))
+            }
+            AstType::Comma(_, ref b) => self.expr_pointee_qual(scope, b),
+            _ => self.pointer_base(scope, a).map(|s| Self::pointee_qual(&s)).unwrap_or_default(),
+        }
+    }
+
+    // 代入先が変更可能かチェック
+    fn check_modifiable(&self, scope: &Scope, a: &AstType, pos: &str, errs: &mut Vec<String>) {
+        match a {
+            AstType::Variable(_, _, ref n, _) if self.search_var(scope, n).map(|s| s.qual.is_const).unwrap_or(false) => {
+                errs.push(format!("{}: assignment of read-only variable: {:?}", pos, source_name(n)));
+            }
+            AstType::Indirect(ref e) => {
+                if let Some(sym) = self.pointer_base(scope, e).filter(|s| Self::pointee_qual(s).is_const) {
+                    errs.push(format!("{}: assignment of read-only location: {:?}", pos, source_name(&sym.var)));
+                }
+            }
+            // constメンバー、又はconstな構造体のメンバー
+            AstType::Member(ref base, _, _, ref n) => match self.member_symbol(scope, base, n) {
+                Some(ref m) if m.qual.is_const => errs.push(format!("{}: assignment of read-only member: {:?}", pos, n)),
+                _ => self.check_modifiable(scope, base, pos, errs),
+            },
+            _ => {}
+        }
+    }
+
+    // メンバーのシンボル取得（構造体定義から検索）
+    fn member_symbol(&self, scope: &Scope, base: &AstType, n: &str) -> Option<Symbol> {
+        match base.value_type() {
+            Some((Type::Struct(ref tag) | Type::Union(ref tag), _)) => self.search_var(scope, tag)?.members.into_iter().find(|m| m.var == n),
+            _ => None,
+        }
+    }
+
+    // 値カテゴリの判定
+    //
+    // const修飾による変更不可は型修飾子の検査（check_modifiable）で扱う
+    fn value_category(a: &AstType) -> ValueCategory {
+        let by_type = |a: &AstType| match a.value_type() {
+            Some((_, Structure::Array(_))) => ValueCategory::NonModifiable,
+            _ => ValueCategory::Modifiable,
+        };
+        match a {
+            AstType::Variable(Type::Func(_), Structure::Identifier, _, _) => ValueCategory::NonModifiable,
+            AstType::Variable(_, _, _, _) | AstType::Indirect(_) => by_type(a),
+            // 右辺値（関数の戻り値等）のメンバーは右辺値
+            AstType::Member(ref base, _, _, _) => match Self::value_category(base) {
+                ValueCategory::Rvalue => ValueCategory::Rvalue,
+                _ => by_type(a),
+            },
+            AstType::StringLiteral(_, _) => ValueCategory::NonModifiable,
+            // 構文エラーは報告済み
+            AstType::Error() => ValueCategory::Modifiable,
+            _ => ValueCategory::Rvalue,
+        }
+    }
+
+    // 代入、増減の被演算子が変更可能な左辺値かチェック
+    fn check_lvalue(&self, a: &AstType, op: &str, operand: &str, pos: &str, errs: &mut Vec<String>) {
+        match (Self::value_category(a), a) {
+            (ValueCategory::Rvalue, _) => errs.push(format!("{}: lvalue required as {}", pos, operand)),
+            (ValueCategory::NonModifiable, AstType::Variable(Type::Func(_), Structure::Identifier, ref n, _)) => {
+                errs.push(format!("{}: {} of function {:?}", pos, op, n));
+            }
+            (ValueCategory::NonModifiable, _) => errs.push(format!("{}: {} of expression with array type", pos, op)),
+            (ValueCategory::Modifiable, _) => {}
+        }
+    }
+
+    // 代入で型修飾子が取り除かれていないかチェック
+    fn check_discard(&self, scope: &Scope, a: &AstType, b: &AstType, pos: &str, errs: &mut Vec<String>) {
+        if let AstType::Variable(_, Structure::Pointer, ref n, _) = a {
+            if let Some(sym) = self.search_var(scope, n) {
+                if !sym.ptr_qual.contains(&self.expr_pointee_qual(scope, b)) {
+                    errs.push(format!("{}: assignment discards qualifiers: {:?}", pos, source_name(n)));
+                }
+            }
+        }
+    }
+
+    // 関数呼び出しで型修飾子が取り除かれていないかチェック
+    fn check_call_discard(&self, scope: &Scope, f: &AstType, args: &AstType, pos: &str, errs: &mut Vec<String>) {
+        let (name, args) = match (f, args) {
+            (AstType::Variable(_, _, ref name, _), AstType::Argment(ref args)) => (name, args),
+            _ => return,
+        };
+
+        // 関数定義の仮引数と比較
+        let params = self.ast.get_tree().iter().find_map(|t| match t {
+            AstType::FuncDef(_, _, ref n, ref p, _) if n == name => match **p {
+                AstType::Argment(ref p) => Some(p),
+                _ => None,
+            },
+            _ => None,
+        });
+        let func_scope = Scope::Local(name.clone());
+        params.into_iter().flatten().zip(args.iter()).enumerate().for_each(|(i, (p, a))| {
+            if let AstType::Variable(_, Structure::Pointer, ref pn, _) = p {
+                if let Some(sym) = self.sym_table.search(&func_scope, pn) {
+                    if !sym.ptr_qual.contains(&self.expr_pointee_qual(scope, a)) {
+                        errs.push(format!("{}: passing argument {} of {:?} discards qualifiers", pos, i + 1, name));
+                    }
+                }
+            }
+        });
+    }
+
     // 変数定義解析
     fn analysis_variable(&self, t: &Type, _s: &Structure, _n: &str) -> Result<(), Vec<String>> {
         let mut errs = vec![];
@@ -183,8 +868,9 @@ fn test_func_type() {
             "main".to_string(),
             Box::new(AstType::Argment(vec![])),
             Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-            ))])),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -199,8 +885,9 @@ fn test_func_type() {
             "main".to_string(),
             Box::new(AstType::Argment(vec![])),
             Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-            ))])),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -224,8 +911,10 @@ fn test_variable() {
                     Type::Unknown("aaaa".to_string()),
                     Structure::Identifier,
                     "a".to_string(),
+                    String::new(),
                 ),
-            ))])),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -250,18 +939,22 @@ fn test_arithmetic() {
                         Type::Int,
                         Structure::Identifier,
                         "a1".to_string(),
+                        String::new(),
                     )),
                     Box::new(AstType::Variable(
                         Type::Unknown("aaaa".to_string()),
                         Structure::Identifier,
                         "a2".to_string(),
+                        String::new(),
                     )),
                 ),
                 AstType::Return(Box::new(AstType::Variable(
                     Type::Int,
                     Structure::Identifier,
                     "r".to_string(),
-                ))),
+                    String::new(),
+                )),
+                    String::new(),),
             ])),
         )];
         let tree = AstTree { tree: ast };
@@ -282,18 +975,22 @@ fn test_arithmetic() {
                         Type::Int,
                         Structure::Identifier,
                         "a1".to_string(),
+                        String::new(),
                     )),
                     Box::new(AstType::Variable(
                         Type::Unknown("aaaa".to_string()),
                         Structure::Identifier,
                         "a2".to_string(),
+                        String::new(),
                     )),
                 ),
                 AstType::Return(Box::new(AstType::Variable(
                     Type::Int,
                     Structure::Identifier,
                     "r".to_string(),
-                ))),
+                    String::new(),
+                )),
+                    String::new(),),
             ])),
         )];
         let tree = AstTree { tree: ast };
@@ -314,18 +1011,22 @@ fn test_arithmetic() {
                         Type::Int,
                         Structure::Identifier,
                         "a1".to_string(),
+                        String::new(),
                     )),
                     Box::new(AstType::Variable(
                         Type::Unknown("aaaa".to_string()),
                         Structure::Identifier,
                         "a2".to_string(),
+                        String::new(),
                     )),
                 ),
                 AstType::Return(Box::new(AstType::Variable(
                     Type::Int,
                     Structure::Identifier,
                     "r".to_string(),
-                ))),
+                    String::new(),
+                )),
+                    String::new(),),
             ])),
         )];
         let tree = AstTree { tree: ast };
@@ -346,18 +1047,22 @@ fn test_arithmetic() {
                         Type::Int,
                         Structure::Identifier,
                         "a1".to_string(),
+                        String::new(),
                     )),
                     Box::new(AstType::Variable(
                         Type::Unknown("aaaa".to_string()),
                         Structure::Identifier,
                         "a2".to_string(),
+                        String::new(),
                     )),
                 ),
                 AstType::Return(Box::new(AstType::Variable(
                     Type::Int,
                     Structure::Identifier,
                     "r".to_string(),
-                ))),
+                    String::new(),
+                )),
+                    String::new(),),
             ])),
         )];
         let tree = AstTree { tree: ast };
@@ -380,10 +1085,12 @@ fn test_func_argment() {
                 Type::Int,
                 Structure::Identifier,
                 "a".to_string(),
+                String::new(),
             )])),
             Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-            ))])),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -400,10 +1107,12 @@ fn test_func_argment() {
                 Type::Unknown("a".to_string()),
                 Structure::Identifier,
                 "a".to_string(),
+                String::new(),
             )])),
             Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-            ))])),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -421,16 +1130,19 @@ fn test_func_argment() {
                     Type::Unknown("a".to_string()),
                     Structure::Identifier,
                     "a".to_string(),
+                    String::new(),
                 ),
                 AstType::Variable(
                     Type::Unknown("b".to_string()),
                     Structure::Identifier,
                     "b".to_string(),
+                    String::new(),
                 ),
             ])),
             Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-            ))])),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -445,17 +1157,18 @@ fn test_func_argment() {
             Structure::Identifier,
             "main".to_string(),
             Box::new(AstType::Argment(vec![
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(),),
-                AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(),),
-                AstType::Variable(Type::Int, Structure::Identifier, "c".to_string(),),
-                AstType::Variable(Type::Int, Structure::Identifier, "d".to_string(),),
-                AstType::Variable(Type::Int, Structure::Identifier, "e".to_string(),),
-                AstType::Variable(Type::Int, Structure::Identifier, "f".to_string(),),
-                AstType::Variable(Type::Int, Structure::Identifier, "f".to_string(),),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+                AstType::Variable(Type::Int, Structure::Identifier, "b".to_string(), String::new()),
+                AstType::Variable(Type::Int, Structure::Identifier, "c".to_string(), String::new()),
+                AstType::Variable(Type::Int, Structure::Identifier, "d".to_string(), String::new()),
+                AstType::Variable(Type::Int, Structure::Identifier, "e".to_string(), String::new()),
+                AstType::Variable(Type::Int, Structure::Identifier, "f".to_string(), String::new()),
+                AstType::Variable(Type::Int, Structure::Identifier, "f".to_string(), String::new()),
             ])),
             Box::new(AstType::Statement(vec![AstType::Return(Box::new(
-                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string()),
-            ))])),
+                AstType::Variable(Type::Int, Structure::Identifier, "a".to_string(), String::new()),
+            ),
+                String::new(),)])),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -472,6 +1185,7 @@ fn test_func_call() {
         let ast = vec![AstType::FuncCall(
             Box::new(AstType::Factor(2)),
             Box::new(AstType::Argment(vec![])),
+            String::new(),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -485,8 +1199,10 @@ fn test_func_call() {
                 Type::Int,
                 Structure::Identifier,
                 "a".to_string(),
+                String::new(),
             )),
             Box::new(AstType::Argment(vec![])),
+            String::new(),
         )];
         let tree = AstTree { tree: ast };
         let sym = SymbolTable::new();
@@ -505,6 +1221,7 @@ fn test_global() {
                 Type::Unknown("aaaa".to_string()),
                 Structure::Identifier,
                 "a".to_string(),
+                String::new(),
             )]),
             AstType::FuncDef(
                 Type::Int,
@@ -513,7 +1230,8 @@ fn test_global() {
                 Box::new(AstType::Argment(vec![])),
                 Box::new(AstType::Statement(vec![AstType::Return(Box::new(
                     AstType::Factor(1),
-                ))])),
+                ),
+                    String::new(),)])),
             ),
         ];
         let tree = AstTree { tree: ast };
@@ -523,3 +1241,406 @@ fn test_global() {
         assert!(r.err().unwrap().len() == 1);
     }
 }
+
+// ソースを解析し、エラー内容を返す
+#[cfg(test)]
+fn analysis(src: &str) -> Vec<String> {
+    analysis_with_warnings(src).0
+}
+
+// ソースを解析し、(エラー内容, 警告)を返す
+#[cfg(test)]
+fn analysis_with_warnings(src: &str) -> (Vec<String>, Vec<Warning>) {
+    use ast::AstGen;
+    use lexer::LexicalAnalysis;
+
+    let mut lexer = LexicalAnalysis::new("test.c".to_string(), src);
+    lexer.read_token();
+    let mut ast = AstGen::new(lexer.get_tokens());
+    let tree = ast.parse();
+    let mut sem = Semantic::new(&tree, ast.get_symbol());
+    let errs = sem.exec().err().unwrap_or_default();
+    (errs, sem.get_warnings().clone())
+}
+
+#[test]
+fn test_qualifier() {
+    // 初期化、const変数の参照は許可
+    assert!(analysis("const int a = 1; int main() { const int b = a; const int *const p = &b; return *p; }").is_empty());
+    assert!(analysis("int main() { int x; const int *p = &x; const int y = *p; p = &y; return y; }").is_empty());
+    assert!(analysis("int f(const char *s) { return *s; } int main() { char *t; t = \"a\"; return f(t); }").is_empty());
+
+    // const変数への代入
+    assert_eq!(
+        vec!["test.c:1:33: assignment of read-only variable: \"a\"".to_string()],
+        analysis("const int a = 1; int main() { a = 2; return a; }")
+    );
+    assert_eq!(
+        vec![
+            "test.c:1:32: assignment of read-only variable: \"b\"".to_string(),
+            "test.c:1:38: assignment of read-only variable: \"b\"".to_string(),
+        ],
+        analysis("int main() { const int b = 1; b++; b += 2; return b; }")
+    );
+
+    // 宣言子リストの後続の変数も修飾する
+    assert_eq!(
+        vec!["test.c:1:40: assignment of read-only variable: \"b\"".to_string()],
+        analysis("int main() { const int a = 1, b = 2; b = 3; return a; }")
+    );
+
+    // 宣言後の最初の代入は初期化ではない
+    assert_eq!(
+        vec!["test.c:1:29: assignment of read-only variable: \"c\"".to_string()],
+        analysis("int main() { const int c; c = 1; return c; }")
+    );
+
+    // constな構造体、constメンバーへの代入
+    assert_eq!(
+        vec!["test.c:1:57: assignment of read-only variable: \"s\"".to_string()],
+        analysis("struct S { int a; }; int main() { const struct S s; s.a = 1; return 0; }")
+    );
+    assert_eq!(
+        vec![
+            "test.c:1:82: assignment of read-only member: \"a\"".to_string(),
+            "test.c:1:101: assignment of read-only member: \"a\"".to_string(),
+        ],
+        analysis("struct S { const int a; int b; }; struct S g; int main() { struct S *p = &g; g.a = 1; p->b = 2; p->a++; return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:76: assignment of read-only location: \"p\"".to_string()],
+        analysis("struct S { int a; }; int main() { struct S s; const struct S *p = &s; p->a = 3; return 0; }")
+    );
+
+    // constへのポインタ経由の代入
+    assert_eq!(
+        vec!["test.c:1:43: assignment of read-only location: \"p\"".to_string()],
+        analysis("int main() { int x; const int *p = &x; *p = 1; return x; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:44: assignment of read-only location: \"a\"".to_string()],
+        analysis("const int a[2] = {1, 2}; int main() { a[1] = 3; return 0; }")
+    );
+
+    // 修飾子を取り除く代入、関数呼び出し
+    assert_eq!(
+        vec!["test.c:1:41: assignment discards qualifiers: \"p\"".to_string()],
+        analysis("int main() { const int x = 1; int *p; p = &x; return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:24: assignment discards qualifiers: \"q\"".to_string()],
+        analysis("volatile int v; int *q = &v; int main() { return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:79: passing argument 2 of \"f\" discards qualifiers".to_string()],
+        analysis("int f(int a, char *s) { return a; } int main() { const char *s = \"a\"; return f(1, s); }")
+    );
+}
+
+#[test]
+fn test_func_pointer() {
+    // シグネチャが一致する呼び出し、代入は許可
+    assert!(analysis("int add(int a, int b) { return a + b; } int main() { int (*fp)(int, int); fp = add; return fp(1, 2) + (*fp)(3, 4); }").is_empty());
+    assert!(analysis("int s(char *p) { return *p; } int main() { int (*f)(char*) = &s; return f(\"a\") + f(0); }").is_empty());
+
+    // 引数の数、型の不一致
+    assert_eq!(
+        vec!["test.c:1:76: wrong number of arguments to function pointer call: expected 1, got 2".to_string()],
+        analysis("int inc(int x) { return x + 1; } int main() { int (*f)(int) = inc; return f(1, 2); }")
+    );
+    assert_eq!(
+        vec!["test.c:1:84: incompatible type for argument 1 of function pointer call: expected int, got int*".to_string()],
+        analysis("int inc(int x) { return x + 1; } int main() { int (*f)(int) = inc; int *p; return f(p); }")
+    );
+
+    // シグネチャの異なる関数の代入
+    assert_eq!(
+        vec!["test.c:1:68: incompatible function pointer types assigning to \"f\": expected int(*)(int), got int(*)(int, int)".to_string()],
+        analysis("int add(int a, int b) { return a + b; } int main() { int (*f)(int) = add; return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:90: incompatible function pointer types assigning to \"op\": expected int(*)(int, int), got int(*)()".to_string()],
+        analysis("struct O { int (*op)(int, int); }; int one() { return 1; } int main() { struct O o; o.op = one; return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:61: incompatible function pointer types assigning to \"t\": expected int(*)(int), got int(*)()".to_string()],
+        analysis("int one() { return 1; } int main() { int (*t[2])(int); t[0] = one; return 0; }")
+    );
+}
+
+#[test]
+fn test_const_init() {
+    // 定数式、アドレス定数、評価されない被演算子のゼロ除算は許可
+    assert!(analysis("int x; int *p = &x; int a = 0 && 1 / 0; int b = 1 ? 2 : 1 / 0; int c = -2147483647 - 1; int main() { static int s = sizeof(int) << 2; return 0; }").is_empty());
+
+    // ゼロ除算、オーバーフロー、シフト幅
+    assert_eq!(
+        vec!["test.c:1:7: initializer element is not constant: division by zero in constant expression".to_string()],
+        analysis("int a = 1 / 0; int main() { return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:27: initializer element is not constant: division by zero in constant expression".to_string()],
+        analysis("int main() { static int s = 3 % (1 - 1); return s; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:7: initializer element is not constant: integer overflow in constant expression".to_string()],
+        analysis("int a = 2147483647 + 1; int main() { return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:7: initializer element is not constant: shift count 40 is out of range in constant expression".to_string()],
+        analysis("int a = 1 << 40; int main() { return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:7: initializer element is not constant: integer overflow in constant expression".to_string()],
+        analysis("int a = (1 << 31) / -1; int main() { return 0; }")
+    );
+
+    // 定数でない初期化子
+    assert_eq!(
+        vec!["test.c:1:14: initializer element is not constant: expression is not a constant".to_string()],
+        analysis("int x; int a = x + 1; int main() { return 0; }")
+    );
+}
+
+#[test]
+fn test_static_assert() {
+    // ファイルスコープ、ブロックスコープ、構造体定義内
+    assert!(analysis("struct S { int a; char b; _Static_assert(sizeof(int) == 4, \"int\"); };\n_Static_assert(sizeof(struct S) == 8, \"S\");\nint main() { _Static_assert(_Alignof(struct S) == 4, \"align\"); return 0; }").is_empty());
+
+    // 失敗時はメッセージと位置
+    assert_eq!(
+        vec!["test.c:2:1: static assertion failed: \"S layout\"".to_string()],
+        analysis("struct S { int a; char b; };\n_Static_assert(sizeof(struct S) == 12, \"S layout\");\nint main() { return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:12: static assertion failed: \"char\"".to_string()],
+        analysis("struct S { _Static_assert(sizeof(char) == 2, \"char\"); int a; }; int main() { return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:14: static assertion failed: \"block\"".to_string()],
+        analysis("int main() { _Static_assert(1 > 2, \"block\"); return 0; }")
+    );
+
+    // 定数でない式
+    assert_eq!(
+        vec!["test.c:1:8: expression in static assertion is not constant: expression is not a constant".to_string()],
+        analysis("int g; _Static_assert(g, \"g\"); int main() { return 0; }")
+    );
+}
+
+#[test]
+fn test_bit_field_address() {
+    // 通常のメンバー、構造体自体のアドレスは許可
+    assert!(analysis("struct S { int a; unsigned b : 3; }; int main() { struct S s; int *p; struct S *q; p = &s.a; q = &s; return s.b; }").is_empty());
+
+    assert_eq!(
+        vec!["test.c:1:75: cannot take address of bit-field \"b\"".to_string()],
+        analysis("struct S { int a; unsigned b : 3; }; int main() { struct S s; int *p; p = &s.b; return 0; }")
+    );
+    assert_eq!(
+        vec!["test.c:1:81: cannot take address of bit-field \"f\"".to_string()],
+        analysis("struct S { char f : 2; }; int main() { struct S s; struct S *p; p = &s; return *&p->f; }")
+    );
+}
+
+#[test]
+fn test_call_argment() {
+    // 整数型同士の変換、配列からポインタへの変換、定数0は許可
+    let decl = "struct S { int a; }; int f(int a, char *p); int g(struct S *s); int h(); ";
+    assert!(analysis(&format!("{}int main() {{ char c[4]; struct S s; return f(c[0], c) + f(sizeof(s), 0) + g(&s) + h(1, 2); }}", decl)).is_empty());
+    assert!(analysis("int sum(int a[][3], int n) { return a[1][2] + n; } int main() { int m[2][3]; return sum(m, 1); }").is_empty());
+
+    // 引数の数
+    assert_eq!(
+        vec![
+            "test.c:1:88: wrong number of arguments to \"f\": expected 2, got 1 (declared at test.c:1:26)".to_string(),
+            "test.c:1:101: wrong number of arguments to \"f\": expected 2, got 3 (declared at test.c:1:26)".to_string(),
+        ],
+        analysis(&format!("{}int main() {{ f(1); return f(1, \"a\", 3); }}", decl))
+    );
+
+    // ポインタと整数の不一致、ポインタが期待される引数への構造体
+    assert_eq!(
+        vec![
+            "test.c:1:107: incompatible type for argument 1 of \"f\": expected int, got int* (declared at test.c:1:26)".to_string(),
+            "test.c:1:119: incompatible type for argument 2 of \"f\": expected char*, got int (declared at test.c:1:26)".to_string(),
+            "test.c:1:139: incompatible type for argument 1 of \"g\": expected struct S*, got struct S (declared at test.c:1:49)".to_string(),
+        ],
+        analysis(&format!("{}int main() {{ int x; struct S s; f(&x, \"a\"); f(x, x + 1); return g(s); }}", decl))
+    );
+}
+
+#[test]
+fn test_return_type() {
+    // ソースを解析し、(エラー内容, 警告内容)を返す
+    let analysis = |src: &str| {
+        let (errs, warns) = analysis_with_warnings(src);
+        (errs, warns.iter().map(|w| w.msg.clone()).collect::<Vec<_>>())
+    };
+
+    // 整数型同士の変換、定数0からポインタへの変換、配列からポインタへの変換は許可
+    assert_eq!(
+        (vec![], vec![]),
+        analysis("char c(int x) { return x; } int *p(int *a) { if (a) { return a; } return 0; } int a[2]; int *q() { return a; } int main() { return c(1); }")
+    );
+
+    // ポインタと整数の不一致、値なしのreturn文
+    assert_eq!(
+        vec![
+            "test.c:1:17: incompatible return type in function \"f\": expected int, got int*".to_string(),
+            "test.c:1:45: incompatible return type in function \"g\": expected int*, got int".to_string(),
+            "test.c:1:67: 'return' with no value in function \"h\" returning int".to_string(),
+        ],
+        analysis("int f(int *p) { return p; } int *g(int x) { return x; } int h() { return; } int main() { return 0; }").0
+    );
+
+    // 終端への到達（ループ、分岐を考慮）
+    assert_eq!(
+        vec![
+            "control reaches end of non-void function \"f\"".to_string(),
+            "control reaches end of non-void function \"g\"".to_string(),
+            "control reaches end of non-void function \"h\"".to_string(),
+        ],
+        analysis(concat!(
+            "int f(int x) { if (x) { return 1; } } ",
+            "int g(int x) { while (1) { if (x) { break; } } } ",
+            "int h(int x) { do { if (x) { continue; } return 1; } while (x); } ",
+            "int i(int x) { if (x) { return 1; } else { return 2; } } ",
+            "int j(int x) { while (1) { while (x) { break; } } } ",
+            "int k(int x) { for (;;) { if (x) { return 1; } } } ",
+            "int l(int x) { do { return 1; } while (1); } ",
+            "int main() { }"
+        )).1
+    );
+
+    // void関数からの値の返却
+    {
+        let stmt = |r: AstType| Box::new(AstType::Statement(vec![r]));
+        let ast = vec![
+            AstType::FuncDef(Type::Void, Structure::Identifier, "f".to_string(), Box::new(AstType::Argment(vec![])), stmt(AstType::Return(Box::new(AstType::Factor(1)), "test.c:1:12".to_string()))),
+            AstType::FuncDef(Type::Void, Structure::Identifier, "g".to_string(), Box::new(AstType::Argment(vec![])), stmt(AstType::ReturnVoid(String::new()))),
+        ];
+        let tree = AstTree { tree: ast };
+        let sym = SymbolTable::new();
+        let mut sem = Semantic::new(&tree, &sym);
+        assert_eq!(Err(vec!["test.c:1:12: 'return' with a value in function \"f\" returning void".to_string()]), sem.exec());
+        assert!(sem.get_warnings().is_empty());
+    }
+}
+
+#[test]
+fn test_void() {
+    // void*は他のオブジェクトポインタと相互に変換可能、void値は捨てる位置でのみ使用可能
+    assert_eq!(
+        Vec::<String>::new(),
+        analysis(concat!(
+            "void f(void) { } void *g(void *p) { return p; } int *h(void *p) { return p; } ",
+            "int main() { int a; char *c; f(); (void)g(&a); c = g(&a); h(c); for (f(); 0; f()) { } a = (f(), 1); return 0; }"
+        ))
+    );
+
+    // void値の使用、(void)への実引数
+    assert_eq!(
+        vec![
+            "test.c:1:39: wrong number of arguments to \"f\": expected 0, got 1 (declared at test.c:1:6)".to_string(),
+            "test.c:1:49: void value not ignored as it ought to be".to_string(),
+            "test.c:1:58: void value not ignored as it ought to be".to_string(),
+            "test.c:1:78: void value not ignored as it ought to be".to_string(),
+        ],
+        analysis("void f(void) { } int main() { int a; f(1); a = f(); if (f()) { } return a ? f() : 0; }")
+    );
+    assert_eq!(
+        vec![
+            "test.c:1:25: void value not ignored as it ought to be".to_string(),
+            "test.c:1:45: void value not ignored as it ought to be".to_string(),
+        ],
+        analysis("int main() { int a; a = (void)0; return a + (void)a; }")
+    );
+
+    // 関数ポインタはvoid*と互換性がない
+    assert_eq!(
+        vec!["test.c:1:61: incompatible type for argument 1 of \"g\": expected void*, got int(*)() (declared at test.c:1:32)".to_string()],
+        analysis("int f(void) { return 0; } void g(void *p) { } int main() { g(f); return 0; }")
+    );
+}
+
+#[test]
+fn test_lvalue() {
+    // 変数、間接参照、添字、メンバーは変更可能な左辺値、配列の初期化は代入ではない
+    assert_eq!(
+        Vec::<String>::new(),
+        analysis(concat!(
+            "struct S { int m; }; int g[2] = {1, 2}; int f(int x) { return x; } ",
+            "int main() { int a; int *p; int arr[2] = {3, 4}; struct S s; int (*fp)(int); ",
+            "a = 1; p = arr; *p = 2; p[1] = 3; s.m = 4; a += 1; a++; --p[0]; fp = f; p = &arr[1]; p = &*p; fp = &f; return 0; }"
+        ))
+    );
+
+    // 右辺値、配列、関数への代入、増減、右辺値のアドレス
+    assert_eq!(
+        vec![
+            "test.c:1:147: lvalue required as left operand of assignment".to_string(),
+            "test.c:1:158: lvalue required as left operand of assignment".to_string(),
+            "test.c:1:167: assignment of expression with array type".to_string(),
+            "test.c:1:174: assignment of function \"f\"".to_string(),
+            "test.c:1:182: increment of expression with array type".to_string(),
+            "test.c:1:186: decrement of function \"f\"".to_string(),
+            "test.c:1:191: lvalue required as increment operand".to_string(),
+            "test.c:1:203: lvalue required as unary '&' operand".to_string(),
+            "test.c:1:217: lvalue required as unary '&' operand".to_string(),
+            "test.c:1:231: lvalue required as left operand of assignment".to_string(),
+        ],
+        analysis(concat!(
+            "struct S { int m; }; struct S mk() { struct S s; s.m = 1; return s; } int f(int x) { return x; } ",
+            "int main() { int a; int b; int arr[3]; int *p; ",
+            "1 = a; a + b = 3; arr = p; f = 0; arr++; --f; ++f(1); p = &(a + 1); p = &f(1); mk().m = 2; return 0; }"
+        ))
+    );
+}
+
+#[test]
+fn test_uninitialized() {
+    // ソースを解析し、未初期化変数の警告を返す
+    let analysis = |src: &str| {
+        let (errs, warns) = analysis_with_warnings(src);
+        assert!(errs.is_empty());
+        warns
+            .iter()
+            .filter(|w| w.kind == WarningKind::Uninitialized)
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // 全ての経路で代入済み
+    assert!(analysis("int main() { int a; int b = 1; if (b) { a = 1; } else { a = 2; } return a + b; }").is_empty());
+    assert!(analysis("int main() { int a; int i; for (i = 0; i < 3; i++) { a = i; } return i; }").is_empty());
+    assert!(analysis("int main() { int a; while (1) { a = 1; break; } return a; }").is_empty());
+    assert!(analysis("int main() { int a; do { a = 1; } while (0); return a; }").is_empty());
+    assert!(analysis("int main(int argc) { int a; if (argc) { return 0; } else { a = 1; } return a; }").is_empty());
+
+    // 仮引数、static変数、配列、アドレスを取得した変数は対象外
+    assert!(analysis("int f(int x) { static int s; int a[2]; a[0] = x; return s + a[0]; }").is_empty());
+    assert!(analysis("int g(int *p) { *p = 1; return 0; } int main() { int a; g(&a); return a; }").is_empty());
+
+    // 分岐の片側のみで代入
+    assert_eq!(
+        vec!["test.c:1:48: warning: variable \"a\" may be used uninitialized [-Wuninitialized]".to_string()],
+        analysis("int f(int c) { int a; if (c) { a = 1; } return a; }")
+    );
+
+    // ループ本体は実行されない場合がある
+    assert_eq!(
+        vec!["test.c:1:72: warning: variable \"a\" may be used uninitialized [-Wuninitialized]".to_string()],
+        analysis("int f(int n) { int a; int i; for (i = 0; i < n; i++) { a = i; } return a; }")
+    );
+
+    // 初期化式での自己参照、&&の右辺での代入、ポインタ
+    assert_eq!(
+        vec![
+            "test.c:1:24: warning: variable \"a\" may be used uninitialized [-Wuninitialized]".to_string(),
+            "test.c:1:65: warning: variable \"p\" may be used uninitialized [-Wuninitialized]".to_string(),
+            "test.c:1:79: warning: variable \"b\" may be used uninitialized [-Wuninitialized]".to_string(),
+        ],
+        analysis("int f(int c) { int a = a + 1; int b; int *p; c = c && (b = 1); *p = a; return b; }")
+    );
+}
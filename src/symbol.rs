@@ -18,10 +18,22 @@ pub enum Type {
     Char,
     Short,
     Long,
+    Void,
     Struct(String), // struct Test → Struct(Test)
+    Union(String), // union Test → Union(Test)
+    Func(Box<Signature>), // int (*f)(int) → Func(Signature)
+    Pointer(Box<Type>), // int *a[2] → Pointer(Int)（ポインタの配列）
+    Array(Box<Type>, Vec<usize>), // int (*a)[3] → Array(Int, [3])（配列へのポインタ）
     Unknown(String),
 }
 
+/// 関数シグネチャ（戻り値、引数の型）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub ret: (Type, Structure),
+    pub params: Vec<(Type, Structure)>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum Structure {
@@ -30,9 +42,51 @@ pub enum Structure {
     Array(Vec<usize>),
     // ToDo(Arrayみたいに、ここにメンバーをもたせたほうがいい？？)
     Struct,
+    BitField(BitField), // 構造体メンバーのビットフィールド
     Unknown,
 }
 
+/// ビットフィールド
+///
+/// 格納単位はメンバーの型で、ビット位置はメンバー登録時に決まる
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitField {
+    pub width: usize, // ビット幅
+    pub pos: usize,   // 格納単位内のビット位置
+    pub signed: bool, // 符号付きかどうか
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Storage {
+    Auto,   // 指定なし
+    Static, // static
+    Extern, // extern
+}
+
+/// 型修飾子
+///
+/// volatileなオブジェクトへのアクセスは、最適化で削除、統合してはならない
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Qualifier {
+    pub is_const: bool,    // const
+    pub is_volatile: bool, // volatile
+}
+
+impl Qualifier {
+    /// 修飾子の合成
+    pub fn merge(&self, q: &Qualifier) -> Qualifier {
+        Qualifier {
+            is_const: self.is_const || q.is_const,
+            is_volatile: self.is_volatile || q.is_volatile,
+        }
+    }
+
+    /// 修飾子を包含しているか
+    pub fn contains(&self, q: &Qualifier) -> bool {
+        (self.is_const || !q.is_const) && (self.is_volatile || !q.is_volatile)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Symbol {
     pub scope: Scope,         // スコープ
@@ -42,7 +96,12 @@ pub struct Symbol {
     pub pos: usize,           // ポジション
     pub offset: usize,        // オフセット
     pub size: usize,          // サイズ
-    pub members: Vec<Symbol>, // メンバー変数
+    pub storage: Storage,     // 記憶域クラス
+    pub decl: String,         // 宣言位置（診断用）
+    pub no_proto: bool,       // 仮引数の型が未指定（関数シンボルの()宣言）
+    pub qual: Qualifier,      // 型修飾子（配列の場合は要素の修飾子）
+    pub ptr_qual: Qualifier,  // ポインタ参照先の型修飾子
+    pub members: Vec<Symbol>, // メンバー変数（関数シンボルの場合は引数）
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -62,41 +121,67 @@ impl Symbol {
             pos: 0,
             offset: 0,
             size: 0,
+            storage: Storage::Auto,
+            decl: String::new(),
+            no_proto: false,
+            qual: Qualifier::default(),
+            ptr_qual: Qualifier::default(),
             members: vec![],
         }
     }
 
     /// メンバー登録
     pub fn regist_mem(&mut self, mem: Vec<Symbol>) {
-        // サイズを設定したメンバーを保存
-        self.members = mem.into_iter().map(|mut m| {
-            m.size = m.type_size();
+        // サイズとアライメントを考慮したオフセット（ビットフィールドはビット位置も）を設定し、メンバーを保存
+        // （共用体のメンバーは全て先頭に配置）
+        let places = match self.t {
+            Type::Union(_) => vec![(0, 0); mem.len()],
+            _ => layout(&mem).0,
+        };
+        self.members = mem.into_iter().zip(places).map(|(mut m, (offset, pos))| {
+            m.size = m.storage_size();
+            m.offset = offset;
+            if let Structure::BitField(ref mut b) = m.strt {
+                b.pos = pos;
+            }
             m
         }).collect();
     }
 
+    /// 構造体、共用体の定義（タグ）判定
+    pub fn is_tag(&self) -> bool {
+        self.strt == Structure::Struct && matches!(self.t, Type::Struct(ref n) | Type::Union(ref n) if *n == self.var)
+    }
+
+    /// 関数シグネチャ取得
+    pub fn signature(&self) -> Signature {
+        Signature {
+            ret: (self.t.clone(), self.strt.clone()),
+            params: self.members.iter().map(|m| (m.t.clone(), m.strt.clone())).collect(),
+        }
+    }
+
     /// 型に応じたサイズ取得
     pub fn type_size(&self) -> usize {
+        // 配列の場合は要素のサイズ
         match self.strt {
             Structure::Pointer => 8,
-            Structure::Array(_) => 8,
             _ => {
                 match self.t {
                     Type::Int => 4,
                     Type::Char => 1,
+                    Type::Long | Type::Func(_) | Type::Pointer(_) | Type::Array(_, _) => 8,
                     Type::Struct(_) => {
-                        // メンバーのサイズを加算し、返す
-                        if let Some(max_mem) = self.members.iter().max_by(|a, b| a.size.cmp(&b.size)) {
-                            // 各メンバーサイズを加算
-                            let size = self.members.iter().fold(0, |acc, s| acc + s.type_size());
-
-                            // アライメントを考慮したサイズを返す
-                            if size % max_mem.size == 0 { size }
-                            else { (size / max_mem.size) * max_mem.size + max_mem.size }
-                        }
-                        else {
-                            0
-                        }
+                        // メンバーを順に配置し、構造体のアライメントで切り上げたサイズを返す
+                        align_to(layout(&self.members).1, self.align())
+                    }
+                    Type::Union(_) => {
+                        // 最大のメンバー（ビットフィールドは幅）を、共用体のアライメントで切り上げたサイズを返す
+                        let size = |m: &Symbol| match m.strt {
+                            Structure::BitField(ref b) => b.width.div_ceil(8),
+                            _ => m.storage_size(),
+                        };
+                        align_to(self.members.iter().map(size).max().unwrap_or(0), self.align())
                     }
                     _ => 0,
                 }
@@ -104,6 +189,127 @@ impl Symbol {
         }
     }
 
+    /// 領域サイズ取得（配列の場合は要素数を考慮）
+    pub fn storage_size(&self) -> usize {
+        match self.strt {
+            Structure::Array(ref v) => self.type_size() * v.iter().product::<usize>(),
+            _ => self.type_size(),
+        }
+    }
+
+    /// フレーム内の位置取得（rbpからの距離）
+    ///
+    /// 配列、構造体は8バイト単位で切り上げた領域を確保する
+    pub fn frame_offset(&self) -> usize {
+        match self.strt {
+            Structure::Array(_) | Structure::Struct => self.offset + align_to(self.size, 8),
+            _ => self.offset + 8,
+        }
+    }
+
+    /// アライメント取得
+    pub fn align(&self) -> usize {
+        match self.t {
+            // 無名ビットフィールドは構造体のアライメントに影響しない
+            _ if self.var.is_empty() && matches!(self.strt, Structure::BitField(_)) => 1,
+            Type::Struct(_) | Type::Union(_) if self.strt != Structure::Pointer => {
+                self.members.iter().map(|m| m.align()).max().unwrap_or(1)
+            }
+            _ => std::cmp::max(self.type_size(), 1),
+        }
+    }
+}
+
+/// 型名取得（診断メッセージ用）
+pub fn type_name(t: &Type, s: &Structure) -> String {
+    let base = match t {
+        Type::Int => "int".to_string(),
+        Type::Char => "char".to_string(),
+        Type::Short => "short".to_string(),
+        Type::Long => "long".to_string(),
+        Type::Void => "void".to_string(),
+        Type::Struct(ref n) => format!("struct {}", n),
+        Type::Union(ref n) => format!("union {}", n),
+        Type::Func(ref sig) => {
+            let params: Vec<String> = sig.params.iter().map(|p| type_name(&p.0, &p.1)).collect();
+            let ret = type_name(&sig.ret.0, &sig.ret.1);
+            return match s {
+                Structure::Identifier => format!("{}({})", ret, params.join(", ")),
+                _ => format!("{}(*)({})", ret, params.join(", ")),
+            };
+        }
+        Type::Pointer(ref t) => format!("{}*", type_name(t, &Structure::Identifier)),
+        Type::Array(ref t, ref v) => {
+            let dims: String = v.iter().map(|n| format!("[{}]", n)).collect();
+            return match s {
+                Structure::Pointer => format!("{}(*){}", type_name(t, &Structure::Identifier), dims),
+                _ => format!("{}{}", type_name(t, &Structure::Identifier), dims),
+            };
+        }
+        Type::Unknown(ref n) => n.clone(),
+    };
+    match s {
+        Structure::Pointer => format!("{}*", base),
+        Structure::Array(ref v) => {
+            v.iter().fold(base, |acc, n| format!("{}[{}]", acc, n))
+        }
+        _ => base,
+    }
+}
+
+/// 変数名取得（診断メッセージ用）
+///
+/// ブロック内で同名の変数を区別する為に付けた番号（名前.番号）を除く
+pub fn source_name(var: &str) -> &str {
+    var.split('.').next().unwrap_or(var)
+}
+
+/// 構造体メンバーの配置
+///
+/// System V ABIに従い、各メンバーの(オフセット, ビット位置)と末尾までのサイズを返す
+/// ビットフィールドは型の格納単位をまたがないよう配置し、幅0の場合は次の格納単位へ進める
+fn layout(members: &[Symbol]) -> (Vec<(usize, usize)>, usize) {
+    let mut bits = 0;
+    let places = members.iter().map(|m| match m.strt {
+        Structure::BitField(ref b) => {
+            let unit = m.type_size() * 8;
+            if b.width == 0 || bits % unit + b.width > unit {
+                bits = align_to(bits, unit);
+            }
+            let place = (bits / unit * m.type_size(), bits % unit);
+            bits += b.width;
+            place
+        }
+        _ => {
+            let offset = align_to(bits.div_ceil(8), m.align());
+            bits = (offset + m.storage_size()) * 8;
+            (offset, 0)
+        }
+    }).collect();
+    (places, bits.div_ceil(8))
+}
+
+/// アライメント境界への切り上げ
+pub fn align_to(n: usize, align: usize) -> usize {
+    n.div_ceil(align) * align
+}
+
+/// 編集距離（挿入、削除、置換、隣接する文字の入れ替えを1とするダメラウ・レーベンシュタイン距離）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    (0..=a.len()).for_each(|i| d[i][0] = i);
+    (0..=b.len()).for_each(|j| d[0][j] = j);
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j - 1] + cost).min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
 }
 
 impl SymbolTable {
@@ -144,48 +350,28 @@ impl SymbolTable {
             .iter()
             .filter(|s| s.scope == sym.scope)
             .cloned()
-            .last();
+            .next_back();
 
         // 前の要素をもとにオフセット等の情報を算出
         match last {
             None => {
                 // 配列の場合、要素数を考慮し、サイズ算出
-                let size = match sym.strt {
-                    Structure::Array(ref v) => sym.type_size() * v.iter().product::<usize>(),
-                    _ => sym.type_size()
-                };
                 reg.pos = 1;
                 reg.offset = 0;
-                reg.size = size;
+                reg.size = sym.storage_size();
                 self.table.push(reg);
             }
             Some(pre_sym) => {
                 // 配列の場合、要素数を考慮
-                match pre_sym.strt {
-                    Structure::Array(ref v) => {
-                        // 要素数分、オフセットなどを計算
-                        let count: usize = v.iter().product();
-                        reg.pos = pre_sym.pos + count;
-                        reg.size = sym.type_size() * count;
-                        reg.offset = pre_sym.offset + pre_sym.type_size() * count;
-                        reg.offset = (reg.offset / 8) * 8;
-                        self.table.push(reg);
-                    }
-                    Structure::Pointer => {
-                        reg.pos = pre_sym.pos + 1;
-                        reg.size = sym.type_size();
-                        reg.offset = pre_sym.offset + pre_sym.type_size();
-                        reg.offset = (reg.offset / 8) * 8;
-                        self.table.push(reg);
-                    }
-                    _ => {
-                        reg.pos = pre_sym.pos + 1;
-                        reg.size = sym.type_size();
-                        reg.offset = pre_sym.offset + pre_sym.type_size();
-                        reg.offset = (reg.offset / 8) * 8 + 8;
-                        self.table.push(reg);
-                    }
-                }
+                reg.pos = match pre_sym.strt {
+                    Structure::Array(ref v) => pre_sym.pos + v.iter().product::<usize>(),
+                    _ => pre_sym.pos + 1,
+                };
+                reg.size = sym.storage_size();
+
+                // 前の要素の領域の直後に配置
+                reg.offset = pre_sym.frame_offset();
+                self.table.push(reg);
             }
         };
     }
@@ -199,6 +385,50 @@ impl SymbolTable {
             .cloned()
     }
 
+    // 類似シンボル名の取得
+    //
+    // 参照可能な名前（使用位置で有効なローカル変数、グローバル、関数）のうち、編集距離が最も近いもの
+    // ローカル変数はvisibleに含まれるシンボル名のみとし、閉じたブロックの変数は候補としない
+    // 名前の長さの1/3（最低1）を超えて離れている場合は候補としない
+    pub fn similar_name(&self, scope: &Scope, var: &str, visible: &[String]) -> Option<String> {
+        let limit = (var.chars().count() / 3).max(1);
+        let rank = |s: &Symbol| match s.scope {
+            ref sc if sc == scope => 0,
+            Scope::Global => 1,
+            _ => 2,
+        };
+        self.table
+            .iter()
+            .filter(|s| (s.scope == *scope && visible.contains(&s.var)) || s.scope == Scope::Global || s.scope == Scope::Func)
+            // 構造体、共用体の定義名は識別子ではない
+            .filter(|s| !s.is_tag())
+            .map(|s| (edit_distance(source_name(&s.var), var), rank(s), source_name(&s.var).to_string()))
+            .filter(|(d, _, _)| *d <= limit)
+            .min_by_key(|(d, r, _)| (*d, *r))
+            .map(|(_, _, n)| n)
+    }
+
+    // シンボル一覧
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.table
+    }
+
+    // 型更新（サイズも再計算）
+    pub fn set_type(&mut self, scope: &Scope, var: &str, t: Type, strt: Structure) {
+        if let Some(s) = self.table.iter_mut().find(|s| s.scope == *scope && s.var == *var) {
+            s.t = t;
+            s.strt = strt;
+            s.size = s.storage_size();
+        }
+    }
+
+    // 記憶域クラス更新
+    pub fn set_storage(&mut self, scope: &Scope, var: &str, storage: Storage) {
+        if let Some(s) = self.table.iter_mut().find(|s| s.scope == *scope && s.var == *var) {
+            s.storage = storage;
+        }
+    }
+
     // カウント取得
     #[allow(dead_code)]
     pub fn count_all(&self) -> usize {
@@ -212,6 +442,16 @@ impl SymbolTable {
             .count()
     }
 
+    // フレームサイズ（ローカル変数が占める領域）
+    pub fn frame_size(&self, scope: &Scope) -> usize {
+        self.table
+            .iter()
+            .filter(|s| s.scope == *scope)
+            .map(|s| s.frame_offset())
+            .max()
+            .unwrap_or(0)
+    }
+
     // 変数トータルサイズ
     #[allow(dead_code)]
     pub fn size(&self, scope: &Scope) -> usize {
@@ -223,9 +463,7 @@ impl SymbolTable {
                 Structure::Pointer => acc + 8,
                 Structure::Identifier => acc + sym.type_size(),
                 // 配列の場合、要素数を考慮
-                Structure::Array(ref items) => {
-                    acc + items.iter().fold(0, |acc2, i| acc2 + (i * sym.type_size()))
-                }
+                Structure::Array(_) => acc + sym.storage_size(),
                 _ => acc,
             })
     }
@@ -251,7 +489,7 @@ mod test {
             assert_eq!(table.count_all(), 1);
             assert_eq!(table.count(&Scope::Global), 1);
             assert_eq!(
-                table.search(&Scope::Global, &"a".to_string()),
+                table.search(&Scope::Global, "a"),
                 Some(Symbol {
                     scope: Scope::Global,
                     var: "a".to_string(),
@@ -260,6 +498,11 @@ mod test {
                     pos: 1,
                     offset: 0,
                     size: 4,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -284,7 +527,7 @@ mod test {
             assert_eq!(table.count_all(), 2);
             assert_eq!(table.count(&Scope::Local("test".to_string())), 2);
             assert_eq!(
-                table.search(&Scope::Local("test".to_string()), &"a".to_string()),
+                table.search(&Scope::Local("test".to_string()), "a"),
                 Some(Symbol {
                     scope: Scope::Local("test".to_string()),
                     var: "a".to_string(),
@@ -293,11 +536,16 @@ mod test {
                     pos: 1,
                     offset: 0,
                     size: 4,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
             assert_eq!(
-                table.search(&Scope::Local("test".to_string()), &"b".to_string()),
+                table.search(&Scope::Local("test".to_string()), "b"),
                 Some(Symbol {
                     scope: Scope::Local("test".to_string()),
                     var: "b".to_string(),
@@ -306,6 +554,11 @@ mod test {
                     pos: 2,
                     offset: 8,
                     size: 4,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -330,7 +583,7 @@ mod test {
             assert_eq!(table.count_all(), 2);
             assert_eq!(table.count(&Scope::Local("test".to_string())), 2);
             assert_eq!(
-                table.search(&Scope::Local("test".to_string()), &"a".to_string()),
+                table.search(&Scope::Local("test".to_string()), "a"),
                 Some(Symbol {
                     scope: Scope::Local("test".to_string()),
                     var: "a".to_string(),
@@ -339,11 +592,16 @@ mod test {
                     pos: 1,
                     offset: 0,
                     size: 4,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
             assert_eq!(
-                table.search(&Scope::Local("test".to_string()), &"b".to_string()),
+                table.search(&Scope::Local("test".to_string()), "b"),
                 Some(Symbol {
                     scope: Scope::Local("test".to_string()),
                     var: "b".to_string(),
@@ -352,6 +610,11 @@ mod test {
                     pos: 2,
                     offset: 8,
                     size: 1,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -366,11 +629,11 @@ mod test {
             ));
 
             // 期待値
-            assert_eq!(table.size(&Scope::Global), 80);
+            assert_eq!(table.size(&Scope::Global), 40);
             assert_eq!(table.count_all(), 1);
             assert_eq!(table.count(&Scope::Global), 1);
             assert_eq!(
-                table.search(&Scope::Global, &"a".to_string()),
+                table.search(&Scope::Global, "a"),
                 Some(Symbol {
                     scope: Scope::Global,
                     var: "a".to_string(),
@@ -378,7 +641,12 @@ mod test {
                     strt: Structure::Array(vec![10]),
                     pos: 1,
                     offset: 0,
-                    size: 80,
+                    size: 40,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -393,11 +661,11 @@ mod test {
             ));
 
             // 期待値
-            assert_eq!(table.size(&Scope::Global), 80);
+            assert_eq!(table.size(&Scope::Global), 10);
             assert_eq!(table.count_all(), 1);
             assert_eq!(table.count(&Scope::Global), 1);
             assert_eq!(
-                table.search(&Scope::Global, &"a".to_string()),
+                table.search(&Scope::Global, "a"),
                 Some(Symbol {
                     scope: Scope::Global,
                     var: "a".to_string(),
@@ -405,7 +673,12 @@ mod test {
                     strt: Structure::Array(vec![10]),
                     pos: 1,
                     offset: 0,
-                    size: 80,
+                    size: 10,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -424,7 +697,7 @@ mod test {
             assert_eq!(table.size(&Scope::Local("test".to_string())), 8);
             assert_eq!(table.count(&Scope::Local("test".to_string())), 1);
             assert_eq!(
-                table.search(&Scope::Local("test".to_string()), &"a".to_string()),
+                table.search(&Scope::Local("test".to_string()), "a"),
                 Some(Symbol {
                     scope: Scope::Local("test".to_string()),
                     var: "a".to_string(),
@@ -433,6 +706,11 @@ mod test {
                     pos: 1,
                     offset: 0,
                     size: 8,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -459,7 +737,7 @@ mod test {
             assert_eq!(table.count(&Scope::Local("test".to_string())), 1);
             assert_eq!(table.size(&Scope::Local("test".to_string())), 1);
             assert_eq!(
-                table.search(&Scope::Global, &"a".to_string()),
+                table.search(&Scope::Global, "a"),
                 Some(Symbol {
                     scope: Scope::Global,
                     var: "a".to_string(),
@@ -468,11 +746,16 @@ mod test {
                     pos: 1,
                     offset: 0,
                     size: 4,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
             assert_eq!(
-                table.search(&Scope::Local("test".to_string()), &"a".to_string()),
+                table.search(&Scope::Local("test".to_string()), "a"),
                 Some(Symbol {
                     scope: Scope::Local("test".to_string()),
                     var: "a".to_string(),
@@ -481,6 +764,11 @@ mod test {
                     pos: 1,
                     offset: 0,
                     size: 1,
+                    storage: Storage::Auto,
+                    decl: String::new(),
+                    no_proto: false,
+                    qual: Qualifier::default(),
+                    ptr_qual: Qualifier::default(),
                     members: vec![],
                 })
             );
@@ -498,6 +786,11 @@ mod test {
                 pos: 0,
                 offset: 0,
                 size: 1,
+                storage: Storage::Auto,
+                decl: String::new(),
+                no_proto: false,
+                qual: Qualifier::default(),
+                ptr_qual: Qualifier::default(),
                 members: vec![],
             };
             assert_eq!( 1, sym.type_size());
@@ -511,6 +804,11 @@ mod test {
                 pos: 0,
                 offset: 0,
                 size: 4,
+                storage: Storage::Auto,
+                decl: String::new(),
+                no_proto: false,
+                qual: Qualifier::default(),
+                ptr_qual: Qualifier::default(),
                 members: vec![],
             };
             assert_eq!(4, sym.type_size());
@@ -524,6 +822,11 @@ mod test {
                 pos: 0,
                 offset: 0,
                 size: 0,
+                storage: Storage::Auto,
+                decl: String::new(),
+                no_proto: false,
+                qual: Qualifier::default(),
+                ptr_qual: Qualifier::default(),
                 members: vec![
                     Symbol {
                         scope: Scope::Local("test".to_string()),
@@ -533,6 +836,11 @@ mod test {
                         pos: 0,
                         offset: 0,
                         size: 1,
+                        storage: Storage::Auto,
+                        decl: String::new(),
+                        no_proto: false,
+                        qual: Qualifier::default(),
+                        ptr_qual: Qualifier::default(),
                         members: vec![],
                     }
                 ],
@@ -548,6 +856,11 @@ mod test {
                 pos: 0,
                 offset: 0,
                 size: 0,
+                storage: Storage::Auto,
+                decl: String::new(),
+                no_proto: false,
+                qual: Qualifier::default(),
+                ptr_qual: Qualifier::default(),
                 members: vec![
                     Symbol {
                         scope: Scope::Local("test".to_string()),
@@ -557,6 +870,11 @@ mod test {
                         pos: 0,
                         offset: 0,
                         size: 1,
+                        storage: Storage::Auto,
+                        decl: String::new(),
+                        no_proto: false,
+                        qual: Qualifier::default(),
+                        ptr_qual: Qualifier::default(),
                         members: vec![],
                     },
                     Symbol {
@@ -567,6 +885,11 @@ mod test {
                         pos: 0,
                         offset: 0,
                         size: 1,
+                        storage: Storage::Auto,
+                        decl: String::new(),
+                        no_proto: false,
+                        qual: Qualifier::default(),
+                        ptr_qual: Qualifier::default(),
                         members: vec![],
                     }
                 ],
@@ -582,6 +905,11 @@ mod test {
                 pos: 0,
                 offset: 0,
                 size: 0,
+                storage: Storage::Auto,
+                decl: String::new(),
+                no_proto: false,
+                qual: Qualifier::default(),
+                ptr_qual: Qualifier::default(),
                 members: vec![
                     Symbol {
                         scope: Scope::Local("test".to_string()),
@@ -591,6 +919,11 @@ mod test {
                         pos: 0,
                         offset: 0,
                         size: 1,
+                        storage: Storage::Auto,
+                        decl: String::new(),
+                        no_proto: false,
+                        qual: Qualifier::default(),
+                        ptr_qual: Qualifier::default(),
                         members: vec![],
                     },
                     Symbol {
@@ -601,6 +934,11 @@ mod test {
                         pos: 0,
                         offset: 0,
                         size: 4,
+                        storage: Storage::Auto,
+                        decl: String::new(),
+                        no_proto: false,
+                        qual: Qualifier::default(),
+                        ptr_qual: Qualifier::default(),
                         members: vec![],
                     }
                 ],
@@ -608,4 +946,161 @@ mod test {
             assert_eq!(8, sym.type_size());
         }
     }
+
+    #[test]
+    fn test_struct_layout() {
+        let mut sym = Symbol::new(
+            Scope::Global,
+            "x".to_string(),
+            Type::Struct("A".to_string()),
+            Structure::Struct,
+        );
+        sym.regist_mem(vec![
+            Symbol::new(Scope::Global, "a".to_string(), Type::Char, Structure::Identifier),
+            Symbol::new(Scope::Global, "b".to_string(), Type::Int, Structure::Identifier),
+            Symbol::new(Scope::Global, "c".to_string(), Type::Char, Structure::Identifier),
+        ]);
+
+        // メンバーはアライメント境界に配置される
+        assert_eq!(vec![0, 4, 8], sym.members.iter().map(|m| m.offset).collect::<Vec<usize>>());
+        assert_eq!(12, sym.type_size());
+        assert_eq!(4, sym.align());
+
+        let mut table = SymbolTable::new();
+        table.register_sym(Symbol::new(Scope::Global, "b".to_string(), Type::Int, Structure::Identifier));
+        table.register_sym(Symbol::new(Scope::Global, "a".to_string(), Type::Int, Structure::Array(vec![2, 3])));
+        table.register_sym(sym);
+        assert_eq!(24, table.search(&Scope::Global, "a").unwrap().size);
+        assert_eq!(12, table.search(&Scope::Global, "x").unwrap().size);
+    }
+
+    #[test]
+    fn test_bit_field_layout() {
+        let bit = |n: &str, t: Type, width: usize| {
+            let b = BitField { width, pos: 0, signed: true };
+            Symbol::new(Scope::Global, n.to_string(), t, Structure::BitField(b))
+        };
+        let place = |sym: &Symbol| sym.members.iter().map(|m| match m.strt {
+            Structure::BitField(ref b) => (m.offset, b.pos),
+            _ => (m.offset, 0),
+        }).collect::<Vec<(usize, usize)>>();
+
+        // 格納単位を共有し、またがる場合は次の単位へ
+        let mut sym = Symbol::new(Scope::Global, "x".to_string(), Type::Struct("A".to_string()), Structure::Struct);
+        sym.regist_mem(vec![
+            Symbol::new(Scope::Global, "a".to_string(), Type::Char, Structure::Identifier),
+            bit("b", Type::Int, 4),
+            bit("c", Type::Int, 24),
+            bit("d", Type::Char, 3),
+        ]);
+        assert_eq!(vec![(0, 0), (0, 8), (4, 0), (7, 0)], place(&sym));
+        assert_eq!(8, sym.type_size());
+        assert_eq!(4, sym.align());
+
+        // 幅0は次の格納単位へ揃え、無名ビットフィールドはアライメントに影響しない
+        let mut sym = Symbol::new(Scope::Global, "y".to_string(), Type::Struct("B".to_string()), Structure::Struct);
+        sym.regist_mem(vec![
+            bit("a", Type::Char, 1),
+            bit("", Type::Int, 0),
+            bit("b", Type::Char, 2),
+            bit("", Type::Int, 9),
+        ]);
+        assert_eq!(vec![(0, 0), (4, 0), (4, 0), (4, 2)], place(&sym));
+        assert_eq!(6, sym.type_size());
+        assert_eq!(1, sym.align());
+    }
+
+    #[test]
+    fn test_union_layout() {
+        let bit = |n: &str, t: Type, width: usize| {
+            let b = BitField { width, pos: 5, signed: true };
+            Symbol::new(Scope::Global, n.to_string(), t, Structure::BitField(b))
+        };
+
+        // メンバーは全て先頭に配置し、最大のメンバーをアライメントで切り上げる
+        let mut sym = Symbol::new(Scope::Global, "u".to_string(), Type::Union("U".to_string()), Structure::Struct);
+        sym.regist_mem(vec![
+            Symbol::new(Scope::Global, "a".to_string(), Type::Char, Structure::Identifier),
+            Symbol::new(Scope::Global, "b".to_string(), Type::Int, Structure::Identifier),
+            Symbol::new(Scope::Global, "c".to_string(), Type::Char, Structure::Array(vec![5])),
+        ]);
+        assert_eq!(vec![0, 0, 0], sym.members.iter().map(|m| m.offset).collect::<Vec<usize>>());
+        assert_eq!(8, sym.type_size());
+        assert_eq!(4, sym.align());
+
+        // ビットフィールドも先頭のビットから配置（無名ビットフィールドはアライメントに影響しない）
+        let mut sym = Symbol::new(Scope::Global, "v".to_string(), Type::Union("V".to_string()), Structure::Struct);
+        sym.regist_mem(vec![bit("", Type::Int, 9), bit("a", Type::Char, 3)]);
+        let places = sym.members.iter().map(|m| match m.strt {
+            Structure::BitField(ref b) => (m.offset, b.pos),
+            _ => (m.offset, 0),
+        }).collect::<Vec<(usize, usize)>>();
+        assert_eq!(vec![(0, 0), (0, 0)], places);
+        assert_eq!(2, sym.type_size());
+        assert_eq!(1, sym.align());
+        assert_eq!("union V", type_name(&sym.t, &Structure::Identifier));
+    }
+
+    #[test]
+    fn test_frame_offset() {
+        {
+            let mut table = SymbolTable::new();
+            let scope = Scope::Local("test".to_string());
+            let mut def = Symbol::new(scope.clone(), "P".to_string(), Type::Struct("P".to_string()), Structure::Struct);
+            def.regist_mem(vec![
+                Symbol::new(scope.clone(), "a".to_string(), Type::Int, Structure::Identifier),
+                Symbol::new(scope.clone(), "b".to_string(), Type::Int, Structure::Identifier),
+                Symbol::new(scope.clone(), "c".to_string(), Type::Char, Structure::Identifier),
+            ]);
+            let mut var = Symbol::new(scope.clone(), "s".to_string(), Type::Struct("P".to_string()), Structure::Struct);
+            var.regist_mem(def.members.clone());
+            table.register_sym(Symbol::new(scope.clone(), "x".to_string(), Type::Int, Structure::Identifier));
+            table.register_sym(var);
+            table.register_sym(Symbol::new(scope.clone(), "y".to_string(), Type::Int, Structure::Identifier));
+
+            // 構造体は8バイト単位で切り上げた領域を占める
+            let x = table.search(&scope, "x").unwrap();
+            let s = table.search(&scope, "s").unwrap();
+            let y = table.search(&scope, "y").unwrap();
+            assert_eq!(12, s.size);
+            assert_eq!(8, x.frame_offset());
+            assert_eq!(24, s.frame_offset());
+            assert_eq!(32, y.frame_offset());
+            assert!(y.frame_offset() - 8 >= s.frame_offset());
+            assert_eq!(32, table.frame_size(&scope));
+        }
+    }
+
+    #[test]
+    fn test_similar_name() {
+        assert_eq!(0, edit_distance("count", "count"));
+        assert_eq!(1, edit_distance("count", "cout"));
+        assert_eq!(2, edit_distance("abc", "bca"));
+        assert_eq!(1, edit_distance("valeu", "value"));
+        assert_eq!(3, edit_distance("", "abc"));
+        {
+            let mut table = SymbolTable::new();
+            let scope = Scope::Local("f".to_string());
+            table.register_sym(Symbol::new(Scope::Func, "count".to_string(), Type::Int, Structure::Identifier));
+            table.register_sym(Symbol::new(Scope::Global, "total".to_string(), Type::Int, Structure::Identifier));
+            table.register_sym(Symbol::new(Scope::Global, "val".to_string(), Type::Int, Structure::Identifier));
+            table.register_sym(Symbol::new(scope.clone(), "vals".to_string(), Type::Int, Structure::Identifier));
+            table.register_sym(Symbol::new(Scope::Local("g".to_string()), "totl".to_string(), Type::Int, Structure::Identifier));
+            table.register_sym(Symbol::new(scope.clone(), "Node".to_string(), Type::Struct("Node".to_string()), Structure::Struct));
+            table.register_sym(Symbol::new(scope.clone(), "value".to_string(), Type::Int, Structure::Identifier));
+            let visible = vec!["vals".to_string(), "value".to_string()];
+
+            // 同じ距離であれば、現在のスコープを優先
+            assert_eq!(Some("count".to_string()), table.similar_name(&scope, "cont", &visible));
+            assert_eq!(Some("vals".to_string()), table.similar_name(&scope, "vala", &visible));
+            assert_eq!(Some("total".to_string()), table.similar_name(&scope, "totl", &visible));
+            // 他の関数のローカル変数、構造体定義名、離れすぎた名前は候補としない
+            assert_eq!(None, table.similar_name(&Scope::Global, "vals2", &[]));
+            assert_eq!(None, table.similar_name(&scope, "node", &visible));
+            assert_eq!(None, table.similar_name(&scope, "xyz", &visible));
+            // 隣接する文字の入れ替えは1文字の誤り、使用位置で無効なローカル変数は候補としない
+            assert_eq!(Some("value".to_string()), table.similar_name(&scope, "valeu", &visible));
+            assert_eq!(None, table.similar_name(&scope, "valeu", &["vals".to_string()]));
+        }
+    }
 }
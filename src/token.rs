@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[doc = "トークン"]
 // トークン識別子.
 #[derive(Debug, Clone, PartialEq)]
@@ -47,19 +49,122 @@ pub enum Token {
     IntPointer,       // intポインタ
     Char,             // char型
     CharPointer,      // charポインタ
+    Void,             // void型
+    VoidPointer,      // voidポインタ
+    Signed,           // signed型指定子
+    Unsigned,         // unsigned型指定子
     Inc,              // 後置インクリメント
     Dec,              // 後置デクリメント
     StringLiteral,    // 文字列リテラル
     SizeOf,           // sizeof演算子
+    AlignOf,          // _Alignof演算子
+    StaticAssert,     // _Static_assert宣言
     PlusAssign,       // +=演算子
     MinusAssign,      // -=演算子
     MultipleAssign,   // *=演算子
     DivisionAssign,   // /=演算子
     RemainderAssign,  // %=演算子
+    BitAndAssign,     // &=演算子
+    BitOrAssign,      // |=演算子
+    BitXorAssign,     // ^=演算子
+    LeftShiftAssign,  // <<=演算子
+    RightShiftAssign, // >>=演算子
     Struct,           // struct宣言
+    Union,            // union宣言
+    Enum,             // enum宣言
+    Static,           // static記憶域クラス
+    Extern,           // extern記憶域クラス
+    Const,            // const型修飾子
+    Volatile,         // volatile型修飾子
+    Dot,              // メンバー参照演算子(.)
+    Arrow,            // メンバー参照演算子(->)
     End,              // 終了.
 }
 
+// 診断メッセージ用のトークン表記（記号、キーワードはつづり）
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match *self {
+            Token::If => "if",
+            Token::Else => "else",
+            Token::Do => "do",
+            Token::While => "while",
+            Token::For => "for",
+            Token::Continue => "continue",
+            Token::Break => "break",
+            Token::Return => "return",
+            Token::BitReverse => "~",
+            Token::And => "&",
+            Token::BitOr => "|",
+            Token::BitXor => "^",
+            Token::LeftShift => "<<",
+            Token::RightShift => ">>",
+            Token::Question => "?",
+            Token::Colon => ":",
+            Token::Comma => ",",
+            Token::LogicalAnd => "&&",
+            Token::LogicalOr => "||",
+            Token::Equal => "==",
+            Token::NotEqual => "!=",
+            Token::LessThan => "<",
+            Token::GreaterThan => ">",
+            Token::LessThanEqual => "<=",
+            Token::GreaterThanEqual => ">=",
+            Token::Number => return write!(f, "number"),
+            Token::Variable => return write!(f, "identifier"),
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Multi => "*",
+            Token::Division => "/",
+            Token::Remainder => "%",
+            Token::LeftParen => "(",
+            Token::RightParen => ")",
+            Token::LeftBrace => "{",
+            Token::RightBrace => "}",
+            Token::LeftBracket => "[",
+            Token::RightBracket => "]",
+            Token::Not => "!",
+            Token::SemiColon => ";",
+            Token::Assign => "=",
+            Token::Int => "int",
+            Token::IntPointer => "int *",
+            Token::Char => "char",
+            Token::CharPointer => "char *",
+            Token::Void => "void",
+            Token::VoidPointer => "void *",
+            Token::Signed => "signed",
+            Token::Unsigned => "unsigned",
+            Token::Inc => "++",
+            Token::Dec => "--",
+            Token::StringLiteral => return write!(f, "string literal"),
+            Token::SizeOf => "sizeof",
+            Token::AlignOf => "_Alignof",
+            Token::StaticAssert => "_Static_assert",
+            Token::PlusAssign => "+=",
+            Token::MinusAssign => "-=",
+            Token::MultipleAssign => "*=",
+            Token::DivisionAssign => "/=",
+            Token::RemainderAssign => "%=",
+            Token::BitAndAssign => "&=",
+            Token::BitOrAssign => "|=",
+            Token::BitXorAssign => "^=",
+            Token::LeftShiftAssign => "<<=",
+            Token::RightShiftAssign => ">>=",
+            Token::Struct => "struct",
+            Token::Union => "union",
+            Token::Enum => "enum",
+            Token::Static => "static",
+            Token::Extern => "extern",
+            Token::Const => "const",
+            Token::Volatile => "volatile",
+            Token::Dot => ".",
+            Token::Arrow => "->",
+            Token::End => return write!(f, "end of file"),
+        };
+        write!(f, "'{}'", s)
+    }
+}
+
 // 位置情報
 #[derive(Debug, Clone, PartialEq)]
 pub struct PosInfo {
@@ -78,6 +183,13 @@ impl PosInfo {
     }
 }
 
+// 診断メッセージ用の位置表記（ファイル名:行:列）
+impl fmt::Display for PosInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.name, self.row, self.col)
+    }
+}
+
 // トークンデータ.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TokenInfo {
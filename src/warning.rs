@@ -0,0 +1,220 @@
+use std::fmt;
+
+#[doc = "警告の種類"]
+// 名前は-W<name>、-Wno-<name>で指定する
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WarningKind {
+    ReturnType,            // 戻り値のある関数の終端への到達.
+    PointerIntegerCompare, // ポインタと整数の比較.
+    ArrayBounds,           // 配列の範囲外への定数添字、ポインタ演算.
+    DivByZero,             // 定数によるゼロ除算.
+    UnusedVariable,        // 未使用のローカル変数.
+    UnusedFunction,        // 未使用の内部結合の関数.
+    Parentheses,           // 条件式としての代入.
+    Uninitialized,         // 未初期化変数の使用.
+    UnusedParameter,       // 未使用の仮引数.
+    Conversion,            // 暗黙の縮小変換.
+    UnreachableCode,       // 到達しないコード.
+    ExcessInitializers,    // 配列の要素数を超える初期化子.
+}
+
+// 警告グループ
+#[derive(Debug, PartialEq)]
+enum Group {
+    Default, // 指定なしで有効
+    All,     // -Wall
+    Extra,   // -Wextra
+}
+
+const KINDS: [WarningKind; 12] = [
+    WarningKind::ReturnType,
+    WarningKind::PointerIntegerCompare,
+    WarningKind::ArrayBounds,
+    WarningKind::DivByZero,
+    WarningKind::UnusedVariable,
+    WarningKind::UnusedFunction,
+    WarningKind::Parentheses,
+    WarningKind::Uninitialized,
+    WarningKind::UnusedParameter,
+    WarningKind::Conversion,
+    WarningKind::UnreachableCode,
+    WarningKind::ExcessInitializers,
+];
+
+impl WarningKind {
+    // オプション名
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarningKind::ReturnType => "return-type",
+            WarningKind::PointerIntegerCompare => "pointer-integer-compare",
+            WarningKind::ArrayBounds => "array-bounds",
+            WarningKind::DivByZero => "div-by-zero",
+            WarningKind::UnusedVariable => "unused-variable",
+            WarningKind::UnusedFunction => "unused-function",
+            WarningKind::Parentheses => "parentheses",
+            WarningKind::Uninitialized => "uninitialized",
+            WarningKind::UnusedParameter => "unused-parameter",
+            WarningKind::Conversion => "conversion",
+            WarningKind::UnreachableCode => "unreachable-code",
+            WarningKind::ExcessInitializers => "excess-initializers",
+        }
+    }
+
+    // 所属グループ
+    fn group(&self) -> Group {
+        match self {
+            WarningKind::ReturnType
+            | WarningKind::PointerIntegerCompare
+            | WarningKind::ArrayBounds
+            | WarningKind::DivByZero
+            | WarningKind::ExcessInitializers => Group::Default,
+            WarningKind::UnusedVariable
+            | WarningKind::UnusedFunction
+            | WarningKind::Parentheses
+            | WarningKind::Uninitialized => Group::All,
+            WarningKind::UnusedParameter | WarningKind::Conversion | WarningKind::UnreachableCode => Group::Extra,
+        }
+    }
+}
+
+#[doc = "警告"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub kind: WarningKind,
+    pub pos: String, // 位置（ファイル名:行:列）
+    pub msg: String,
+}
+
+impl Warning {
+    pub fn new(kind: WarningKind, pos: String, msg: String) -> Self {
+        Warning { kind, pos, msg }
+    }
+
+    // -Werrorでエラーとする場合のメッセージ
+    pub fn to_error(&self) -> String {
+        format!("{}: {} [-Werror={}]", self.pos, self.msg, self.kind.name())
+    }
+}
+
+// 診断メッセージ用の表記（位置: warning: メッセージ [-W名前]）
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.pos.is_empty() {
+            write!(f, "{}: ", self.pos)?;
+        }
+        write!(f, "warning: {} [-W{}]", self.msg, self.kind.name())
+    }
+}
+
+#[doc = "警告オプション"]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarningOptions {
+    enabled: Vec<WarningKind>,
+    pub werror: bool, // 警告をエラーとして扱う
+}
+
+impl Default for WarningOptions {
+    fn default() -> Self {
+        WarningOptions {
+            enabled: KINDS.iter().filter(|k| k.group() == Group::Default).cloned().collect(),
+            werror: false,
+        }
+    }
+}
+
+impl WarningOptions {
+    // -Wオプションの解析
+    //
+    // 指定順に適用する（-Wall -Wno-unused-variable等）
+    pub fn apply(&mut self, opt: &str) -> Result<(), String> {
+        let name = match opt.strip_prefix("-W") {
+            Some(n) => n,
+            None => return Err(format!("unknown option: {}", opt)),
+        };
+        if name == "error" {
+            self.werror = true;
+            return Ok(());
+        }
+        let (on, name) = match name.strip_prefix("no-") {
+            Some(n) => (false, n),
+            None => (true, name),
+        };
+        let kinds: Vec<WarningKind> = match name {
+            "all" => KINDS.iter().filter(|k| k.group() == Group::All).cloned().collect(),
+            "extra" => KINDS.iter().filter(|k| k.group() == Group::Extra).cloned().collect(),
+            _ => KINDS.iter().filter(|k| k.name() == name).cloned().collect(),
+        };
+        if kinds.is_empty() {
+            return Err(format!("unknown warning option: {}", opt));
+        }
+        kinds.iter().for_each(|k| self.set(*k, on));
+        Ok(())
+    }
+
+    // 有効判定
+    pub fn is_enabled(&self, kind: WarningKind) -> bool {
+        self.enabled.contains(&kind)
+    }
+
+    fn set(&mut self, kind: WarningKind, on: bool) {
+        self.enabled.retain(|k| *k != kind);
+        if on {
+            self.enabled.push(kind);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        {
+            // 指定なしは既定グループのみ
+            let opts = WarningOptions::default();
+            assert!(opts.is_enabled(WarningKind::ReturnType));
+            assert!(opts.is_enabled(WarningKind::PointerIntegerCompare));
+            assert!(opts.is_enabled(WarningKind::ArrayBounds));
+            assert!(opts.is_enabled(WarningKind::DivByZero));
+            assert!(!opts.is_enabled(WarningKind::UnusedVariable));
+            assert!(!opts.is_enabled(WarningKind::UnusedParameter));
+            assert!(!opts.werror);
+        }
+        {
+            // グループ、個別指定を順に適用
+            let mut opts = WarningOptions::default();
+            ["-Wall", "-Wno-unused-variable", "-Wunreachable-code", "-Wno-return-type", "-Werror"]
+                .iter()
+                .for_each(|o| opts.apply(o).unwrap());
+            assert!(opts.is_enabled(WarningKind::UnusedFunction));
+            assert!(opts.is_enabled(WarningKind::Parentheses));
+            assert!(opts.is_enabled(WarningKind::Uninitialized));
+            assert!(!opts.is_enabled(WarningKind::UnusedVariable));
+            assert!(opts.is_enabled(WarningKind::UnreachableCode));
+            assert!(!opts.is_enabled(WarningKind::Conversion));
+            assert!(!opts.is_enabled(WarningKind::ReturnType));
+            assert!(opts.werror);
+
+            opts.apply("-Wextra").unwrap();
+            assert!(opts.is_enabled(WarningKind::UnusedParameter));
+            assert!(opts.is_enabled(WarningKind::Conversion));
+        }
+        {
+            let mut opts = WarningOptions::default();
+            assert_eq!(Err("unknown warning option: -Wfoo".to_string()), opts.apply("-Wfoo"));
+            assert_eq!(Err("unknown option: -O2".to_string()), opts.apply("-O2"));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let w = Warning::new(WarningKind::UnusedVariable, "test.c:2:7".to_string(), "unused variable \"a\"".to_string());
+        assert_eq!("test.c:2:7: warning: unused variable \"a\" [-Wunused-variable]", w.to_string());
+        assert_eq!("test.c:2:7: unused variable \"a\" [-Werror=unused-variable]", w.to_error());
+
+        // 位置不明
+        let w = Warning::new(WarningKind::ReturnType, String::new(), "control reaches end of non-void function \"f\"".to_string());
+        assert_eq!("warning: control reaches end of non-void function \"f\" [-Wreturn-type]", w.to_string());
+    }
+}